@@ -0,0 +1,77 @@
+//! Compares reading only the header of a file with reading the whole file.
+//! Run with `cargo bench --bench header`.
+use dstv::prelude::*;
+use std::time::{Duration, Instant};
+
+/// The number of times each file is read
+const ITERATIONS: u32 = 200;
+
+/// Builds a plate with a detailed contour and many holes, like a large nested part
+fn large_part() -> String {
+    let header = HeaderBuilder::new()
+        .order_identification("O1")
+        .piece_identification("P1")
+        .steel_quality("S355J2")
+        .length(12000.0)
+        .plate(2500.0, 20.0)
+        .build()
+        .unwrap();
+    let contour = (0..2000)
+        .map(|i| {
+            let x = 12000.0 * f64::from(i) / 2000.0;
+            (x, 2500.0 - f64::from(i % 7))
+        })
+        .chain(vec![(12000.0, 0.0), (0.0, 0.0)])
+        .collect::<Vec<_>>();
+    (0..2000)
+        .fold(
+            DstvBuilder::new(header).outer_contour(PartFace::Front, contour),
+            |builder, i| {
+                let (x, y) = (
+                    f64::from(i % 100) * 110.0 + 50.0,
+                    f64::from(i / 100) * 110.0 + 50.0,
+                );
+                builder.add_hole(PartFace::Front, x, y, 22.0)
+            },
+        )
+        .build()
+        .unwrap()
+        .to_dstv()
+}
+
+/// Returns the average time of reading a file
+fn time<T, F: Fn() -> T>(read: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        read();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let path = std::env::temp_dir().join("dstv-bench-large-part.nc");
+    std::fs::write(&path, large_part()).unwrap();
+    let files = vec![
+        ("large part".to_string(), path.clone()),
+        (
+            "0008-SE0004.nc1".to_string(),
+            "./tests/data/0008-SE0004.nc1".into(),
+        ),
+        ("P1565.nc".to_string(), "./tests/data/P1565.nc".into()),
+    ];
+    println!(
+        "{:<20}{:>16}{:>16}{:>10}",
+        "file", "from_file", "read_header", "speedup"
+    );
+    for (name, file) in &files {
+        let whole = time(|| Dstv::from_file(file).unwrap().elements.len());
+        let header = time(|| Dstv::read_header(file).unwrap().length);
+        println!(
+            "{name:<20}{:>16}{:>16}{:>9.1}x",
+            format!("{whole:?}"),
+            format!("{header:?}"),
+            whole.as_secs_f64() / header.as_secs_f64()
+        );
+    }
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+use dstv::prelude::*;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let source = String::from_utf8_lossy(data);
+    if let Ok(dstv) = Dstv::from_str(&source) {
+        // a parsed part is written back and drawn without panicking
+        let _ = Dstv::from_str(&dstv.to_dstv());
+        dstv.clone().to_svg();
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use dstv::prelude::*;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Header::from_reader(data);
+    for element in DstvReader::new(data) {
+        let _ = element;
+    }
+});
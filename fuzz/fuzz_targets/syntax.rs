@@ -0,0 +1,13 @@
+#![no_main]
+
+use dstv::prelude::*;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let source = String::from_utf8_lossy(data);
+    let mut tree = SyntaxTree::parse(&source);
+    assert_eq!(tree.to_string(), source);
+    if let Ok(dstv) = Dstv::from_syntax(&tree) {
+        tree.apply(&dstv);
+    }
+});
@@ -1,45 +1,137 @@
-use crate::dstv_element::ParseDstvError;
-use crate::get_f64_from_str;
-use crate::prelude::DstvElement;
-
-/// A bend is a circular arc.
-/// It is defined by the angle of the arc, the radius of the arc, and the start and end points of the arc.
-/// The start point is the origin of the arc.
-/// The end point is the point where the arc ends.
-/// The arc is drawn counter-clockwise from the origin to the end point.
-/// The arc is drawn clockwise from the end point to the origin.
-#[derive(Debug)]
+use crate::dstv_element::{Block, ParseDstvError};
+use crate::geometry::{bounding_box, Point, EPSILON};
+use crate::prelude::{Degrees, DstvElement, Millimetres, PartFace};
+use crate::{get_f64_from_str, get_str_from_f64};
+use std::{
+    fmt::{self, Write},
+    str::FromStr,
+};
+
+/// A bend line on a plate.
+/// The contour of a bent plate is its flat pattern, and the bend line runs through the
+/// middle of the bend zone, from the origin to the finish point.
+/// The angle is the angle the plate is bent over, positive when bending up towards the
+/// viewer of the face and negative when bending down.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bend {
-    /// The angle of the arc in degrees.
-    pub angle: f64,
-    /// The radius of the arc.
-    pub radius: f64,
-    /// The x-coordinate of the end point of the arc.
-    pub finish_x: f64,
-    /// The y-coordinate of the end point of the arc.
-    pub finish_y: f64,
-    /// The x-coordinate of the origin of the arc.
-    pub origin_x: f64,
-    /// The y-coordinate of the origin of the arc.
-    pub origin_y: f64,
+    /// The flange code of the face the bend line is on
+    pub fl_code: PartFace,
+    /// The angle of the bend in degrees.
+    pub angle: Degrees,
+    /// The inner radius of the bend.
+    pub radius: Millimetres,
+    /// The x-coordinate of the end point of the bend line.
+    pub finish_x: Millimetres,
+    /// The y-coordinate of the end point of the bend line.
+    pub finish_y: Millimetres,
+    /// The x-coordinate of the origin of the bend line.
+    pub origin_x: Millimetres,
+    /// The y-coordinate of the origin of the bend line.
+    pub origin_y: Millimetres,
+}
+
+/// The direction of a bend, seen from the face of the bend line
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BendDirection {
+    /// The plate is bent towards the viewer
+    Up,
+    /// The plate is bent away from the viewer
+    Down,
+}
+
+impl Bend {
+    /// Returns the start point of the bend line
+    pub fn origin(&self) -> Point {
+        Point::new(*self.origin_x, *self.origin_y)
+    }
+
+    /// Returns the end point of the bend line
+    pub fn finish(&self) -> Point {
+        Point::new(*self.finish_x, *self.finish_y)
+    }
+
+    /// Returns the length of the bend line
+    pub fn length(&self) -> f64 {
+        self.origin().distance(&self.finish())
+    }
+
+    /// Returns the direction of the bend
+    pub fn direction(&self) -> BendDirection {
+        match self.angle < 0.0 {
+            true => BendDirection::Down,
+            false => BendDirection::Up,
+        }
+    }
+
+    /// Returns the length of the neutral fibre through the bend zone, which the flat
+    /// pattern needs for the bend
+    /// # Arguments
+    /// * `thickness` - The thickness of the plate
+    /// * `k_factor` - The position of the neutral fibre as a fraction of the thickness,
+    ///   measured from the inside of the bend, usually between 0.3 and 0.5
+    pub fn allowance(&self, thickness: f64, k_factor: f64) -> f64 {
+        self.angle.abs().to_radians() * (*self.radius + k_factor * thickness)
+    }
+
+    /// Returns how much shorter the flat pattern is than the sum of the outside
+    /// lengths of both legs of the formed part
+    /// # Arguments
+    /// * `thickness` - The thickness of the plate
+    /// * `k_factor` - The position of the neutral fibre as a fraction of the thickness
+    pub fn deduction(&self, thickness: f64, k_factor: f64) -> f64 {
+        let setback = (*self.radius + thickness) * (self.angle.abs().to_radians() / 2.0).tan();
+        2.0 * setback - self.allowance(thickness, k_factor)
+    }
+
+    /// Returns the signed distance of a point on the face to the bend line, positive on
+    /// the right of the line looking from the origin to the finish point
+    pub fn distance(&self, point: &Point) -> f64 {
+        let length = self.length();
+        if length <= EPSILON {
+            return point.distance(&self.origin());
+        }
+        let (dx, dy) = (
+            (self.finish_x - self.origin_x).value() / length,
+            (self.finish_y - self.origin_y).value() / length,
+        );
+        (point.x - *self.origin_x) * dy - (point.y - *self.origin_y) * dx
+    }
+
+    /// Describes the bend for the press brake, e.g. `up 90° R5`
+    pub fn label(&self) -> String {
+        let direction = match self.direction() {
+            BendDirection::Up => "up",
+            BendDirection::Down => "down",
+        };
+        format!("{direction} {}° R{}", self.angle.abs(), self.radius)
+    }
 }
 
 impl DstvElement for Bend {
-    /// Create a new bend from a string slice.
-    /// The string slice is expected to be a line from a DSTV file.
+    /// Create a new bend from its block.
+    /// The values of the bend are read from the first line of the block.
     /// # Arguments
-    /// * `line` - A string slice containing a line from a DSTV file.
+    /// * `block` - A block of a DSTV file holding a bend.
     /// # Returns
-    /// A Result containing either a Bend or a &'static str.
-    fn from_str(line: &str) -> Result<Self, ParseDstvError> {
-        let mut iter = line.split_whitespace();
-        let origin_x = get_f64_from_str(iter.next(), "origin_x")?;
-        let origin_y = get_f64_from_str(iter.next(), "origin_y")?;
-        let angle = get_f64_from_str(iter.next(), "angle")?;
-        let radius = get_f64_from_str(iter.next(), "radius")?;
-        let finish_x = get_f64_from_str(iter.next(), "finish_x")?;
-        let finish_y = get_f64_from_str(iter.next(), "finish_y")?;
+    /// A `Result` containing either a `Bend` or an error message
+    fn from_block(block: &Block) -> Result<Self, ParseDstvError> {
+        let mut iter = block.first_line().split_whitespace().peekable();
+        let fl_code = match iter.peek().map(|code| PartFace::from_str(code)) {
+            Some(Ok(fl_code)) => {
+                iter.next();
+                fl_code
+            }
+            _ => PartFace::Front,
+        };
+        let origin_x = get_f64_from_str(iter.next(), "origin_x")?.into();
+        let origin_y = get_f64_from_str(iter.next(), "origin_y")?.into();
+        let angle = get_f64_from_str(iter.next(), "angle")?.into();
+        let radius = get_f64_from_str(iter.next(), "radius")?.into();
+        let finish_x = get_f64_from_str(iter.next(), "finish_x")?.into();
+        let finish_y = get_f64_from_str(iter.next(), "finish_y")?.into();
         Ok(Self {
+            fl_code,
             angle,
             radius,
             finish_x,
@@ -49,21 +141,54 @@ impl DstvElement for Bend {
         })
     }
 
+    fn write(&self, out: &mut dyn Write) -> fmt::Result {
+        write!(
+            out,
+            "KA\n  {}{}{}{}{}{}{}\n",
+            self.fl_code.code(),
+            get_str_from_f64(self.origin_x),
+            get_str_from_f64(self.origin_y),
+            get_str_from_f64(self.angle),
+            get_str_from_f64(self.radius),
+            get_str_from_f64(self.finish_x),
+            get_str_from_f64(self.finish_y)
+        )
+    }
+
     fn get_index(&self) -> usize {
         2
     }
 
-    fn get_facing(&self) -> &crate::prelude::PartFace {
-        &crate::prelude::PartFace::Top
+    fn get_facing(&self) -> &PartFace {
+        &self.fl_code
+    }
+
+    fn position(&self) -> Option<Point> {
+        Some(self.origin())
+    }
+
+    fn bounding_box(&self) -> Option<(Point, Point)> {
+        bounding_box(&[self.origin(), self.finish()])
     }
 
-    /// Convert the bend to an SVG path.
+    /// Convert the bend to a dashed bend line with its angle, radius and direction.
+    /// Bends up are drawn in blue, bends down in red.
     /// # Returns
-    /// A string containing an SVG path.
+    /// A string containing the SVG line and label.
     fn to_svg(&self) -> String {
+        let color = match self.direction() {
+            BendDirection::Up => "blue",
+            BendDirection::Down => "red",
+        };
         format!(
-            "<path d=\"M{},{} A{},{},0,0,1,{},{}\" stroke=\"black\" fill=\"none\" />",
-            self.origin_x, self.origin_y, self.radius, self.radius, self.finish_x, self.finish_y
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{color}\" stroke-dasharray=\"10 5\" /><text x=\"{}\" y=\"{}\" fill=\"{color}\" font-size=\"10\">{}</text>",
+            self.origin_x,
+            self.origin_y,
+            self.finish_x,
+            self.finish_y,
+            (self.origin_x + self.finish_x) / 2.0,
+            (self.origin_y + self.finish_y) / 2.0,
+            self.label()
         )
     }
 }
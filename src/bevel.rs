@@ -0,0 +1,181 @@
+use crate::{
+    dstv_element::ParseDstvError,
+    get_f64_from_str, get_str_from_f64,
+    prelude::{Degrees, Millimetres},
+};
+use std::fmt::Display;
+
+/// The shape of a weld preparation on an edge
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BevelKind {
+    /// A bevel on one side through the full thickness
+    V,
+    /// A bevel on one side down to a depth, leaving a root face
+    Y,
+    /// A double-V preparation: both sides are bevelled symmetrically about the middle
+    /// of the material, with the same angle and depth
+    X,
+    /// A double-bevel preparation: both sides are bevelled, but not symmetrically, so
+    /// the root face is off the middle of the material
+    K,
+}
+
+impl Display for BevelKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let kind = match self {
+            BevelKind::V => "V",
+            BevelKind::Y => "Y",
+            BevelKind::X => "X",
+            BevelKind::K => "K",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+/// The weld preparation of a contour edge, read from the values after the radius of
+/// a contour point. It applies to the edge from that point to the next one.
+/// The top side is the side of the face the contour is given on, the bottom side the
+/// opposite side of the material. Angles are measured from a square edge in degrees,
+/// depths from the surface of their side. A depth of zero bevels through the material
+/// that is left. A negative angle in the file bevels the other side of the material,
+/// and is read as a positive angle on that side.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bevel {
+    /// The angle of the bevel on the top side
+    pub top_angle: Degrees,
+    /// The depth of the bevel on the top side
+    pub top_depth: Millimetres,
+    /// The angle of the bevel on the bottom side
+    pub bottom_angle: Degrees,
+    /// The depth of the bevel on the bottom side
+    pub bottom_depth: Millimetres,
+}
+
+impl Bevel {
+    /// Reads a bevel from the values following the radius of a contour point
+    /// # Arguments
+    /// * `values` - The top angle, top depth, bottom angle and bottom depth, of which
+    ///   the missing trailing ones are zero
+    /// # Returns
+    /// The bevel, or `None` if no side is bevelled
+    /// # Error
+    /// * If one of the values is not a number
+    /// * If the signs of the angles put both bevels on the same side
+    pub fn from_values<'a, I>(values: I) -> Result<Option<Self>, ParseDstvError>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut values = values.into_iter();
+        let top_angle = get_f64_from_str(values.next(), "bevel top angle")?;
+        let top_depth = get_f64_from_str(values.next(), "bevel top depth")?;
+        let bottom_angle = get_f64_from_str(values.next(), "bevel bottom angle")?;
+        let bottom_depth = get_f64_from_str(values.next(), "bevel bottom depth")?;
+        let mut bevel = Self::default();
+        for (top, angle, depth) in [
+            (true, top_angle, top_depth),
+            (false, bottom_angle, bottom_depth),
+        ] {
+            if angle == 0.0 {
+                continue;
+            }
+            // a negative angle bevels the other side of the material
+            let (side_angle, side_depth) = match top == (angle > 0.0) {
+                true => (&mut bevel.top_angle, &mut bevel.top_depth),
+                false => (&mut bevel.bottom_angle, &mut bevel.bottom_depth),
+            };
+            if *side_angle != 0.0 {
+                return Err(ParseDstvError::new(format!(
+                    "Both bevels are on the same side: {top_angle} and {bottom_angle}"
+                )));
+            }
+            *side_angle = Degrees(angle.abs());
+            *side_depth = Millimetres(depth);
+        }
+        Ok(match bevel.is_top() || bevel.is_bottom() {
+            true => Some(bevel),
+            false => None,
+        })
+    }
+
+    /// Writes the values of the bevel for a contour line, leaving out trailing zeros
+    pub fn to_dstv(&self) -> String {
+        let values = [
+            *self.top_angle,
+            *self.top_depth,
+            *self.bottom_angle,
+            *self.bottom_depth,
+        ];
+        let count = values
+            .iter()
+            .rposition(|value| *value != 0.0)
+            .map_or(0, |i| i + 1);
+        values[..count]
+            .iter()
+            .map(|value| get_str_from_f64(*value))
+            .collect()
+    }
+
+    /// Returns true if the top side is bevelled
+    pub fn is_top(&self) -> bool {
+        self.top_angle != 0.0
+    }
+
+    /// Returns true if the bottom side is bevelled
+    pub fn is_bottom(&self) -> bool {
+        self.bottom_angle != 0.0
+    }
+
+    /// Returns the shape of the weld preparation
+    pub fn kind(&self) -> BevelKind {
+        match (self.is_top(), self.is_bottom()) {
+            (true, true)
+                if self.top_angle == self.bottom_angle && self.top_depth == self.bottom_depth =>
+            {
+                BevelKind::X
+            }
+            (true, true) => BevelKind::K,
+            (true, false) if self.top_depth == 0.0 => BevelKind::V,
+            (false, true) if self.bottom_depth == 0.0 => BevelKind::V,
+            _ => BevelKind::Y,
+        }
+    }
+
+    /// Returns the height of the square part of the edge that is left between the bevels
+    /// # Arguments
+    /// * `thickness` - The thickness of the material at the edge
+    pub fn root_face(&self, thickness: f64) -> f64 {
+        let top = if self.is_top() { *self.top_depth } else { 0.0 };
+        let bottom = if self.is_bottom() {
+            *self.bottom_depth
+        } else {
+            0.0
+        };
+        match self.kind() {
+            BevelKind::V => 0.0,
+            BevelKind::X | BevelKind::K if top == 0.0 || bottom == 0.0 => 0.0,
+            _ => (thickness - top - bottom).max(0.0),
+        }
+    }
+}
+
+impl Display for Bevel {
+    /// Describes the bevel for the bevel cutter, e.g. `V 30°`, `Y 30° 8` or
+    /// `K 45° 10/30° 5`, listing the top side first
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let side = |angle: Degrees, depth: Millimetres| match depth != 0.0 {
+            true => format!("{angle}° {depth}"),
+            false => format!("{angle}°"),
+        };
+        let sides = [
+            (self.is_top(), side(self.top_angle, self.top_depth)),
+            (self.is_bottom(), side(self.bottom_angle, self.bottom_depth)),
+        ]
+        .iter()
+        .filter(|(bevelled, _)| *bevelled)
+        .map(|(_, side)| side.clone())
+        .collect::<Vec<_>>();
+        write!(f, "{} {}", self.kind(), sides.join("/"))
+    }
+}
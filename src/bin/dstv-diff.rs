@@ -0,0 +1,56 @@
+//! Compares two DSTV files and prints the changes.
+//!
+//! Usage: `dstv-diff OLD NEW [--svg OUT] [--tolerance MM] [--max-move MM]`
+//!
+//! Exits with 0 if the files are the same, 1 if they differ and 2 on errors.
+use dstv::prelude::*;
+use std::process::exit;
+
+const USAGE: &str = "Usage: dstv-diff OLD NEW [--svg OUT] [--tolerance MM] [--max-move MM]";
+
+/// Parses the next argument as a length in mm
+fn next_f64(args: &mut impl Iterator<Item = String>, name: &str) -> Result<f64, String> {
+    args.next()
+        .and_then(|value| value.parse().ok())
+        .ok_or(format!("Expected a number after `{name}`"))
+}
+
+fn run() -> Result<bool, String> {
+    let mut args = std::env::args().skip(1);
+    let mut files = vec![];
+    let mut svg = None;
+    let mut options = DiffOptions::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--svg" => svg = Some(args.next().ok_or("Expected a path after `--svg`")?),
+            "--tolerance" => options.tolerance = next_f64(&mut args, &arg)?,
+            "--max-move" => options.max_move = next_f64(&mut args, &arg)?,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                exit(0);
+            }
+            _ => files.push(arg),
+        }
+    }
+    if files.len() != 2 {
+        return Err(USAGE.to_string());
+    }
+    let old = Dstv::from_file(&files[0]).map_err(|e| format!("{}: {e}", files[0]))?;
+    let new = Dstv::from_file(&files[1]).map_err(|e| format!("{}: {e}", files[1]))?;
+    let diff = old.diff(&new, &options);
+    print!("{diff}");
+    if let Some(path) = svg {
+        std::fs::write(&path, diff.to_svg(&new)).map_err(|e| format!("{path}: {e}"))?;
+    }
+    Ok(!diff.is_empty())
+}
+
+fn main() {
+    match run() {
+        Ok(changed) => exit(changed as i32),
+        Err(e) => {
+            eprintln!("{e}");
+            exit(2);
+        }
+    }
+}
@@ -1,13 +1,18 @@
 use crate::{
-    dstv_element::{DstvElement, ParseDstvError},
-    get_f64_from_str,
-    prelude::PartFace,
+    dstv_element::{Block, DstvElement, ParseDstvError},
+    geometry::{self, Point, Segment},
+    get_f64_from_str, get_str_from_f64,
+    prelude::{Bevel, Millimetres, PartFace},
+};
+use std::{
+    fmt::{self, Write},
+    str::FromStr,
 };
-use std::str::FromStr;
 
 /// A struct representing the outer border of a DSTV file
 /// A DSTV file can have multiple outer borders
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OuterBorder {
     /// A vector of border points, representing the contour of the outer border
     pub contour: Vec<BorderPoint>,
@@ -15,7 +20,8 @@ pub struct OuterBorder {
 
 /// A struct representing the inner border of a DSTV file
 /// A DSTV file can have multiple inner borders
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InnerBorder {
     /// A vector of border points, representing the contour of the inner border
     pub contour: Vec<BorderPoint>,
@@ -24,18 +30,19 @@ pub struct InnerBorder {
 /// A struct representing a border point
 /// A border point is a point on the contour of a border
 /// It has an x and y coordinate and a radius
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BorderPoint {
     /// The flange code of the border point
     pub fl_code: PartFace,
     /// The x coordinate of the border point
-    pub x_coord: f64,
+    pub x_coord: Millimetres,
     /// The y coordinate of the border point
-    pub y_coord: f64,
+    pub y_coord: Millimetres,
     /// The radius of the border point
-    pub radius: f64,
-    /// The bevel of the border point between this and the next point.
-    pub bevel: f64,
+    pub radius: Millimetres,
+    /// The weld preparation of the edge between this and the next point, if any
+    pub bevel: Option<Bevel>,
 }
 
 /// Reads the contour of a border from a DSTV file.
@@ -49,6 +56,7 @@ pub struct BorderPoint {
 /// * If the x coordinate of a border point is invalid
 /// * If the y coordinate of a border point is invalid
 /// * If the radius of a border point is invalid
+/// * If a value of the bevel of a border point is invalid
 fn read_contour(lines: &[&str]) -> Result<Vec<BorderPoint>, ParseDstvError> {
     lines
         .iter()
@@ -64,13 +72,10 @@ fn read_contour(lines: &[&str]) -> Result<Vec<BorderPoint>, ParseDstvError> {
                 Err(_) => PartFace::Front,
             };
 
-            let x_coord = get_f64_from_str(iter.next(), "x_coord")?;
-            let y_coord = get_f64_from_str(iter.next(), "y_coord")?;
-            let radius = get_f64_from_str(iter.next(), "radius")?;
-            let bevel = match iter.next() {
-                Some(val) => get_f64_from_str(Some(val), "bevel")?,
-                _ => 0.0,
-            };
+            let x_coord = get_f64_from_str(iter.next(), "x_coord")?.into();
+            let y_coord = get_f64_from_str(iter.next(), "y_coord")?.into();
+            let radius = get_f64_from_str(iter.next(), "radius")?.into();
+            let bevel = Bevel::from_values(iter)?;
             Ok(BorderPoint {
                 fl_code,
                 x_coord,
@@ -107,16 +112,23 @@ fn contour_to_svg(contour: &[BorderPoint], color: &str, stroke_width: f64) -> St
                     r if r > 0.0 && prev.y_coord > point.y_coord && point.x_coord < prev.x_coord => {
                         format!(" Q {} {} {} {}", point.x_coord, prev.y_coord, point.x_coord, point.y_coord)
                     },
-                    r if r > 0.0 || r < 0.0 => {
+                    r if r != 0.0 => {
                         format!(" A {} {} 0 0 0 {} {}", -prev.radius, -prev.radius, point.x_coord, point.y_coord)
                     },
                     _ => {
                     format!(" L {} {}", point.x_coord, point.y_coord)                   }
                 }};
-            if prev.bevel > 0.0 {
+            // the bevel is annotated with its kind, angles and depths in the middle of the edge
+            if let Some(bevel) = prev.bevel {
                 let bevel_line = format!(
-                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"red\" stroke-width=\"4\" />",
-                    prev.x_coord, prev.y_coord, point.x_coord, point.y_coord
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"red\" stroke-width=\"4\" /><text x=\"{}\" y=\"{}\" fill=\"red\" font-size=\"10\">{}</text>",
+                    prev.x_coord,
+                    prev.y_coord,
+                    point.x_coord,
+                    point.y_coord,
+                    (prev.x_coord + point.x_coord) / 2.0,
+                    (prev.y_coord + point.y_coord) / 2.0,
+                    bevel
                 );
                 bevel_lines.push(bevel_line);
             }
@@ -134,65 +146,127 @@ fn contour_to_svg(contour: &[BorderPoint], color: &str, stroke_width: f64) -> St
     )
 }
 
-impl OuterBorder {
-    /// Creates a new OuterBorder from a vector of BorderPoints
-    /// # Arguments
-    /// * `lines` - A vector of string slices, representing the contour of the border
-    pub fn from_lines(lines: &[&str]) -> Result<Self, ParseDstvError> {
-        Ok(Self {
-            contour: read_contour(lines)?,
+/// Converts a contour to the data lines of a border block
+/// # Arguments
+/// * `contour` - A vector of BorderPoints, representing the contour of a border
+/// # Returns
+/// A string holding a line for each point of the contour
+fn contour_to_dstv(contour: &[BorderPoint]) -> String {
+    contour
+        .iter()
+        .map(|point| {
+            let bevel = point.bevel.map(|bevel| bevel.to_dstv()).unwrap_or_default();
+            format!(
+                "  {}{}{}{}{}\n",
+                point.fl_code.code(),
+                get_str_from_f64(point.x_coord),
+                get_str_from_f64(point.y_coord),
+                get_str_from_f64(point.radius),
+                bevel
+            )
         })
-    }
+        .collect()
 }
 
-impl InnerBorder {
-    /// Creates a new InnerBorder from a vector of BorderPoints
-    /// # Arguments
-    /// * `lines` - A vector of string slices, representing the contour of the border
-    pub fn from_lines(lines: &[&str]) -> Result<Self, ParseDstvError> {
-        Ok(Self {
-            contour: read_contour(lines)?,
-        })
-    }
-}
-impl DstvElement for OuterBorder {
-    /// Converts the outer border to an SVG path
-    /// # Returns
-    /// A string representing the SVG path of the outer border
-    fn to_svg(&self) -> String {
-        contour_to_svg(&self.contour, "grey", 0.5)
-    }
+/// The geometry of a border, computed from its contour. Outer and inner borders share
+/// it, so both measure their contour the same way.
+pub trait Border {
+    /// Returns the points of the contour
+    fn contour(&self) -> &[BorderPoint];
 
-    fn from_str(_line: &str) -> Result<Self, ParseDstvError> {
-        todo!("Find out how to split traits and casts when when calling in a idiomatic way");
+    /// Returns the segments of the contour, closing it if needed
+    fn segments(&self) -> Vec<Segment> {
+        geometry::contour_segments(self.contour())
     }
 
-    fn get_index(&self) -> usize {
-        0
+    /// Returns the area enclosed by the contour, following arcs exactly
+    fn area(&self) -> f64 {
+        geometry::signed_area(&self.segments()).abs()
     }
 
-    fn get_facing(&self) -> &PartFace {
-        &self.contour[0].fl_code
+    /// Returns the length of the contour, which equals the cut length of the border
+    fn perimeter(&self) -> f64 {
+        geometry::perimeter(&self.segments())
     }
-}
 
-impl DstvElement for InnerBorder {
-    /// Converts the inner border to an SVG path
+    /// Returns the centroid of the area enclosed by the contour
     /// # Returns
-    /// A string representing the SVG path of the inner border
-    fn to_svg(&self) -> String {
-        contour_to_svg(&self.contour, "white", 0.5)
+    /// The centroid, or `None` if the contour does not enclose any area
+    fn centroid(&self) -> Option<Point> {
+        geometry::centroid(&self.segments())
     }
 
-    fn from_str(_line: &str) -> Result<Self, ParseDstvError> {
-        todo!("Find out how to split traits and casts when when calling in a idiomatic way");
+    /// Returns the contour as a polygon, with arcs approximated by chords
+    fn polygon(&self) -> Vec<Point> {
+        geometry::flatten(&self.segments(), geometry::FLATTEN_TOLERANCE)
     }
 
-    fn get_index(&self) -> usize {
-        1
+    /// Checks whether a point lies within the contour
+    /// # Arguments
+    /// * `point` - The point to check, in the coordinates of the face of the border
+    fn contains(&self, point: &Point) -> bool {
+        geometry::polygon_contains(&self.polygon(), point)
     }
+}
 
-    fn get_facing(&self) -> &PartFace {
-        &self.contour[0].fl_code
+impl Border for OuterBorder {
+    fn contour(&self) -> &[BorderPoint] {
+        &self.contour
     }
 }
+
+impl Border for InnerBorder {
+    fn contour(&self) -> &[BorderPoint] {
+        &self.contour
+    }
+}
+
+/// Implements `DstvElement` for a border, which is written as a block holding a line
+/// for each point of its contour
+macro_rules! border_element {
+    ($name:ident, $code:literal, $index:literal, $color:literal) => {
+        impl DstvElement for $name {
+            /// Converts the border to an SVG path
+            /// # Returns
+            /// A string representing the SVG path of the border
+            fn to_svg(&self) -> String {
+                contour_to_svg(&self.contour, $color, 0.5)
+            }
+
+            fn write(&self, out: &mut dyn Write) -> fmt::Result {
+                write!(out, concat!($code, "\n{}"), contour_to_dstv(&self.contour))
+            }
+
+            /// Parses a border from the lines of its block, one line for each point of
+            /// the contour
+            fn from_block(block: &Block) -> Result<Self, ParseDstvError> {
+                Ok(Self {
+                    contour: read_contour(block.lines)?,
+                })
+            }
+
+            fn get_index(&self) -> usize {
+                $index
+            }
+
+            /// Returns the face of the first point, or the front of an empty contour
+            fn get_facing(&self) -> &PartFace {
+                self.contour
+                    .first()
+                    .map_or(&PartFace::Front, |point| &point.fl_code)
+            }
+
+            /// Returns the centroid of the area enclosed by the contour
+            fn position(&self) -> Option<Point> {
+                self.centroid()
+            }
+
+            fn bounding_box(&self) -> Option<(Point, Point)> {
+                geometry::bounding_box(&self.polygon())
+            }
+        }
+    };
+}
+
+border_element!(OuterBorder, "AK", 0, "grey");
+border_element!(InnerBorder, "IK", 1, "white");
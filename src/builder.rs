@@ -0,0 +1,508 @@
+use crate::prelude::{
+    BorderPoint, CodeProfile, Degrees, Dstv, DstvElementType, Finding, Header, Hole, InnerBorder,
+    KilogramsPerMetre, Millimetres, Numeration, OuterBorder, PartFace, Severity, Slot,
+    SquareMetresPerMetre, STEEL_DENSITY,
+};
+use std::fmt::{self, Display};
+
+/// The value used for identifications that are not given
+const NOT_AVAILABLE: &str = "NA";
+/// The default letter height of marks in mm
+const MARK_HEIGHT: f64 = 10.0;
+
+/// An error building a header or a DSTV file, naming what is missing or invalid
+#[derive(Clone, Debug, PartialEq)]
+pub enum BuildDstvError {
+    /// A header field is missing or invalid
+    Header {
+        /// The name of the field
+        field: &'static str,
+        /// What the field must be
+        requirement: &'static str,
+    },
+    /// The text of a mark is empty or contains whitespace
+    MarkText(String),
+    /// The piece does not pass validation, with the findings of error severity
+    Validation(Vec<Finding>),
+}
+
+impl Display for BuildDstvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildDstvError::Header { field, requirement } => {
+                write!(f, "header: `{field}` must be {requirement}")
+            }
+            BuildDstvError::MarkText(text) => write!(f, "mark: invalid text `{text}`"),
+            BuildDstvError::Validation(findings) => {
+                write!(f, "piece does not pass validation")?;
+                findings
+                    .iter()
+                    .try_for_each(|finding| write!(f, "\n{finding}"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildDstvError {}
+
+impl From<(f64, f64)> for BorderPoint {
+    /// Creates a border point on the front face without a radius
+    fn from((x_coord, y_coord): (f64, f64)) -> Self {
+        Self {
+            x_coord: x_coord.into(),
+            y_coord: y_coord.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<(f64, f64, f64)> for BorderPoint {
+    /// Creates a border point on the front face with a radius towards the next point
+    fn from((x_coord, y_coord, radius): (f64, f64, f64)) -> Self {
+        Self {
+            x_coord: x_coord.into(),
+            y_coord: y_coord.into(),
+            radius: radius.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A builder for the header of a DSTV file.
+/// Identifications that are not given default to `NA`, the quantity to 1 and the steel
+/// quality to `S235JR`.
+#[derive(Clone, Debug)]
+pub struct HeaderBuilder {
+    header: Header,
+}
+
+impl Default for HeaderBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeaderBuilder {
+    /// Creates a new header builder for a plate without dimensions
+    pub fn new() -> Self {
+        Self {
+            header: Header {
+                order_identification: NOT_AVAILABLE.to_string(),
+                drawing_identification: NOT_AVAILABLE.to_string(),
+                phase_identification: NOT_AVAILABLE.to_string(),
+                piece_identification: String::new(),
+                steel_quality: "S235JR".to_string(),
+                quantity_of_pieces: 1,
+                profile: String::new(),
+                code_profile: CodeProfile::B,
+                length: Default::default(),
+                saw_length: None,
+                profile_height: Default::default(),
+                flange_width: Default::default(),
+                flange_thickness: Default::default(),
+                web_thickness: Default::default(),
+                radius: Default::default(),
+                weight_by_meter: Default::default(),
+                painting_surface_by_meter: Default::default(),
+                web_start_cut: Default::default(),
+                web_end_cut: Default::default(),
+                flange_start_cut: Default::default(),
+                flange_end_cut: Default::default(),
+                text1_info_on_piece: String::new(),
+                text2_info_on_piece: String::new(),
+                text3_info_on_piece: String::new(),
+                text4_info_on_piece: String::new(),
+            },
+        }
+    }
+
+    /// Sets the order identification
+    pub fn order_identification<S: AsRef<str>>(mut self, value: S) -> Self {
+        self.header.order_identification = value.as_ref().to_string();
+        self
+    }
+
+    /// Sets the drawing identification
+    pub fn drawing_identification<S: AsRef<str>>(mut self, value: S) -> Self {
+        self.header.drawing_identification = value.as_ref().to_string();
+        self
+    }
+
+    /// Sets the phase identification
+    pub fn phase_identification<S: AsRef<str>>(mut self, value: S) -> Self {
+        self.header.phase_identification = value.as_ref().to_string();
+        self
+    }
+
+    /// Sets the piece identification, which is required
+    pub fn piece_identification<S: AsRef<str>>(mut self, value: S) -> Self {
+        self.header.piece_identification = value.as_ref().to_string();
+        self
+    }
+
+    /// Sets the steel quality, e.g. `S355J2+N`
+    pub fn steel_quality<S: AsRef<str>>(mut self, value: S) -> Self {
+        self.header.steel_quality = value.as_ref().to_string();
+        self
+    }
+
+    /// Sets the quantity of pieces
+    pub fn quantity(mut self, quantity: i32) -> Self {
+        self.header.quantity_of_pieces = quantity;
+        self
+    }
+
+    /// Sets the length of the piece in mm
+    pub fn length(mut self, length: f64) -> Self {
+        self.header.length = length.into();
+        self
+    }
+
+    /// Sets the saw length of the piece in mm
+    pub fn saw_length(mut self, saw_length: f64) -> Self {
+        self.header.saw_length = Some(saw_length.into());
+        self
+    }
+
+    /// Makes the piece a plate. The profile name, the dimensions, the weight by meter and
+    /// the painting surface by meter are derived from the width and the thickness.
+    /// # Arguments
+    /// * `width` - The width of the plate in mm
+    /// * `thickness` - The thickness of the plate in mm
+    pub fn plate(mut self, width: f64, thickness: f64) -> Self {
+        self.header.profile = format!("PL{thickness}*{width}");
+        self.header.code_profile = CodeProfile::B;
+        self.header.profile_height = width.into();
+        self.header.flange_width = thickness.into();
+        self.header.flange_thickness = thickness.into();
+        self.header.web_thickness = thickness.into();
+        self.header.radius = Millimetres(0.0);
+        self.header.weight_by_meter = KilogramsPerMetre(width * thickness * STEEL_DENSITY * 1e-6);
+        self.header.painting_surface_by_meter =
+            SquareMetresPerMetre(2.0 * (width + thickness) / 1000.0);
+        self
+    }
+
+    /// Sets the profile of the piece
+    /// # Arguments
+    /// * `name` - The name of the profile, e.g. `HEA200`
+    /// * `code_profile` - The type of the profile
+    pub fn profile<S: AsRef<str>>(mut self, name: S, code_profile: CodeProfile) -> Self {
+        self.header.profile = name.as_ref().to_string();
+        self.header.code_profile = code_profile;
+        self
+    }
+
+    /// Sets the dimensions of the profile in mm
+    /// # Arguments
+    /// * `profile_height` - The height of the profile
+    /// * `flange_width` - The width of the flanges
+    /// * `flange_thickness` - The thickness of the flanges
+    /// * `web_thickness` - The thickness of the web
+    /// * `radius` - The root radius
+    pub fn dimensions(
+        mut self,
+        profile_height: f64,
+        flange_width: f64,
+        flange_thickness: f64,
+        web_thickness: f64,
+        radius: f64,
+    ) -> Self {
+        self.header.profile_height = profile_height.into();
+        self.header.flange_width = flange_width.into();
+        self.header.flange_thickness = flange_thickness.into();
+        self.header.web_thickness = web_thickness.into();
+        self.header.radius = radius.into();
+        self
+    }
+
+    /// Sets the weight by meter in kg/m
+    pub fn weight_by_meter(mut self, weight_by_meter: f64) -> Self {
+        self.header.weight_by_meter = weight_by_meter.into();
+        self
+    }
+
+    /// Sets the painting surface by meter in m²/m
+    pub fn painting_surface_by_meter(mut self, painting_surface_by_meter: f64) -> Self {
+        self.header.painting_surface_by_meter = painting_surface_by_meter.into();
+        self
+    }
+
+    /// Sets the angles of the cuts at the start and the end of the web and the flanges
+    /// in degrees
+    pub fn end_cuts(
+        mut self,
+        web_start_cut: f64,
+        web_end_cut: f64,
+        flange_start_cut: f64,
+        flange_end_cut: f64,
+    ) -> Self {
+        self.header.web_start_cut = web_start_cut.into();
+        self.header.web_end_cut = web_end_cut.into();
+        self.header.flange_start_cut = flange_start_cut.into();
+        self.header.flange_end_cut = flange_end_cut.into();
+        self
+    }
+
+    /// Sets one of the four text lines of the piece
+    /// # Arguments
+    /// * `line` - The number of the text line, from 1 to 4. Other numbers are ignored.
+    /// * `text` - The text
+    pub fn text<S: AsRef<str>>(mut self, line: usize, text: S) -> Self {
+        let text = text.as_ref().to_string();
+        match line {
+            1 => self.header.text1_info_on_piece = text,
+            2 => self.header.text2_info_on_piece = text,
+            3 => self.header.text3_info_on_piece = text,
+            4 => self.header.text4_info_on_piece = text,
+            _ => {}
+        }
+        self
+    }
+
+    /// Validates and builds the header
+    /// # Returns
+    /// The header, or an error if a required value is missing or invalid
+    /// # Error
+    /// * If an identification, the steel quality or the profile is empty or spans lines
+    /// * If the quantity or the length is not positive
+    /// * If a dimension the profile requires is not positive
+    pub fn build(self) -> Result<Header, BuildDstvError> {
+        let header = self.header;
+        for (field, value) in [
+            ("order_identification", &header.order_identification),
+            ("drawing_identification", &header.drawing_identification),
+            ("phase_identification", &header.phase_identification),
+            ("piece_identification", &header.piece_identification),
+            ("steel_quality", &header.steel_quality),
+            ("profile", &header.profile),
+        ] {
+            if value.trim().is_empty() || value.contains(['\n', '\r']) {
+                return Err(BuildDstvError::Header {
+                    field,
+                    requirement: "a single, non-empty line",
+                });
+            }
+        }
+        if header.quantity_of_pieces < 1 {
+            return Err(BuildDstvError::Header {
+                field: "quantity_of_pieces",
+                requirement: "at least 1",
+            });
+        }
+        let required = match header.code_profile {
+            CodeProfile::B | CodeProfile::RU | CodeProfile::RO => {
+                vec![("profile_height", header.profile_height)]
+            }
+            _ => vec![
+                ("profile_height", header.profile_height),
+                ("flange_width", header.flange_width),
+                ("flange_thickness", header.flange_thickness),
+                ("web_thickness", header.web_thickness),
+            ],
+        };
+        for (field, value) in [("length", header.length)].into_iter().chain(required) {
+            if value.is_nan() || value <= 0.0 {
+                return Err(BuildDstvError::Header {
+                    field,
+                    requirement: "positive",
+                });
+            }
+        }
+        Ok(header)
+    }
+}
+
+/// A builder for DSTV files.
+/// Elements are added in the order they are written. Plates without an outer contour
+/// on the front face get a rectangular contour of their length and width.
+#[derive(Clone, Debug)]
+pub struct DstvBuilder {
+    header: Header,
+    elements: Vec<DstvElementType>,
+}
+
+impl DstvBuilder {
+    /// Creates a new builder for a piece
+    /// # Arguments
+    /// * `header` - The header of the piece, e.g. built with a `HeaderBuilder`
+    pub fn new(header: Header) -> Self {
+        Self {
+            header,
+            elements: vec![],
+        }
+    }
+
+    /// Sets the outer contour of a face. The contour is closed if its last point does
+    /// not repeat its first point.
+    /// # Arguments
+    /// * `face` - The face of the contour
+    /// * `points` - The points of the contour, e.g. `(x, y)` or `(x, y, radius)` tuples
+    pub fn outer_contour<P, I>(mut self, face: PartFace, points: I) -> Self
+    where
+        P: Into<BorderPoint>,
+        I: IntoIterator<Item = P>,
+    {
+        let contour = contour(face, points);
+        self.elements
+            .push(DstvElementType::OuterBorder(OuterBorder { contour }));
+        self
+    }
+
+    /// Adds an inner contour, such as a cut-out, to a face. The contour is closed if its
+    /// last point does not repeat its first point.
+    /// # Arguments
+    /// * `face` - The face of the contour
+    /// * `points` - The points of the contour, e.g. `(x, y)` or `(x, y, radius)` tuples
+    pub fn add_inner_contour<P, I>(mut self, face: PartFace, points: I) -> Self
+    where
+        P: Into<BorderPoint>,
+        I: IntoIterator<Item = P>,
+    {
+        let contour = contour(face, points);
+        self.elements
+            .push(DstvElementType::InnerBorder(InnerBorder { contour }));
+        self
+    }
+
+    /// Adds a through hole
+    /// # Arguments
+    /// * `face` - The face the hole is drilled in
+    /// * `x` - The x coordinate of the centre of the hole
+    /// * `y` - The y coordinate of the centre of the hole
+    /// * `diameter` - The diameter of the hole
+    pub fn add_hole(mut self, face: PartFace, x: f64, y: f64, diameter: f64) -> Self {
+        self.elements.push(DstvElementType::Hole(Hole {
+            diameter: diameter.into(),
+            depth: None,
+            x_coord: x.into(),
+            y_coord: y.into(),
+            fl_code: face,
+        }));
+        self
+    }
+
+    /// Adds a through slot
+    /// # Arguments
+    /// * `face` - The face the slot is cut in
+    /// * `x` - The x coordinate of the centre of the first rounding of the slot
+    /// * `y` - The y coordinate of the centre of the first rounding of the slot
+    /// * `diameter` - The width of the slot
+    /// * `slot_length` - The distance between the roundings along the slot
+    /// * `slot_width` - The distance between the roundings across the slot
+    /// * `angle` - The angle of the slot in degrees
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_slot(
+        mut self,
+        face: PartFace,
+        x: f64,
+        y: f64,
+        diameter: f64,
+        slot_length: f64,
+        slot_width: f64,
+        angle: f64,
+    ) -> Self {
+        self.elements.push(DstvElementType::Slot(Slot {
+            angle: angle.into(),
+            slot_length: slot_length.into(),
+            slot_width: slot_width.into(),
+            diameter: diameter.into(),
+            depth: Millimetres(0.0),
+            x_coord: x.into(),
+            y_coord: y.into(),
+            fl_code: face,
+        }));
+        self
+    }
+
+    /// Adds a mark, written horizontally with a letter height of 10 mm
+    /// # Arguments
+    /// * `face` - The face the mark is placed on
+    /// * `x` - The x coordinate of the mark
+    /// * `y` - The y coordinate of the mark
+    /// * `text` - The text of the mark, without whitespace
+    pub fn add_mark<S: AsRef<str>>(mut self, face: PartFace, x: f64, y: f64, text: S) -> Self {
+        self.elements.push(DstvElementType::Numeration(Numeration {
+            angle: Degrees(0.0),
+            letterheight: Millimetres(MARK_HEIGHT),
+            text: text.as_ref().to_string(),
+            x_coord: x.into(),
+            y_coord: y.into(),
+            fl_code: face,
+        }));
+        self
+    }
+
+    /// Adds any other element
+    pub fn add_element(mut self, element: DstvElementType) -> Self {
+        self.elements.push(element);
+        self
+    }
+
+    /// Validates and builds the DSTV file
+    /// # Returns
+    /// The DSTV file, or an error listing the validation findings of error severity
+    /// # Error
+    /// * If a mark has no text or contains whitespace
+    /// * If the piece does not pass validation, see `Validator`
+    pub fn build(self) -> Result<Dstv, BuildDstvError> {
+        let mut elements = self.elements;
+        let has_front_contour = elements.iter().any(|element| {
+            matches!(element, DstvElementType::OuterBorder(_))
+                && element.get_facing() == &PartFace::Front
+        });
+        if self.header.code_profile == CodeProfile::B && !has_front_contour {
+            let width = self.header.face_width(&PartFace::Front);
+            let length = *self.header.length;
+            let points = [(0.0, 0.0), (length, 0.0), (length, width), (0.0, width)];
+            let contour = contour(PartFace::Front, points);
+            elements.insert(0, DstvElementType::OuterBorder(OuterBorder { contour }));
+        }
+        for element in &elements {
+            if let DstvElementType::Numeration(mark) = element {
+                if mark.text.is_empty() || mark.text.contains(char::is_whitespace) {
+                    return Err(BuildDstvError::MarkText(mark.text.clone()));
+                }
+            }
+        }
+        let dstv = Dstv {
+            header: self.header,
+            elements,
+        };
+        let errors = dstv
+            .validate()
+            .into_iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .collect::<Vec<_>>();
+        match errors.is_empty() {
+            true => Ok(dstv),
+            false => Err(BuildDstvError::Validation(errors)),
+        }
+    }
+}
+
+/// Collects the points of a contour on a face, closing the contour if needed
+fn contour<P, I>(face: PartFace, points: I) -> Vec<BorderPoint>
+where
+    P: Into<BorderPoint>,
+    I: IntoIterator<Item = P>,
+{
+    let mut contour = points
+        .into_iter()
+        .map(|point| BorderPoint {
+            fl_code: face.clone(),
+            ..point.into()
+        })
+        .collect::<Vec<_>>();
+    if let (Some(first), Some(last)) = (contour.first(), contour.last()) {
+        if contour.len() > 1 && (first.x_coord != last.x_coord || first.y_coord != last.y_coord) {
+            let closing = BorderPoint {
+                radius: Default::default(),
+                bevel: None,
+                ..first.clone()
+            };
+            contour.push(closing);
+        }
+    }
+    contour
+}
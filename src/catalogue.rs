@@ -0,0 +1,460 @@
+use crate::{
+    dstv_element::ParseDstvError,
+    prelude::{CodeProfile, Header},
+};
+use std::{f64::consts::PI, str::FromStr};
+
+/// Absolute tolerance for comparing header dimensions with the catalogue, in millimetres
+const DIMENSION_TOLERANCE: f64 = 0.5;
+/// Relative tolerance for comparing the header weight by meter with the catalogue
+const WEIGHT_TOLERANCE: f64 = 0.03;
+
+/// Families of standard European sections
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SectionFamily {
+    /// IPE I-beams
+    Ipe,
+    /// HEA wide flange beams
+    Hea,
+    /// HEB wide flange beams
+    Heb,
+    /// HEM wide flange beams
+    Hem,
+    /// UPN channels with tapered flanges
+    Upn,
+    /// UPE channels with parallel flanges
+    Upe,
+    /// Equal and unequal leg angles
+    Angle,
+    /// Rectangular hollow sections
+    Rhs,
+    /// Square hollow sections
+    Shs,
+    /// Circular hollow sections
+    Chs,
+}
+
+impl SectionFamily {
+    /// Returns the designation of the family as used in profile names
+    pub fn to_str(&self) -> &str {
+        match self {
+            SectionFamily::Ipe => "IPE",
+            SectionFamily::Hea => "HEA",
+            SectionFamily::Heb => "HEB",
+            SectionFamily::Hem => "HEM",
+            SectionFamily::Upn => "UPN",
+            SectionFamily::Upe => "UPE",
+            SectionFamily::Angle => "L",
+            SectionFamily::Rhs => "RHS",
+            SectionFamily::Shs => "SHS",
+            SectionFamily::Chs => "CHS",
+        }
+    }
+
+    /// Returns the DSTV profile type sections of this family are exported as
+    pub fn code_profile(&self) -> CodeProfile {
+        match self {
+            SectionFamily::Ipe | SectionFamily::Hea | SectionFamily::Heb | SectionFamily::Hem => {
+                CodeProfile::I
+            }
+            SectionFamily::Upn | SectionFamily::Upe => CodeProfile::U,
+            SectionFamily::Angle => CodeProfile::L,
+            SectionFamily::Rhs | SectionFamily::Shs => CodeProfile::M,
+            SectionFamily::Chs => CodeProfile::RO,
+        }
+    }
+
+    /// Returns the catalogue table of the family, if it is a tabulated family.
+    /// Each row holds the size, height, width, web thickness, flange thickness, root
+    /// radius and weight by meter.
+    fn table(&self) -> Option<&'static [CatalogueRow]> {
+        match self {
+            SectionFamily::Ipe => Some(IPE),
+            SectionFamily::Hea => Some(HEA),
+            SectionFamily::Heb => Some(HEB),
+            SectionFamily::Hem => Some(HEM),
+            SectionFamily::Upn => Some(UPN),
+            SectionFamily::Upe => Some(UPE),
+            _ => None,
+        }
+    }
+}
+
+/// Nominal dimensions of a standard section
+#[derive(Clone, Debug, PartialEq)]
+pub struct SectionDimensions {
+    /// The family of the section
+    pub family: SectionFamily,
+    /// The normalized name of the section, e.g. `HEA 200` or `SHS 100x100x6`
+    pub name: String,
+    /// The height of the section, or the outside diameter of a circular section
+    pub height: f64,
+    /// The width of the section, or the outside diameter of a circular section
+    pub width: f64,
+    /// The thickness of the web, or the wall thickness of hollow sections and angles
+    pub web_thickness: f64,
+    /// The thickness of the flanges, or the wall thickness of hollow sections and angles
+    pub flange_thickness: f64,
+    /// The root radius, or the outer corner radius of rectangular hollow sections, if the
+    /// section standard defines one
+    pub radius: Option<f64>,
+    /// The nominal weight by meter in kg/m
+    pub weight_by_meter: f64,
+}
+
+/// A dimension of the header that does not match the catalogue
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileMismatch {
+    /// The name of the header field
+    pub field: &'static str,
+    /// The value from the catalogue
+    pub expected: f64,
+    /// The value from the header
+    pub actual: f64,
+}
+
+type CatalogueRow = (u32, f64, f64, f64, f64, f64, f64);
+
+#[rustfmt::skip]
+const IPE: &[CatalogueRow] = &[
+    (80, 80.0, 46.0, 3.8, 5.2, 5.0, 6.0),
+    (100, 100.0, 55.0, 4.1, 5.7, 7.0, 8.1),
+    (120, 120.0, 64.0, 4.4, 6.3, 7.0, 10.4),
+    (140, 140.0, 73.0, 4.7, 6.9, 7.0, 12.9),
+    (160, 160.0, 82.0, 5.0, 7.4, 9.0, 15.8),
+    (180, 180.0, 91.0, 5.3, 8.0, 9.0, 18.8),
+    (200, 200.0, 100.0, 5.6, 8.5, 12.0, 22.4),
+    (220, 220.0, 110.0, 5.9, 9.2, 12.0, 26.2),
+    (240, 240.0, 120.0, 6.2, 9.8, 15.0, 30.7),
+    (270, 270.0, 135.0, 6.6, 10.2, 15.0, 36.1),
+    (300, 300.0, 150.0, 7.1, 10.7, 15.0, 42.2),
+    (330, 330.0, 160.0, 7.5, 11.5, 18.0, 49.1),
+    (360, 360.0, 170.0, 8.0, 12.7, 18.0, 57.1),
+    (400, 400.0, 180.0, 8.6, 13.5, 21.0, 66.3),
+    (450, 450.0, 190.0, 9.4, 14.6, 21.0, 77.6),
+    (500, 500.0, 200.0, 10.2, 16.0, 21.0, 90.7),
+    (550, 550.0, 210.0, 11.1, 17.2, 24.0, 106.0),
+    (600, 600.0, 220.0, 12.0, 19.0, 24.0, 122.0),
+];
+
+#[rustfmt::skip]
+const HEA: &[CatalogueRow] = &[
+    (100, 96.0, 100.0, 5.0, 8.0, 12.0, 16.7),
+    (120, 114.0, 120.0, 5.0, 8.0, 12.0, 19.9),
+    (140, 133.0, 140.0, 5.5, 8.5, 12.0, 24.7),
+    (160, 152.0, 160.0, 6.0, 9.0, 15.0, 30.4),
+    (180, 171.0, 180.0, 6.0, 9.5, 15.0, 35.5),
+    (200, 190.0, 200.0, 6.5, 10.0, 18.0, 42.3),
+    (220, 210.0, 220.0, 7.0, 11.0, 18.0, 50.5),
+    (240, 230.0, 240.0, 7.5, 12.0, 21.0, 60.3),
+    (260, 250.0, 260.0, 7.5, 12.5, 24.0, 68.2),
+    (280, 270.0, 280.0, 8.0, 13.0, 24.0, 76.4),
+    (300, 290.0, 300.0, 8.5, 14.0, 27.0, 88.3),
+    (320, 310.0, 300.0, 9.0, 15.5, 27.0, 97.6),
+    (340, 330.0, 300.0, 9.5, 16.5, 27.0, 105.0),
+    (360, 350.0, 300.0, 10.0, 17.5, 27.0, 112.0),
+    (400, 390.0, 300.0, 11.0, 19.0, 27.0, 125.0),
+    (450, 440.0, 300.0, 11.5, 21.0, 27.0, 140.0),
+    (500, 490.0, 300.0, 12.0, 23.0, 27.0, 155.0),
+    (550, 540.0, 300.0, 12.5, 24.0, 27.0, 166.0),
+    (600, 590.0, 300.0, 13.0, 25.0, 27.0, 178.0),
+    (650, 640.0, 300.0, 13.5, 26.0, 27.0, 190.0),
+    (700, 690.0, 300.0, 14.5, 27.0, 27.0, 204.0),
+    (800, 790.0, 300.0, 15.0, 28.0, 30.0, 224.0),
+    (900, 890.0, 300.0, 16.0, 30.0, 30.0, 252.0),
+    (1000, 990.0, 300.0, 16.5, 31.0, 30.0, 272.0),
+];
+
+#[rustfmt::skip]
+const HEB: &[CatalogueRow] = &[
+    (100, 100.0, 100.0, 6.0, 10.0, 12.0, 20.4),
+    (120, 120.0, 120.0, 6.5, 11.0, 12.0, 26.7),
+    (140, 140.0, 140.0, 7.0, 12.0, 12.0, 33.7),
+    (160, 160.0, 160.0, 8.0, 13.0, 15.0, 42.6),
+    (180, 180.0, 180.0, 8.5, 14.0, 15.0, 51.2),
+    (200, 200.0, 200.0, 9.0, 15.0, 18.0, 61.3),
+    (220, 220.0, 220.0, 9.5, 16.0, 18.0, 71.5),
+    (240, 240.0, 240.0, 10.0, 17.0, 21.0, 83.2),
+    (260, 260.0, 260.0, 10.0, 17.5, 24.0, 93.0),
+    (280, 280.0, 280.0, 10.5, 18.0, 24.0, 103.0),
+    (300, 300.0, 300.0, 11.0, 19.0, 27.0, 117.0),
+    (320, 320.0, 300.0, 11.5, 20.5, 27.0, 127.0),
+    (340, 340.0, 300.0, 12.0, 21.5, 27.0, 134.0),
+    (360, 360.0, 300.0, 12.5, 22.5, 27.0, 142.0),
+    (400, 400.0, 300.0, 13.5, 24.0, 27.0, 155.0),
+    (450, 450.0, 300.0, 14.0, 26.0, 27.0, 171.0),
+    (500, 500.0, 300.0, 14.5, 28.0, 27.0, 187.0),
+    (550, 550.0, 300.0, 15.0, 29.0, 27.0, 199.0),
+    (600, 600.0, 300.0, 15.5, 30.0, 27.0, 212.0),
+    (650, 650.0, 300.0, 16.0, 31.0, 27.0, 225.0),
+    (700, 700.0, 300.0, 17.0, 32.0, 27.0, 241.0),
+    (800, 800.0, 300.0, 17.5, 33.0, 30.0, 262.0),
+    (900, 900.0, 300.0, 18.5, 35.0, 30.0, 291.0),
+    (1000, 1000.0, 300.0, 19.0, 36.0, 30.0, 314.0),
+];
+
+#[rustfmt::skip]
+const HEM: &[CatalogueRow] = &[
+    (100, 120.0, 106.0, 12.0, 20.0, 12.0, 41.8),
+    (120, 140.0, 126.0, 12.5, 21.0, 12.0, 52.1),
+    (140, 160.0, 146.0, 13.0, 22.0, 12.0, 63.2),
+    (160, 180.0, 166.0, 14.0, 23.0, 15.0, 76.2),
+    (180, 200.0, 186.0, 14.5, 24.0, 15.0, 88.9),
+    (200, 220.0, 206.0, 15.0, 25.0, 18.0, 103.0),
+    (220, 240.0, 226.0, 15.5, 26.0, 18.0, 117.0),
+    (240, 270.0, 248.0, 18.0, 32.0, 21.0, 157.0),
+    (260, 290.0, 268.0, 18.0, 32.5, 24.0, 172.0),
+    (280, 310.0, 288.0, 18.5, 33.0, 24.0, 189.0),
+    (300, 340.0, 310.0, 21.0, 39.0, 27.0, 238.0),
+    (320, 359.0, 309.0, 21.0, 40.0, 27.0, 245.0),
+    (340, 377.0, 309.0, 21.0, 40.0, 27.0, 248.0),
+    (360, 395.0, 308.0, 21.0, 40.0, 27.0, 250.0),
+    (400, 432.0, 307.0, 21.0, 40.0, 27.0, 256.0),
+    (450, 478.0, 307.0, 21.0, 40.0, 27.0, 263.0),
+    (500, 524.0, 306.0, 21.0, 40.0, 27.0, 270.0),
+    (550, 572.0, 306.0, 21.0, 40.0, 27.0, 278.0),
+    (600, 620.0, 305.0, 21.0, 40.0, 27.0, 285.0),
+    (650, 668.0, 305.0, 21.0, 40.0, 27.0, 293.0),
+    (700, 716.0, 304.0, 21.0, 40.0, 27.0, 301.0),
+    (800, 814.0, 303.0, 21.0, 40.0, 30.0, 317.0),
+    (900, 910.0, 302.0, 21.0, 40.0, 30.0, 333.0),
+    (1000, 1008.0, 302.0, 21.0, 40.0, 30.0, 349.0),
+];
+
+#[rustfmt::skip]
+const UPN: &[CatalogueRow] = &[
+    (80, 80.0, 45.0, 6.0, 8.0, 8.0, 8.64),
+    (100, 100.0, 50.0, 6.0, 8.5, 8.5, 10.6),
+    (120, 120.0, 55.0, 7.0, 9.0, 9.0, 13.4),
+    (140, 140.0, 60.0, 7.0, 10.0, 10.0, 16.0),
+    (160, 160.0, 65.0, 7.5, 10.5, 10.5, 18.8),
+    (180, 180.0, 70.0, 8.0, 11.0, 11.0, 22.0),
+    (200, 200.0, 75.0, 8.5, 11.5, 11.5, 25.3),
+    (220, 220.0, 80.0, 9.0, 12.5, 12.5, 29.4),
+    (240, 240.0, 85.0, 9.5, 13.0, 13.0, 33.2),
+    (260, 260.0, 90.0, 10.0, 14.0, 14.0, 37.9),
+    (280, 280.0, 95.0, 10.0, 15.0, 15.0, 41.8),
+    (300, 300.0, 100.0, 10.0, 16.0, 16.0, 46.2),
+    (320, 320.0, 100.0, 14.0, 17.5, 17.5, 59.5),
+    (350, 350.0, 100.0, 14.0, 16.0, 16.0, 60.6),
+    (380, 380.0, 102.0, 13.5, 16.0, 16.0, 63.1),
+    (400, 400.0, 110.0, 14.0, 18.0, 18.0, 71.8),
+];
+
+#[rustfmt::skip]
+const UPE: &[CatalogueRow] = &[
+    (80, 80.0, 50.0, 4.0, 7.0, 10.0, 7.9),
+    (100, 100.0, 55.0, 4.5, 7.5, 10.0, 9.82),
+    (120, 120.0, 60.0, 5.0, 8.0, 12.0, 12.1),
+    (140, 140.0, 65.0, 5.0, 9.0, 12.0, 14.5),
+    (160, 160.0, 70.0, 5.5, 9.5, 12.0, 17.0),
+    (180, 180.0, 75.0, 5.5, 10.5, 12.0, 19.7),
+    (200, 200.0, 80.0, 6.0, 11.0, 13.0, 22.8),
+    (220, 220.0, 85.0, 6.5, 12.0, 13.0, 26.6),
+    (240, 240.0, 90.0, 7.0, 12.5, 15.0, 30.2),
+    (270, 270.0, 95.0, 7.5, 13.5, 15.0, 35.2),
+    (300, 300.0, 100.0, 9.5, 15.0, 15.0, 44.4),
+    (330, 330.0, 105.0, 11.0, 16.0, 18.0, 53.2),
+    (360, 360.0, 110.0, 12.0, 17.0, 18.0, 61.2),
+    (400, 400.0, 115.0, 13.5, 18.0, 18.0, 72.2),
+];
+
+/// Designations recognized in profile names, longest first so that e.g. `SHS` is not
+/// mistaken for a shorter designation.
+const DESIGNATIONS: &[(&str, SectionFamily)] = &[
+    ("HFRHS", SectionFamily::Rhs),
+    ("CFRHS", SectionFamily::Rhs),
+    ("HFSHS", SectionFamily::Shs),
+    ("CFSHS", SectionFamily::Shs),
+    ("HFCHS", SectionFamily::Chs),
+    ("CFCHS", SectionFamily::Chs),
+    ("IPE", SectionFamily::Ipe),
+    ("HEA", SectionFamily::Hea),
+    ("HEB", SectionFamily::Heb),
+    ("HEM", SectionFamily::Hem),
+    ("UPN", SectionFamily::Upn),
+    ("UPE", SectionFamily::Upe),
+    ("RHS", SectionFamily::Rhs),
+    ("SHS", SectionFamily::Shs),
+    ("CHS", SectionFamily::Chs),
+    ("RO", SectionFamily::Chs),
+    ("L", SectionFamily::Angle),
+];
+
+/// Converts a steel area in mm² to a weight by meter in kg/m
+fn weight_by_meter(area: f64) -> f64 {
+    area * crate::prelude::STEEL_DENSITY * 1e-6
+}
+
+/// Returns the outer and inner corner radius of a hot finished hollow section, which are
+/// 1.5 and 1 times the wall thickness
+/// # Arguments
+/// * `thickness` - The wall thickness of the section
+pub(crate) fn hollow_corner_radii(thickness: f64) -> (f64, f64) {
+    (1.5 * thickness, thickness)
+}
+
+/// Splits a normalized profile name into its family and the remaining dimensions.
+/// Both `HEA200`, `200HEA` and the alternative notation `HE200A` are recognized.
+fn split_designation(name: &str) -> Option<(SectionFamily, &str)> {
+    if let Some(rest) = name.strip_prefix("HE") {
+        let family = match rest.chars().last() {
+            Some('A') => Some(SectionFamily::Hea),
+            Some('B') => Some(SectionFamily::Heb),
+            Some('M') => Some(SectionFamily::Hem),
+            _ => None,
+        };
+        if let Some(family) = family {
+            let size = &rest[..rest.len() - 1];
+            if !size.is_empty() && size.chars().all(|c| c.is_ascii_digit()) {
+                return Some((family, size));
+            }
+        }
+    }
+    DESIGNATIONS.iter().find_map(|(designation, family)| {
+        name.strip_prefix(designation)
+            .or_else(|| name.strip_suffix(designation))
+            .filter(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+            .map(|rest| (*family, rest))
+    })
+}
+
+impl SectionDimensions {
+    fn from_table(family: SectionFamily, size: &str) -> Option<Self> {
+        let size = size.parse::<u32>().ok()?;
+        let (_, height, width, web_thickness, flange_thickness, radius, weight) =
+            family.table()?.iter().find(|row| row.0 == size)?;
+        Some(Self {
+            family,
+            name: format!("{} {}", family.to_str(), size),
+            height: *height,
+            width: *width,
+            web_thickness: *web_thickness,
+            flange_thickness: *flange_thickness,
+            radius: Some(*radius),
+            weight_by_meter: *weight,
+        })
+    }
+
+    fn from_dimensions(family: SectionFamily, dimensions: &[f64]) -> Option<Self> {
+        let (height, width, thickness) = match (family, dimensions) {
+            (SectionFamily::Angle, [a, t]) | (SectionFamily::Shs, [a, t]) => (*a, *a, *t),
+            (SectionFamily::Angle, [a, b, t])
+            | (SectionFamily::Rhs, [a, b, t])
+            | (SectionFamily::Shs, [a, b, t]) => (*a, *b, *t),
+            (SectionFamily::Chs, [d, t]) => (*d, *d, *t),
+            _ => return None,
+        };
+        let area = match family {
+            SectionFamily::Angle => thickness * (height + width - thickness),
+            SectionFamily::Chs => PI * (height - thickness) * thickness,
+            _ => {
+                let (outer, inner) = hollow_corner_radii(thickness);
+                2.0 * thickness * (height + width - 2.0 * thickness)
+                    - (4.0 - PI) * (outer * outer - inner * inner)
+            }
+        };
+        let name = match family {
+            SectionFamily::Chs => format!("CHS {height}x{thickness}"),
+            _ => format!("{} {height}x{width}x{thickness}", family.to_str()),
+        };
+        Some(Self {
+            family,
+            name,
+            height,
+            width,
+            web_thickness: thickness,
+            flange_thickness: thickness,
+            radius: match family {
+                SectionFamily::Rhs | SectionFamily::Shs => Some(hollow_corner_radii(thickness).0),
+                _ => None,
+            },
+            weight_by_meter: weight_by_meter(area),
+        })
+    }
+}
+
+impl FromStr for SectionDimensions {
+    type Err = ParseDstvError;
+
+    /// Recognizes a standard section from a profile name, such as `HEA200`, `IPE 300`,
+    /// `L100x100x10` or `100x100x6 SHS`
+    /// # Arguments
+    /// * `s` - The profile name, e.g. from `Header::profile`
+    /// # Returns
+    /// The nominal dimensions of the section
+    /// # Error
+    /// * If the profile name is not a known standard section
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s
+            .to_uppercase()
+            .replace(|c: char| c.is_whitespace() || c == '-', "")
+            .replace(['*', '/'], "X");
+        let unknown = || ParseDstvError::new(format!("Unknown profile: {}", s.trim()));
+        let (family, rest) = split_designation(&name).ok_or_else(unknown)?;
+        match family.table() {
+            Some(_) => Self::from_table(family, rest),
+            None => rest
+                .split('X')
+                .map(|value| value.parse::<f64>().ok())
+                .collect::<Option<Vec<_>>>()
+                .and_then(|dimensions| Self::from_dimensions(family, &dimensions)),
+        }
+        .ok_or_else(unknown)
+    }
+}
+
+impl Header {
+    /// Looks up the profile of the piece in the catalogue of standard sections
+    /// # Returns
+    /// The nominal dimensions of the profile, or an error if it is not a standard section
+    pub fn catalogue_section(&self) -> Result<SectionDimensions, ParseDstvError> {
+        SectionDimensions::from_str(&self.profile)
+    }
+
+    /// Compares the dimensions and the weight by meter in the header with the catalogue.
+    /// A weight by meter or a radius of zero is taken to be missing and is not compared,
+    /// neither is the corner radius of hollow sections.
+    /// # Returns
+    /// The fields that do not match the catalogue, or an error if the profile is not a
+    /// standard section
+    pub fn check_profile(&self) -> Result<Vec<ProfileMismatch>, ParseDstvError> {
+        let section = self.catalogue_section()?;
+        let mut checks = vec![
+            ("profile_height", section.height, *self.profile_height),
+            ("flange_width", section.width, *self.flange_width),
+            (
+                "flange_thickness",
+                section.flange_thickness,
+                *self.flange_thickness,
+            ),
+            ("web_thickness", section.web_thickness, *self.web_thickness),
+        ];
+        // the corner radius of hollow sections varies between mills, only root radii are
+        // compared
+        let rolled = section.family.table().is_some();
+        if let (Some(radius), true) = (section.radius, rolled && self.radius > 0.0) {
+            checks.push(("radius", radius, *self.radius));
+        }
+        let mut mismatches = checks
+            .into_iter()
+            .filter(|(_, expected, actual)| (expected - actual).abs() > DIMENSION_TOLERANCE)
+            .map(|(field, expected, actual)| ProfileMismatch {
+                field,
+                expected,
+                actual,
+            })
+            .collect::<Vec<_>>();
+        if self.weight_by_meter > 0.0
+            && (section.weight_by_meter - *self.weight_by_meter).abs()
+                > WEIGHT_TOLERANCE * section.weight_by_meter
+        {
+            mismatches.push(ProfileMismatch {
+                field: "weight_by_meter",
+                expected: section.weight_by_meter,
+                actual: *self.weight_by_meter,
+            });
+        }
+        Ok(mismatches)
+    }
+}
@@ -1,10 +1,24 @@
-use crate::dstv_element::ParseDstvError;
-use crate::get_f64_from_str;
-use crate::prelude::DstvElement;
+use crate::dstv_element::{Block, ParseDstvError};
+use crate::geometry::{self, Point, EPSILON};
+use crate::prelude::{
+    Border, CodeProfile, Dstv, DstvElement, DstvElementType, Header, Millimetres, PartEnd, PartFace,
+};
+use crate::{get_f64_from_str, get_str_from_f64};
+use std::{
+    fmt::{self, Write},
+    str::FromStr,
+};
 
-/// Represents a cut in the DSTV file
-#[derive(Debug)]
+/// Represents a cut in the DSTV file.
+/// A cut is a plane through a point, trimming everything on the side its normal vector
+/// points to. The point is given in the 3D coordinates of the part: x along the length,
+/// y along the profile height from the bottom flange and z along the flange width from
+/// the front face.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cut {
+    /// Flange code of the cut, the face it was defined on
+    pub fl_code: PartFace,
     /// Normal vector x component
     pub nor_vec_x: f64,
     /// Normal vector y component
@@ -12,34 +26,42 @@ pub struct Cut {
     /// Normal vector z component
     pub nor_vec_z: f64,
     /// Start point x coordinate
-    pub sp_point_x: f64,
+    pub sp_point_x: Millimetres,
     /// Start point y coordinate
-    pub sp_point_y: f64,
+    pub sp_point_y: Millimetres,
     /// Start point z coordinate
-    pub sp_point_z: f64,
+    pub sp_point_z: Millimetres,
 }
 
 impl DstvElement for Cut {
-    /// Create a new cut from a string slice.
-    /// The string slice is expected to be a line from a DSTV file.
+    /// Create a new cut from its block.
+    /// The values of the cut are read from the first line of the block.
     /// # Arguments
-    /// * `line` - A string slice containing a line from a DSTV file.
+    /// * `block` - A block of a DSTV file holding a cut.
     /// # Returns
-    /// A Result containing either a Cut or a &'static str.
-    fn from_str(line: &str) -> Result<Self, ParseDstvError> {
-        let mut iter = line.split_whitespace();
+    /// A `Result` containing either a `Cut` or an error message
+    fn from_block(block: &Block) -> Result<Self, ParseDstvError> {
+        let mut iter = block.first_line().split_whitespace().peekable();
+        let fl_code = match iter.peek().map(|code| PartFace::from_str(code)) {
+            Some(Ok(fl_code)) => {
+                iter.next();
+                fl_code
+            }
+            _ => PartFace::Front,
+        };
         if iter.clone().count() < 6 {
             return Err(ParseDstvError::new(
                 "Illegal data vector format (SC): too short",
             ));
         }
-        let sp_point_x = get_f64_from_str(iter.next(), "sp_point_x")?;
-        let sp_point_y = get_f64_from_str(iter.next(), "sp_point_y")?;
-        let sp_point_z = get_f64_from_str(iter.next(), "sp_point_z")?;
+        let sp_point_x = get_f64_from_str(iter.next(), "sp_point_x")?.into();
+        let sp_point_y = get_f64_from_str(iter.next(), "sp_point_y")?.into();
+        let sp_point_z = get_f64_from_str(iter.next(), "sp_point_z")?.into();
         let nor_vec_x = get_f64_from_str(iter.next(), "nor_vec_x")?;
         let nor_vec_y = get_f64_from_str(iter.next(), "nor_vec_y")?;
         let nor_vec_z = get_f64_from_str(iter.next(), "nor_vec_z")?;
         Ok(Self {
+            fl_code,
             nor_vec_x,
             nor_vec_y,
             nor_vec_z,
@@ -49,16 +71,26 @@ impl DstvElement for Cut {
         })
     }
 
-    /// Convert the cut to an SVG path.
+    /// A cut has no drawing of its own: it is a plane through the part, and where it
+    /// crosses a face depends on the header. `Dstv::to_svg` draws the cut through the
+    /// outline of every face it removes material from.
     /// # Returns
-    /// A string containing an SVG path.
+    /// An empty string
     fn to_svg(&self) -> String {
-        format!(
-            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\" />",
-            self.sp_point_x,
-            self.sp_point_y,
-            self.sp_point_x + self.nor_vec_x,
-            self.sp_point_y + self.nor_vec_y
+        String::new()
+    }
+
+    fn write(&self, out: &mut dyn Write) -> fmt::Result {
+        write!(
+            out,
+            "SC\n  {}{}{}{}{}{}{}\n",
+            self.fl_code.code(),
+            get_str_from_f64(self.sp_point_x),
+            get_str_from_f64(self.sp_point_y),
+            get_str_from_f64(self.sp_point_z),
+            get_str_from_f64(self.nor_vec_x),
+            get_str_from_f64(self.nor_vec_y),
+            get_str_from_f64(self.nor_vec_z)
         )
     }
 
@@ -66,7 +98,220 @@ impl DstvElement for Cut {
         2
     }
 
-    fn get_facing(&self) -> &crate::prelude::PartFace {
-        &crate::prelude::PartFace::Top
+    fn get_facing(&self) -> &PartFace {
+        &self.fl_code
+    }
+
+    fn position(&self) -> Option<Point> {
+        Some(Point::new(*self.sp_point_x, *self.sp_point_y))
+    }
+}
+
+impl Cut {
+    /// Returns the point the cutting plane runs through, in part coordinates
+    pub fn point(&self) -> [f64; 3] {
+        [*self.sp_point_x, *self.sp_point_y, *self.sp_point_z]
+    }
+
+    /// Returns the unit normal of the cutting plane, pointing to the removed material
+    pub fn normal(&self) -> [f64; 3] {
+        let [x, y, z] = [self.nor_vec_x, self.nor_vec_y, self.nor_vec_z];
+        let length = (x * x + y * y + z * z).sqrt();
+        match length > EPSILON {
+            true => [x / length, y / length, z / length],
+            false => [0.0, 0.0, 0.0],
+        }
+    }
+
+    /// Returns the signed distance from the cutting plane to a point in part
+    /// coordinates, positive on the side that is removed
+    pub fn distance(&self, point: [f64; 3]) -> f64 {
+        let normal = self.normal();
+        let origin = self.point();
+        (0..3).map(|i| normal[i] * (point[i] - origin[i])).sum()
+    }
+
+    /// Checks whether the cut removes a point in part coordinates
+    pub fn removes(&self, point: [f64; 3]) -> bool {
+        self.distance(point) > EPSILON
+    }
+
+    /// Returns the line where the cutting plane crosses a face, as the coefficients of
+    /// `a * x + b * y + c`, which is positive where the face is removed
+    /// # Arguments
+    /// * `header` - The header of the part, giving the position of the face
+    /// * `face` - The face to project the cut on
+    pub fn face_line(&self, header: &Header, face: &PartFace) -> (f64, f64, f64) {
+        // the normal is left unscaled, so lines along the axes stay exact
+        let normal = [self.nor_vec_x, self.nor_vec_y, self.nor_vec_z];
+        let origin = self.point();
+        let value =
+            |point: [f64; 3]| -> f64 { (0..3).map(|i| normal[i] * (point[i] - origin[i])).sum() };
+        let c = value(header.face_point(face, 0.0, 0.0));
+        let a = value(header.face_point(face, 1.0, 0.0)) - c;
+        let b = value(header.face_point(face, 0.0, 1.0)) - c;
+        (a, b, c)
+    }
+
+    /// Trims a polygon on a face by the cut
+    /// # Arguments
+    /// * `header` - The header of the part, giving the position of the face
+    /// * `face` - The face of the polygon
+    /// * `polygon` - The corner points of the polygon
+    /// # Returns
+    /// The part of the polygon that remains, empty if the cut removes all of it
+    pub fn clip(&self, header: &Header, face: &PartFace, polygon: &[Point]) -> Vec<Point> {
+        let (a, b, c) = self.face_line(header, face);
+        geometry::clip_polygon(polygon, a, b, c)
+    }
+
+    /// Returns the part of a polygon on a face that the cut removes
+    /// # Arguments
+    /// * `header` - The header of the part, giving the position of the face
+    /// * `face` - The face of the polygon
+    /// * `polygon` - The corner points of the polygon
+    pub fn removed(&self, header: &Header, face: &PartFace, polygon: &[Point]) -> Vec<Point> {
+        let (a, b, c) = self.face_line(header, face);
+        geometry::clip_polygon(polygon, -a, -b, -c)
+    }
+}
+
+impl Header {
+    /// Returns the position of the plane of a face along the flange width, e.g. the
+    /// web of an I profile lies halfway the flanges
+    fn face_depth(&self, face: &PartFace) -> f64 {
+        match (&self.code_profile, face) {
+            (_, PartFace::Behind) => *self.flange_width,
+            (CodeProfile::I | CodeProfile::T, _) => *self.flange_width / 2.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Converts a point on a face to the 3D coordinates of the part: x along the length,
+    /// y along the profile height from the bottom flange and z along the flange width
+    /// from the front face
+    /// # Arguments
+    /// * `face` - The face of the point
+    /// * `x` - The x coordinate on the face
+    /// * `y` - The y coordinate on the face
+    pub fn face_point(&self, face: &PartFace, x: f64, y: f64) -> [f64; 3] {
+        match face {
+            PartFace::Front | PartFace::Behind => [x, y, self.face_depth(face)],
+            PartFace::Top => [x, *self.profile_height, y],
+            PartFace::Bottom => [x, 0.0, y],
+        }
+    }
+}
+
+/// Returns the area of a polygon
+fn polygon_area(polygon: &[Point]) -> f64 {
+    (0..polygon.len())
+        .map(|i| {
+            let (a, b) = (&polygon[i], &polygon[(i + 1) % polygon.len()]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f64>()
+        .abs()
+        / 2.0
+}
+
+impl Dstv {
+    /// Returns the cuts of the part
+    pub fn cuts(&self) -> Vec<&Cut> {
+        self.elements
+            .iter()
+            .filter_map(|element| match element {
+                DstvElementType::Cut(cut) => Some(cut),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Checks whether a point in part coordinates lies beyond one of the end cuts of the
+    /// header or on the removed side of one of the cuts
+    /// # Arguments
+    /// * `point` - The point, with x along the length, y along the profile height and z
+    ///   along the flange width
+    pub fn is_cut_away(&self, point: [f64; 3]) -> bool {
+        let [x, y, z] = point;
+        x < self.header.end_plane_x(PartEnd::Start, y, z) - EPSILON
+            || x > self.header.end_plane_x(PartEnd::End, y, z) + EPSILON
+            || self.cuts().iter().any(|cut| cut.removes(point))
+    }
+
+    /// Checks whether one of the cuts removes part of a polygon on a face
+    pub(crate) fn is_cut(&self, face: &PartFace, polygon: &[Point]) -> bool {
+        self.cuts()
+            .iter()
+            .any(|cut| polygon_area(&cut.removed(&self.header, face, polygon)) > EPSILON)
+    }
+
+    /// Returns the outlines of a face after applying the cuts. These are the polygons of
+    /// the outer contours on the face, or the outline from the header if there are none.
+    /// # Arguments
+    /// * `face` - The face to get the outlines for
+    /// # Returns
+    /// The corner points of each outline, leaving out outlines that are cut away entirely
+    pub fn face_outlines(&self, face: &PartFace) -> Vec<Vec<Point>> {
+        let mut outlines = self
+            .elements
+            .iter()
+            .filter_map(|element| match element {
+                DstvElementType::OuterBorder(border) if border.get_facing() == face => {
+                    Some(border.polygon())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if outlines.is_empty() {
+            let mut outline = self
+                .header
+                .default_outline(face)
+                .iter()
+                .map(|point| Point::new(*point.x_coord, *point.y_coord))
+                .collect::<Vec<_>>();
+            outline.pop();
+            outlines.push(outline);
+        }
+        outlines
+            .into_iter()
+            .map(|outline| {
+                self.cuts().iter().fold(outline, |outline, cut| {
+                    cut.clip(&self.header, face, &outline)
+                })
+            })
+            .filter(|outline| outline.len() > 2)
+            .collect()
+    }
+
+    /// Draws the parts of the outer contours on a face that the cuts remove, each with
+    /// a dashed line where the cut crosses the contour
+    pub(crate) fn cuts_to_svg(&self, face: &PartFace) -> String {
+        self.elements
+            .iter()
+            .filter_map(|element| match element {
+                DstvElementType::OuterBorder(border) if border.get_facing() == face => {
+                    Some(border.polygon())
+                }
+                _ => None,
+            })
+            .flat_map(|polygon| {
+                self.cuts()
+                    .into_iter()
+                    .map(move |cut| cut.removed(&self.header, face, &polygon))
+                    .filter(|removed| polygon_area(removed) > EPSILON)
+                    .collect::<Vec<_>>()
+            })
+            .map(|removed| {
+                let points = removed
+                    .iter()
+                    .map(|p| format!("{},{}", p.x, p.y))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                format!(
+                    "<polygon points=\"{points}\" fill=\"white\" stroke=\"red\" stroke-dasharray=\"4\" />"
+                )
+            })
+            .collect()
     }
 }
@@ -0,0 +1,134 @@
+use crate::{
+    dstv::START,
+    syntax::{is_comment, HEADER_LINES},
+};
+use std::fmt::Display;
+
+/// The header lines of the DSTV edition of 1998, which has no text lines. The current
+/// standard adds 4 text lines, `HEADER_LINES` in all.
+pub(crate) const HEADER_VALUE_LINES: usize = 20;
+/// The header line holding the length, and the saw length for some exporters
+const LENGTH_LINE: usize = 8;
+/// The codes that start a block, ending the header
+const BLOCK_CODES: &[&str] = &[
+    "AK", "IK", "BO", "LO", "SI", "SC", "PU", "KO", "KA", "TO", "UE", "PR", "EN",
+];
+
+/// The variant of the DSTV format a file was written in. The exporters differ in the
+/// comments after `ST`, in how they write the length and in whether they write the
+/// text lines of the header, but all of them are read the same way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dialect {
+    /// The header of the current DSTV standard, with 20 values and 4 text lines
+    Standard,
+    /// Tekla Structures, which writes the saw length after the length, e.g.
+    /// `233.70,233.70`
+    Tekla,
+    /// Advance Steel, recognised by its comment after `ST`
+    AdvanceSteel,
+    /// SDS/2, recognised by its comment after `ST`
+    Sds2,
+    /// The DSTV edition of 1998, recognised by its comment after `ST` or by a header
+    /// without text lines
+    Dstv1998,
+}
+
+impl Display for Dialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Dialect::Standard => "DSTV",
+            Dialect::Tekla => "Tekla Structures",
+            Dialect::AdvanceSteel => "Advance Steel",
+            Dialect::Sds2 => "SDS/2",
+            Dialect::Dstv1998 => "DSTV 1998",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// How a line is read while looking for the header
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HeaderLine {
+    /// The line is not part of the header, e.g. `ST` or a comment
+    Skip,
+    /// The line is the next line of the header
+    Header,
+    /// The line starts the first block, so the header ended before it
+    End,
+}
+
+/// Finds the lines of the header one line at a time. The header starts after `ST`
+/// and is read by position, skipping only comments: 20 values, of which the text
+/// values may be blank, followed by up to 4 text lines. It ends after the text lines
+/// or at the first block, so files of the 1998 edition without text lines are read
+/// too.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HeaderScanner {
+    /// The number of header lines read
+    count: usize,
+    /// True once `ST` or the first value was read, from where lines count by position
+    started: bool,
+    /// The comments read before the end of the header
+    comments: Vec<String>,
+    /// True if the length line holds the saw length as well
+    saw_length: bool,
+    /// True once a block ended the header
+    ended: bool,
+}
+
+impl HeaderScanner {
+    /// Reads the next line of the file
+    /// # Arguments
+    /// * `text` - The text of the line, without its line ending
+    pub(crate) fn next(&mut self, text: &str) -> HeaderLine {
+        let trimmed = text.trim();
+        if is_comment(text) {
+            self.comments.push(trimmed.to_string());
+            return HeaderLine::Skip;
+        }
+        if self.count >= HEADER_VALUE_LINES && BLOCK_CODES.contains(&trimmed) {
+            self.ended = true;
+            return HeaderLine::End;
+        }
+        if self.count == 0 && trimmed == START {
+            self.started = true;
+            return HeaderLine::Skip;
+        }
+        // blank lines before `ST` are not part of the header
+        if !self.started && trimmed.is_empty() {
+            return HeaderLine::Skip;
+        }
+        self.started = true;
+        if self.count == LENGTH_LINE {
+            self.saw_length = trimmed.contains(',');
+        }
+        self.count += 1;
+        HeaderLine::Header
+    }
+
+    /// Checks whether the header is complete, so no more lines need to be read
+    pub(crate) fn is_complete(&self) -> bool {
+        self.ended || self.count >= HEADER_LINES
+    }
+
+    /// Returns the dialect of the file, from the comments and the header read
+    pub(crate) fn dialect(&self) -> Dialect {
+        let comments = self.comments.join("\n").to_lowercase();
+        if comments.contains("tekla") {
+            Dialect::Tekla
+        } else if comments.contains("advance steel") {
+            Dialect::AdvanceSteel
+        } else if comments.contains("sds/2") || comments.contains("sds2") {
+            Dialect::Sds2
+        } else if comments.contains("dstv") && comments.contains("1998") {
+            Dialect::Dstv1998
+        } else if self.saw_length {
+            Dialect::Tekla
+        } else if self.count < HEADER_LINES {
+            Dialect::Dstv1998
+        } else {
+            Dialect::Standard
+        }
+    }
+}
@@ -0,0 +1,552 @@
+use crate::{
+    geometry::{self, Point, FLATTEN_TOLERANCE},
+    prelude::{BorderPoint, Dstv, DstvElementType, Header, PartFace},
+};
+use std::fmt::Display;
+
+/// The names of the header fields, in the order they are written to a DSTV file
+const HEADER_FIELDS: [&str; 25] = [
+    "order_identification",
+    "drawing_identification",
+    "phase_identification",
+    "piece_identification",
+    "steel_quality",
+    "quantity_of_pieces",
+    "profile",
+    "code_profile",
+    "length",
+    "saw_length",
+    "profile_height",
+    "flange_width",
+    "flange_thickness",
+    "web_thickness",
+    "radius",
+    "weight_by_meter",
+    "painting_surface_by_meter",
+    "web_start_cut",
+    "web_end_cut",
+    "flange_start_cut",
+    "flange_end_cut",
+    "text1_info_on_piece",
+    "text2_info_on_piece",
+    "text3_info_on_piece",
+    "text4_info_on_piece",
+];
+
+/// Returns the values of the header fields as they are written to a DSTV file, in the
+/// order of `HEADER_FIELDS`. The saw length, written after the length on the same line,
+/// is empty if it is not given.
+fn header_values(header: &Header) -> Vec<String> {
+    let mut values = header
+        .to_dstv()
+        .lines()
+        .map(|line| line.trim().to_string())
+        .collect::<Vec<_>>();
+    let length = values[8].clone();
+    let (length, saw_length) = length.split_once(',').unwrap_or((&length, ""));
+    values.splice(8..9, [length.to_string(), saw_length.to_string()]);
+    values
+}
+
+/// Returns the header fields that are compared within the tolerance: the length, the
+/// dimensions and the end cut angles. A saw length that is not given has no value.
+fn measured_values(header: &Header) -> [(&'static str, Option<f64>); 11] {
+    [
+        ("length", Some(*header.length)),
+        ("saw_length", header.saw_length.map(f64::from)),
+        ("profile_height", Some(*header.profile_height)),
+        ("flange_width", Some(*header.flange_width)),
+        ("flange_thickness", Some(*header.flange_thickness)),
+        ("web_thickness", Some(*header.web_thickness)),
+        ("radius", Some(*header.radius)),
+        ("web_start_cut", Some(*header.web_start_cut)),
+        ("web_end_cut", Some(*header.web_end_cut)),
+        ("flange_start_cut", Some(*header.flange_start_cut)),
+        ("flange_end_cut", Some(*header.flange_end_cut)),
+    ]
+}
+
+/// Options for comparing two DSTV files
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DiffOptions {
+    /// Coordinates and dimensions closer than this are taken to be equal, in mm. The
+    /// end cut angles of the header are compared within the same number of degrees.
+    pub tolerance: f64,
+    /// Elements of the same size that moved less than this are reported as moved
+    /// instead of removed and added, in mm
+    pub max_move: f64,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.1,
+            max_move: 100.0,
+        }
+    }
+}
+
+/// The kinds of changes between two DSTV files
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// The element only exists in the new file
+    Added,
+    /// The element only exists in the old file
+    Removed,
+    /// The element has the same size, but another position
+    Moved,
+    /// The element has the same position, but another size or shape
+    Modified,
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ChangeKind::Added => write!(f, "added"),
+            ChangeKind::Removed => write!(f, "removed"),
+            ChangeKind::Moved => write!(f, "moved"),
+            ChangeKind::Modified => write!(f, "modified"),
+        }
+    }
+}
+
+/// A changed field of the header
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeaderChange {
+    /// The name of the header field
+    pub field: &'static str,
+    /// The value in the old file, as written to a DSTV file
+    pub old: String,
+    /// The value in the new file, as written to a DSTV file
+    pub new: String,
+}
+
+impl Display for HeaderChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: `{}` -> `{}`", self.field, self.old, self.new)
+    }
+}
+
+/// A changed element
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElementChange {
+    /// The kind of change
+    pub kind: ChangeKind,
+    /// The element in the old file, unless it was added
+    pub old: Option<DstvElementType>,
+    /// The element in the new file, unless it was removed
+    pub new: Option<DstvElementType>,
+}
+
+impl ElementChange {
+    /// Returns the face of the changed element, in the new file if it still exists
+    pub fn face(&self) -> &PartFace {
+        self.new
+            .as_ref()
+            .or(self.old.as_ref())
+            .map(|element| element.get_facing())
+            .unwrap_or(&PartFace::Front)
+    }
+}
+
+impl Display for ElementChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let describe =
+            |element: &Option<DstvElementType>| element.as_ref().map(describe).unwrap_or_default();
+        match self.kind {
+            ChangeKind::Added => write!(f, "added {}", describe(&self.new)),
+            ChangeKind::Removed => write!(f, "removed {}", describe(&self.old)),
+            kind => write!(
+                f,
+                "{kind} {} -> {}",
+                describe(&self.old),
+                describe(&self.new)
+            ),
+        }
+    }
+}
+
+/// The differences between two DSTV files
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DstvDiff {
+    /// The changed fields of the header
+    pub header: Vec<HeaderChange>,
+    /// The added, removed, moved and modified elements
+    pub elements: Vec<ElementChange>,
+}
+
+impl DstvDiff {
+    /// Returns true if the files are the same
+    pub fn is_empty(&self) -> bool {
+        self.header.is_empty() && self.elements.is_empty()
+    }
+
+    /// Draws the changes on top of a DSTV file, usually the new one. Removed elements are
+    /// drawn in red, added elements in green and modified elements in orange. Moved
+    /// elements are drawn in red at their old and in green at their new position.
+    /// The faces are laid out as in `Dstv::to_svg`.
+    /// # Arguments
+    /// * `dstv` - The DSTV file to draw the changes on
+    /// # Returns
+    /// A string containing the SVG
+    pub fn to_svg(&self, dstv: &Dstv) -> String {
+        let header = &dstv.header;
+        let mut svg = String::new();
+        let mut offset = 0.0;
+        for (face, id) in [
+            (PartFace::Bottom, "bottom"),
+            (PartFace::Front, "front"),
+            (PartFace::Top, "top"),
+            (PartFace::Behind, "back"),
+        ] {
+            let base = dstv
+                .elements
+                .iter()
+                .filter(|element| element.get_facing() == &face)
+                .map(outline)
+                .collect::<String>();
+            let changes = self
+                .elements
+                .iter()
+                .flat_map(|change| {
+                    let old = change.old.iter().map(|e| (e, "red"));
+                    let new = change.new.iter().map(|e| match change.kind {
+                        ChangeKind::Modified => (e, "orange"),
+                        _ => (e, "green"),
+                    });
+                    old.chain(new)
+                })
+                .filter(|(element, _)| element.get_facing() == &face)
+                .map(|(element, color)| {
+                    format!(
+                        "<g fill=\"{color}\" fill-opacity=\"0.3\" stroke=\"{color}\" stroke-width=\"2\">{}</g>",
+                        outline(element)
+                    )
+                })
+                .collect::<String>();
+            if base.is_empty() && changes.is_empty() {
+                continue;
+            }
+            let transform = match face {
+                PartFace::Top => {
+                    format!(
+                        "translate(0,{}) scale(1, -1)",
+                        offset + *header.flange_width
+                    )
+                }
+                _ => format!("translate(0,{offset})"),
+            };
+            svg.push_str(&format!(
+                "<g transform=\"{transform}\" id=\"{id}\"><g fill=\"none\" stroke=\"grey\">{base}</g>{changes}</g>"
+            ));
+            offset += match face {
+                PartFace::Front | PartFace::Behind => *header.profile_height,
+                _ => *header.flange_width,
+            };
+        }
+        format!(
+            "<svg viewbox=\"0 0 {} {}\" width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">{}</svg>",
+            header.length, offset, header.length, offset, svg
+        )
+    }
+}
+
+impl Display for DstvDiff {
+    /// Writes the changes, one per line
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for change in &self.header {
+            writeln!(f, "header {change}")?;
+        }
+        for change in &self.elements {
+            writeln!(f, "{}: {change}", change.face().code())?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns a short description of an element, e.g. `hole Ø22 at (100, 50)`
+fn describe(element: &DstvElementType) -> String {
+    match element {
+        DstvElementType::OuterBorder(e) => format!("outer contour of {} points", e.contour.len()),
+        DstvElementType::InnerBorder(e) => format!("inner contour of {} points", e.contour.len()),
+        DstvElementType::Hole(e) => {
+            format!("hole Ø{} at ({}, {})", e.diameter, e.x_coord, e.y_coord)
+        }
+        DstvElementType::Slot(e) => format!(
+            "slot Ø{} {}x{} at ({}, {})",
+            e.diameter, e.slot_length, e.slot_width, e.x_coord, e.y_coord
+        ),
+        DstvElementType::Numeration(e) => {
+            format!("mark `{}` at ({}, {})", e.text, e.x_coord, e.y_coord)
+        }
+        DstvElementType::Cut(e) => format!(
+            "cut through ({}, {}, {})",
+            e.sp_point_x, e.sp_point_y, e.sp_point_z
+        ),
+        DstvElementType::Bend(e) => format!(
+            "bend from ({}, {}) to ({}, {})",
+            e.origin_x, e.origin_y, e.finish_x, e.finish_y
+        ),
+        DstvElementType::Custom(e) => {
+            let dstv = e.to_dstv();
+            format!("`{}` block", dstv.lines().next().unwrap_or_default().trim())
+        }
+        DstvElementType::Unknown(e) => format!("unknown `{}` block", e.code),
+    }
+}
+
+/// Returns the outline of an element as SVG, without fill or stroke
+fn outline(element: &DstvElementType) -> String {
+    let polygon = |contour: &[BorderPoint]| {
+        let points = geometry::flatten(&geometry::contour_segments(contour), FLATTEN_TOLERANCE);
+        let points = points
+            .iter()
+            .map(|p| format!("{},{}", p.x, p.y))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!("<polygon points=\"{points}\" />")
+    };
+    match element {
+        DstvElementType::OuterBorder(e) => polygon(&e.contour),
+        DstvElementType::InnerBorder(e) => polygon(&e.contour),
+        DstvElementType::Hole(e) => format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" />",
+            e.x_coord,
+            e.y_coord,
+            e.diameter / 2.0
+        ),
+        DstvElementType::Slot(e) => format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" transform=\"rotate({} {} {})\" />",
+            e.x_coord - e.diameter / 2.0,
+            e.y_coord - e.diameter / 2.0,
+            e.slot_length + e.diameter,
+            e.slot_width + e.diameter,
+            e.diameter / 2.0,
+            e.angle,
+            e.x_coord,
+            e.y_coord
+        ),
+        DstvElementType::Numeration(e) => format!(
+            "<text x=\"{}\" y=\"{}\" font-size=\"{}\">{}</text>",
+            e.x_coord, e.y_coord, e.letterheight, e.text
+        ),
+        element => element.to_svg(),
+    }
+}
+
+/// Checks whether two values are equal within a tolerance
+fn close(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() <= tolerance
+}
+
+/// Returns the segments of a contour without zero length segments, each as a start
+/// point, an end point and a radius
+fn contour_key(contour: &[BorderPoint], tolerance: f64) -> Vec<(Point, Point, f64)> {
+    geometry::contour_segments(contour)
+        .into_iter()
+        .filter(|segment| segment.chord() > tolerance)
+        .map(|segment| (segment.start, segment.end, segment.radius))
+        .collect()
+}
+
+/// Checks whether two contours describe the same shape, regardless of their starting
+/// point and direction
+fn same_contour(a: &[BorderPoint], b: &[BorderPoint], tolerance: f64) -> bool {
+    let (a, b) = (contour_key(a, tolerance), contour_key(b, tolerance));
+    let same_point = |p: &Point, q: &Point| p.distance(q) <= tolerance;
+    a.len() == b.len()
+        && a.iter().all(|(start, end, radius)| {
+            b.iter().any(|(s, e, r)| {
+                (same_point(start, s) && same_point(end, e) && close(*radius, *r, tolerance))
+                    || (same_point(start, e) && same_point(end, s) && close(*radius, -r, tolerance))
+            })
+        })
+}
+
+/// Checks whether two elements are the same, within the tolerance
+fn same(a: &DstvElementType, b: &DstvElementType, tolerance: f64) -> bool {
+    let eq = |x: f64, y: f64| close(x, y, tolerance);
+    if a.get_facing() != b.get_facing() {
+        return false;
+    }
+    match (a, b) {
+        (DstvElementType::OuterBorder(a), DstvElementType::OuterBorder(b)) => {
+            same_contour(&a.contour, &b.contour, tolerance)
+        }
+        (DstvElementType::InnerBorder(a), DstvElementType::InnerBorder(b)) => {
+            same_contour(&a.contour, &b.contour, tolerance)
+        }
+        (DstvElementType::Hole(_), DstvElementType::Hole(_))
+        | (DstvElementType::Slot(_), DstvElementType::Slot(_))
+        | (DstvElementType::Numeration(_), DstvElementType::Numeration(_)) => {
+            same_size(a, b, tolerance)
+                && match (a.position(), b.position()) {
+                    (Some(a), Some(b)) => a.distance(&b) <= tolerance,
+                    _ => false,
+                }
+        }
+        (DstvElementType::Cut(a), DstvElementType::Cut(b)) => {
+            eq(*a.sp_point_x, *b.sp_point_x)
+                && eq(*a.sp_point_y, *b.sp_point_y)
+                && eq(*a.sp_point_z, *b.sp_point_z)
+                && eq(a.nor_vec_x, b.nor_vec_x)
+                && eq(a.nor_vec_y, b.nor_vec_y)
+                && eq(a.nor_vec_z, b.nor_vec_z)
+        }
+        (DstvElementType::Bend(a), DstvElementType::Bend(b)) => {
+            eq(*a.angle, *b.angle)
+                && eq(*a.radius, *b.radius)
+                && eq(*a.origin_x, *b.origin_x)
+                && eq(*a.origin_y, *b.origin_y)
+                && eq(*a.finish_x, *b.finish_x)
+                && eq(*a.finish_y, *b.finish_y)
+        }
+        (DstvElementType::Custom(a), DstvElementType::Custom(b)) => a == b,
+        (DstvElementType::Unknown(a), DstvElementType::Unknown(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Checks whether two elements of the same kind have the same size and shape, ignoring
+/// their position
+fn same_size(a: &DstvElementType, b: &DstvElementType, tolerance: f64) -> bool {
+    let eq = |x: f64, y: f64| close(x, y, tolerance);
+    match (a, b) {
+        (DstvElementType::Hole(a), DstvElementType::Hole(b)) => {
+            eq(*a.diameter, *b.diameter) && eq(*a.depth_or_zero(), *b.depth_or_zero())
+        }
+        (DstvElementType::Slot(a), DstvElementType::Slot(b)) => {
+            eq(*a.diameter, *b.diameter)
+                && eq(*a.depth, *b.depth)
+                && eq(*a.slot_length, *b.slot_length)
+                && eq(*a.slot_width, *b.slot_width)
+                && eq(*a.angle, *b.angle)
+        }
+        (DstvElementType::Numeration(a), DstvElementType::Numeration(b)) => {
+            a.text == b.text && eq(*a.angle, *b.angle) && eq(*a.letterheight, *b.letterheight)
+        }
+        _ => false,
+    }
+}
+
+/// Checks whether two elements are of the same kind
+fn same_kind(a: &DstvElementType, b: &DstvElementType) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+}
+
+impl Dstv {
+    /// Compares this DSTV file with a newer revision.
+    /// Elements are matched by their position and size, not by their order in the file,
+    /// and contours by their shape, regardless of the order of their points.
+    /// # Arguments
+    /// * `new` - The newer revision of the file
+    /// * `options` - The tolerances to compare with
+    /// # Returns
+    /// The changes from this file to the new one
+    pub fn diff(&self, new: &Dstv, options: &DiffOptions) -> DstvDiff {
+        let tolerance = options.tolerance;
+        let old_measured = measured_values(&self.header);
+        let new_measured = measured_values(&new.header);
+        let measured = |field: &str| {
+            let value = |values: &[(&str, Option<f64>)]| {
+                values
+                    .iter()
+                    .find(|(name, _)| *name == field)
+                    .map(|(_, value)| *value)
+            };
+            value(&old_measured).zip(value(&new_measured))
+        };
+        let old_header = header_values(&self.header);
+        let new_header = header_values(&new.header);
+        let header = HEADER_FIELDS
+            .iter()
+            .zip(old_header.into_iter().zip(new_header))
+            .filter(|(field, (old, new))| match measured(field) {
+                Some((Some(a), Some(b))) => !close(a, b, tolerance),
+                _ => old != new,
+            })
+            .map(|(field, (old, new))| HeaderChange { field, old, new })
+            .collect();
+
+        let mut old = self.elements.iter().collect::<Vec<_>>();
+        let mut added = vec![];
+        for element in &new.elements {
+            match old.iter().position(|o| same(o, element, tolerance)) {
+                Some(index) => {
+                    old.remove(index);
+                }
+                None => added.push(element),
+            }
+        }
+
+        // pair the remaining elements: the same size nearby means moved, the same kind at
+        // the same place means modified
+        let mut candidates = vec![];
+        for (i, o) in old.iter().enumerate() {
+            for (j, n) in added.iter().enumerate() {
+                if o.get_facing() != n.get_facing() || !same_kind(o, n) {
+                    continue;
+                }
+                let distance = match (o.position(), n.position()) {
+                    (Some(a), Some(b)) => a.distance(&b),
+                    _ => f64::INFINITY,
+                };
+                let kind = match o {
+                    DstvElementType::OuterBorder(_) => Some(ChangeKind::Modified),
+                    _ if same_size(o, n, tolerance) && distance <= options.max_move => {
+                        Some(ChangeKind::Moved)
+                    }
+                    _ if distance <= options.max_move => Some(ChangeKind::Modified),
+                    _ => None,
+                };
+                if let Some(kind) = kind {
+                    candidates.push((distance, i, j, kind));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        let mut paired_old = vec![false; old.len()];
+        let mut paired_new = vec![false; added.len()];
+        let mut pairs = vec![];
+        for (_, i, j, kind) in candidates {
+            if paired_old[i] || paired_new[j] {
+                continue;
+            }
+            paired_old[i] = true;
+            paired_new[j] = true;
+            pairs.push((i, j, kind));
+        }
+        // report the pairs in the order of the old file
+        pairs.sort_by_key(|pair| pair.0);
+        let mut elements = pairs
+            .into_iter()
+            .map(|(i, j, kind)| ElementChange {
+                kind,
+                old: Some(old[i].clone()),
+                new: Some(added[j].clone()),
+            })
+            .collect::<Vec<_>>();
+        elements.extend(
+            old.iter()
+                .zip(&paired_old)
+                .filter(|(_, paired)| !**paired)
+                .map(|(element, _)| ElementChange {
+                    kind: ChangeKind::Removed,
+                    old: Some((*element).clone()),
+                    new: None,
+                }),
+        );
+        elements.extend(
+            added
+                .iter()
+                .zip(&paired_new)
+                .filter(|(_, paired)| !**paired)
+                .map(|(element, _)| ElementChange {
+                    kind: ChangeKind::Added,
+                    old: None,
+                    new: Some((*element).clone()),
+                }),
+        );
+        DstvDiff { header, elements }
+    }
+}
@@ -1,14 +1,17 @@
 use crate::{
-    dstv_element::ParseDstvError,
+    dstv_element::{Block, ParseDstvError},
     dstv_element_type::DstvElementType,
+    encoding::read_file,
     prelude::{
-        Bend, Cut, DstvElement, Header, Hole, InnerBorder, Numeration, OuterBorder, PartFace, Slot,
+        Bend, Cut, DstvElement, ElementRegistry, Encoding, Header, Hole, InnerBorder, Numeration,
+        OuterBorder, PartFace, Slot, SyntaxTree, UnknownBlock,
     },
 };
-use std::iter::Peekable;
 
 /// Represents a DSTV file
 /// Includes a header and a vector of DSTV elements
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Dstv {
     /// The header of the DSTV file containing information about the project, phase, etc.
     pub header: Header,
@@ -16,67 +19,121 @@ pub struct Dstv {
     pub elements: Vec<DstvElementType>,
 }
 
-const HOLE_TYPE: &str = "BO";
+pub(crate) const HOLE_TYPE: &str = "BO";
 const CUT_TYPE: &str = "SC";
 const BEND_TYPE: &str = "KA";
 const OUTER_BORDER_TYPE: &str = "AK";
 const INNER_BORDER_TYPE: &str = "IK";
 const NUMERATION_TYPE: &str = "SI";
-const START: &str = "ST";
-const END: &str = "EN";
-
-/// Helper function to parse a line into a specific `DstvElementType` variant
-fn parse_dstv_element(
-    element_type: &str,
-    lines: &Vec<&str>,
-) -> Result<DstvElementType, ParseDstvError> {
-    match element_type {
-        OUTER_BORDER_TYPE => OuterBorder::from_lines(lines).map(DstvElementType::OuterBorder),
-        INNER_BORDER_TYPE => InnerBorder::from_lines(lines).map(DstvElementType::InnerBorder),
-        _ => match element_type {
-            CUT_TYPE => Cut::from_str(lines[0]).map(DstvElementType::Cut),
-            BEND_TYPE => Bend::from_str(lines[0]).map(DstvElementType::Bend),
-            HOLE_TYPE => match lines[0].split_whitespace().count() > 7 {
-                true => Slot::from_str(lines[0]).map(DstvElementType::Slot),
-                false => Hole::from_str(lines[0]).map(DstvElementType::Hole),
-            },
-            NUMERATION_TYPE => Numeration::from_str(lines[0]).map(DstvElementType::Numeration),
-            _ => Err(ParseDstvError::new(&format!(
-                "Unknown element type: `{}`",
-                element_type
-            ))),
-        },
+pub(crate) const START: &str = "ST";
+pub(crate) const END: &str = "EN";
+
+/// Helper function to parse a block into a specific `DstvElementType` variant.
+/// Blocks registered in the registry are read by their parser, other blocks that are
+/// not standard are kept as an `UnknownBlock` unless the registry skips them.
+/// # Returns
+/// The element, or `None` if the registry skips the block
+/// # Error
+/// * If the block is invalid, naming its line
+pub(crate) fn parse_dstv_element(
+    block: &Block,
+    registry: &ElementRegistry,
+) -> Result<Option<DstvElementType>, ParseDstvError> {
+    if let Some(element) = registry.parse(block) {
+        return element
+            .map(|element| Some(DstvElementType::Custom(element)))
+            .map_err(|e| block.locate(e));
     }
+    let element = match block.code {
+        OUTER_BORDER_TYPE => OuterBorder::from_block(block).map(DstvElementType::OuterBorder),
+        INNER_BORDER_TYPE => InnerBorder::from_block(block).map(DstvElementType::InnerBorder),
+        CUT_TYPE => Cut::from_block(block).map(DstvElementType::Cut),
+        BEND_TYPE => Bend::from_block(block).map(DstvElementType::Bend),
+        HOLE_TYPE => match block.first_line().split_whitespace().count() > 7 {
+            true => Slot::from_block(block).map(DstvElementType::Slot),
+            false => Hole::from_block(block).map(DstvElementType::Hole),
+        },
+        NUMERATION_TYPE => Numeration::from_block(block).map(DstvElementType::Numeration),
+        _ if registry.skips_unknown() => return Ok(None),
+        _ => UnknownBlock::from_block(block).map(DstvElementType::Unknown),
+    };
+    element.map(Some).map_err(|e| block.locate(e))
 }
 
 impl Dstv {
+    /// Reads a part from a file, detecting whether it is UTF-8 or Windows-1252
+    /// # Arguments
+    /// * `file_path` - The path of the file
+    /// # Error
+    /// * If the file cannot be read
+    /// * If the header is invalid
     pub fn from_file<P: AsRef<std::path::Path>>(file_path: P) -> Result<Self, ParseDstvError> {
-        let file_path = file_path.as_ref();
-        let file = std::fs::read_to_string(file_path).map_err(|e| {
-            ParseDstvError::new(&format!("Unable to read file: `{file_path:#?}`\t{e}"))
-        })?;
-        Self::from_str(&file)
+        Self::from_file_detect(file_path).map(|(dstv, _)| dstv)
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str<S: AsRef<str>>(file: S) -> Result<Self, ParseDstvError> {
-        let file_content = file.as_ref();
-        let mut lines = file_content
-            .lines()
-            .filter(|line| !line.trim().starts_with('*'))
-            .peekable();
+        Self::from_syntax(&SyntaxTree::parse(file.as_ref()))
+    }
 
-        let header_lines = extract_header_lines(&mut lines);
-        let header = Header::from_lines(header_lines)
-            .map_err(|e| ParseDstvError::from_err("Invalid Header", e))?;
+    /// Reads a part from the contents of a file like `from_str`, but fails on an invalid
+    /// block instead of skipping it
+    /// # Arguments
+    /// * `file` - The contents of the file
+    /// # Error
+    /// * If the header is invalid
+    /// * If a block is invalid, naming the block and its line
+    pub fn from_str_strict<S: AsRef<str>>(file: S) -> Result<Self, ParseDstvError> {
+        Self::from_syntax_strict(&SyntaxTree::parse(file.as_ref()))
+    }
 
-        let element_groups = group_elements_by_type(lines);
+    /// Reads a part from a file, reading the blocks beyond the DSTV standard with the
+    /// parsers of a registry
+    /// # Arguments
+    /// * `file_path` - The path of the file
+    /// * `registry` - The registry of the parsers
+    /// # Error
+    /// * If the file cannot be read
+    /// * If the header is invalid
+    pub fn from_file_with_registry<P: AsRef<std::path::Path>>(
+        file_path: P,
+        registry: &ElementRegistry,
+    ) -> Result<Self, ParseDstvError> {
+        let bytes = read_file(file_path.as_ref())?;
+        Self::from_bytes_with_registry(&bytes, Encoding::detect(&bytes), registry)
+    }
+
+    /// Reads a part from the contents of a file, reading the blocks beyond the DSTV
+    /// standard with the parsers of a registry
+    /// # Arguments
+    /// * `file` - The contents of the file
+    /// * `registry` - The registry of the parsers
+    /// # Error
+    /// * If the header is invalid
+    pub fn from_str_with_registry<S: AsRef<str>>(
+        file: S,
+        registry: &ElementRegistry,
+    ) -> Result<Self, ParseDstvError> {
+        Self::from_syntax(&SyntaxTree::parse_with_registry(file.as_ref(), registry))
+    }
 
-        let elements = parse_elements(element_groups)?;
-        Ok(Self { header, elements })
+    /// Converts the DSTV file back to the contents of an NC file
+    /// # Returns
+    /// A string holding the header and the blocks of all elements, ending with `EN`
+    pub fn to_dstv(&self) -> String {
+        let mut dstv = format!("{START}\n{}", self.header.to_dstv());
+        for element in &self.elements {
+            element
+                .write(&mut dstv)
+                .expect("writing to a string does not fail");
+        }
+        dstv + END + "\n"
     }
 
     pub fn to_svg(&mut self) -> String {
         self.elements.sort_by_key(|element| element.get_index());
+        // faces without a contour are only drawn when their ends are not square or cut
+        let outlines = self.default_outlines();
         let mut svg = String::new();
         let mut offset = 0.0;
 
@@ -86,19 +143,21 @@ impl Dstv {
             (PartFace::Top, "top"),
             (PartFace::Behind, "back"),
         ] {
-            let elements_svg = self
-                .elements
+            let elements_svg = outlines
                 .iter()
+                .chain(self.elements.iter())
                 .filter(|element| element.get_facing() == face)
                 .map(|element| element.to_svg())
                 .collect::<Vec<_>>()
-                .join("");
+                .join("")
+                // cuts are planes through the part, drawn on every face they cross
+                + &self.cuts_to_svg(face);
 
             if !elements_svg.is_empty() {
                 let transform = if *face == PartFace::Top {
                     format!(
                         "translate(0,{}) scale(1, -1)",
-                        offset + self.header.flange_width
+                        offset + *self.header.flange_width
                     )
                 } else {
                     format!("translate(0,{})", offset)
@@ -110,9 +169,9 @@ impl Dstv {
                 ));
 
                 offset += if *face == PartFace::Front || *face == PartFace::Behind {
-                    self.header.profile_height
+                    *self.header.profile_height
                 } else {
-                    self.header.flange_width
+                    *self.header.flange_width
                 };
             }
         }
@@ -123,73 +182,3 @@ impl Dstv {
         )
     }
 }
-
-/// Extracts header lines from the beginning of the file iterator `lines`.
-/// Stops when it encounters the first empty line following non-empty lines.
-fn extract_header_lines<'a, I>(lines: &mut Peekable<I>) -> Vec<&'a str>
-where
-    I: Iterator<Item = &'a str> + Clone,
-{
-    let mut out = Vec::new();
-    while let Some(line) = lines.peek() {
-        let line = line.trim();
-        if out.len() > 23 {
-            // 24 Lines is the correct length for Header.
-            // We leave this item unskipped, as it will likely be an "AK" block
-            break;
-        } else if !((line.is_empty() && out.len() < 20) || line == START) {
-            // If we have an empty line BEFORE notes, ignore
-            out.push(line);
-        }
-        // With the line consumed, jump to next
-        lines.next();
-    }
-    out
-}
-
-/// Groups lines into (element type, lines) pairs based on the first two characters.
-/// Stops processing when it encounters the `END` line.
-fn group_elements_by_type<'a, I>(lines: I) -> Vec<(&'a str, Vec<&'a str>)>
-where
-    I: Iterator<Item = &'a str>,
-{
-    lines
-        .into_iter()
-        .filter(|line| !line.eq(&END))
-        .filter(|line| !line.trim().is_empty())
-        .fold(Vec::<(&str, Vec<&str>)>::new(), |mut elements, line| {
-            // check if the first two characters of the line are empty
-            let current_element = elements.last();
-            match line[..2].trim().is_empty() {
-                true => {
-                    if current_element.is_some()
-                        // chef special for holes, which have a single element indicating the hole type
-                        // followed by multiple hole lines
-                        && current_element.unwrap().0.eq(HOLE_TYPE)
-                        && current_element.unwrap().1.len() == 1
-                    {
-                        elements.push((HOLE_TYPE, vec![line]));
-                    } else {
-                        elements.last_mut().unwrap().1.push(line);
-                    }
-                }
-                false => {
-                    elements.push((line, Vec::new()));
-                }
-            }
-            elements
-        })
-        .into_iter()
-        .collect::<Vec<_>>()
-}
-
-/// Parses each element group into `DstvElementType`.
-fn parse_elements(
-    element_groups: Vec<(&str, Vec<&str>)>,
-) -> Result<Vec<DstvElementType>, ParseDstvError> {
-    element_groups
-        .into_iter()
-        .map(|(element_type, lines)| parse_dstv_element(element_type, &lines))
-        .filter(|element| element.is_ok())
-        .collect::<Result<Vec<_>, _>>()
-}
@@ -1,8 +1,8 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Write};
 
-use crate::prelude::PartFace;
+use crate::prelude::{PartFace, Point};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ParseDstvError {
     message: String,
 }
@@ -28,21 +28,115 @@ impl std::fmt::Display for ParseDstvError {
 
 impl std::error::Error for ParseDstvError {}
 
+/// A block of a DSTV file: the identifier on its first line and the data lines that
+/// follow it. Blocks read from a file also know the line numbers of their data lines,
+/// so errors can point at them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Block<'a> {
+    /// The identifier of the block, e.g. `BO`, or empty if it is not known
+    pub code: &'a str,
+    /// The data lines of the block
+    pub lines: &'a [&'a str],
+    /// The line numbers of the data lines, starting at 1, or empty if the block was not
+    /// read from a file
+    pub line_numbers: &'a [usize],
+}
+
+impl<'a> Block<'a> {
+    /// Creates a block without line numbers
+    /// # Arguments
+    /// * `code` - The identifier of the block
+    /// * `lines` - The data lines of the block
+    pub fn new(code: &'a str, lines: &'a [&'a str]) -> Self {
+        Self {
+            code,
+            lines,
+            line_numbers: &[],
+        }
+    }
+
+    /// Sets the line numbers of the data lines
+    /// # Arguments
+    /// * `line_numbers` - The line number of each data line, starting at 1
+    pub fn with_line_numbers(mut self, line_numbers: &'a [usize]) -> Self {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    /// Returns the first data line, which holds the values of single line elements
+    /// # Returns
+    /// The first line, or an empty line if the block has none
+    pub fn first_line(&self) -> &'a str {
+        self.lines.first().copied().unwrap_or_default()
+    }
+
+    /// Returns the line number of a data line
+    /// # Arguments
+    /// * `index` - The index of the data line within the block
+    pub fn line_number(&self, index: usize) -> Option<usize> {
+        self.line_numbers.get(index).copied()
+    }
+
+    /// Adds the identifier and the line number of the block to an error reading it
+    /// # Arguments
+    /// * `error` - The error reading the block
+    pub fn locate(&self, error: ParseDstvError) -> ParseDstvError {
+        match self.line_number(0) {
+            Some(line) => ParseDstvError::from_err(
+                format!("Invalid `{}` block at line {line}", self.code),
+                error,
+            ),
+            None => error,
+        }
+    }
+}
+
 pub trait DstvElement {
-    /// Creates a new DSTV element from a string slice.
+    /// Creates a new DSTV element from its block.
     /// # Arguments
-    /// * `line` - A string slice that holds the line of the DSTV file
+    /// * `block` - The block holding the data lines of the element
     /// # Returns
     /// * A DSTV element
-    fn from_str(line: &str) -> Result<Self, ParseDstvError>
+    fn from_block(block: &Block) -> Result<Self, ParseDstvError>
     where
         Self: Sized;
 
+    /// Creates a new DSTV element from the data lines of its block, separated by line
+    /// breaks. For most elements this is a single line.
+    /// # Arguments
+    /// * `lines` - A string slice that holds the data lines of the DSTV file
+    /// # Returns
+    /// * A DSTV element
+    fn from_str(lines: &str) -> Result<Self, ParseDstvError>
+    where
+        Self: Sized,
+    {
+        let lines = lines.lines().collect::<Vec<_>>();
+        Self::from_block(&Block::new("", &lines))
+    }
+
     /// Convert the element to an SVG representation.
     /// # Returns
     /// A string containing an SVG representation of the element.
     fn to_svg(&self) -> String;
 
+    /// Writes the element as its block in a DSTV file: the block identifier and the
+    /// data lines of the element, each line ending with a newline.
+    /// # Arguments
+    /// * `out` - The text to write the block to
+    fn write(&self, out: &mut dyn Write) -> fmt::Result;
+
+    /// Converts the element to its block in a DSTV file.
+    /// # Returns
+    /// A string containing the block identifier and the data lines of the element,
+    /// each line ending with a newline.
+    fn to_dstv(&self) -> String {
+        let mut block = String::new();
+        self.write(&mut block)
+            .expect("writing to a string does not fail");
+        block
+    }
+
     /// Returns the index of the element in the DSTV file.
     /// This is used to determine the order in which the elements are drawn.
     /// The lower the index, the earlier the element is drawn.
@@ -53,4 +147,20 @@ pub trait DstvElement {
     /// Returns the flange code of the element.
     /// This is used to determine the side of the element that is drawn.
     fn get_facing(&self) -> &PartFace;
+
+    /// Returns the point the element is placed at on its face, such as the centre of a
+    /// hole, used to match elements between revisions.
+    /// # Returns
+    /// The point, or `None` if the element has no position
+    fn position(&self) -> Option<Point> {
+        None
+    }
+
+    /// Returns the area the element takes up on its face.
+    /// # Returns
+    /// The lower left and the upper right corner, or `None` if the element has no
+    /// extent
+    fn bounding_box(&self) -> Option<(Point, Point)> {
+        None
+    }
 }
@@ -1,8 +1,15 @@
 use crate::prelude::{
-    Bend, Cut, DstvElement, Hole, InnerBorder, Numeration, OuterBorder, PartFace, Slot,
+    Bend, CustomElement, Cut, DstvElement, Hole, InnerBorder, Numeration, OuterBorder, PartFace,
+    Point, Slot, UnknownBlock,
 };
+use std::fmt::{self, Write};
 
-#[derive(Debug)]
+/// A single element of a DSTV file.
+/// When serialized, the element is tagged with its type in snake case, e.g.
+/// `{"type": "hole", "fl_code": "v", ...}`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
 pub enum DstvElementType {
     OuterBorder(OuterBorder),
     InnerBorder(InnerBorder),
@@ -11,45 +18,94 @@ pub enum DstvElementType {
     Slot(Slot),
     Hole(Hole),
     Numeration(Numeration),
+    /// An element of a block registered in an `ElementRegistry`. Custom elements are
+    /// serialized as the block they write, in the shape of an `UnknownBlock`, and are
+    /// deserialized as an `UnknownBlock` that writes the same block.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "serialize_custom",
+            deserialize_with = "deserialize_custom"
+        )
+    )]
+    Custom(Box<dyn CustomElement>),
+    /// A block that is neither standard nor registered, kept as it was read
+    Unknown(UnknownBlock),
+}
+
+/// Serializes a custom element as the unknown block holding the block it writes
+#[cfg(feature = "serde")]
+#[allow(clippy::borrowed_box)] // serde passes the field as it is declared
+fn serialize_custom<S: serde::Serializer>(
+    element: &Box<dyn CustomElement>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::Serialize;
+    UnknownBlock::from_str(&element.to_dstv())
+        .map_err(serde::ser::Error::custom)?
+        .serialize(serializer)
+}
+
+/// Deserializes a custom element as the unknown block it was serialized as
+#[cfg(feature = "serde")]
+fn deserialize_custom<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Box<dyn CustomElement>, D::Error> {
+    use serde::Deserialize;
+    Ok(Box::new(UnknownBlock::deserialize(deserializer)?))
 }
 
 impl DstvElementType {
-    /// Returns the SVG representation of each element based on type
-    pub fn to_svg(&self) -> String {
+    /// Returns the element as a `DstvElement`, whatever its type
+    pub fn as_element(&self) -> &dyn DstvElement {
         match self {
-            DstvElementType::OuterBorder(e) => e.to_svg(),
-            DstvElementType::InnerBorder(e) => e.to_svg(),
-            DstvElementType::Cut(e) => e.to_svg(),
-            DstvElementType::Bend(e) => e.to_svg(),
-            DstvElementType::Slot(e) => e.to_svg(),
-            DstvElementType::Hole(e) => e.to_svg(),
-            DstvElementType::Numeration(e) => e.to_svg(),
+            DstvElementType::OuterBorder(e) => e,
+            DstvElementType::InnerBorder(e) => e,
+            DstvElementType::Cut(e) => e,
+            DstvElementType::Bend(e) => e,
+            DstvElementType::Slot(e) => e,
+            DstvElementType::Hole(e) => e,
+            DstvElementType::Numeration(e) => e,
+            DstvElementType::Custom(e) => e.as_element(),
+            DstvElementType::Unknown(e) => e,
         }
     }
 
+    /// Returns the SVG representation of each element based on type
+    pub fn to_svg(&self) -> String {
+        self.as_element().to_svg()
+    }
+
+    /// Writes the DSTV block of the element
+    /// # Arguments
+    /// * `out` - The text to write the block to
+    pub fn write(&self, out: &mut dyn Write) -> fmt::Result {
+        self.as_element().write(out)
+    }
+
+    /// Returns the DSTV block of each element based on type
+    pub fn to_dstv(&self) -> String {
+        self.as_element().to_dstv()
+    }
+
     /// Returns the index used to determine the rendering order
     pub fn get_index(&self) -> usize {
-        match self {
-            DstvElementType::OuterBorder(e) => e.get_index(),
-            DstvElementType::InnerBorder(e) => e.get_index(),
-            DstvElementType::Cut(e) => e.get_index(),
-            DstvElementType::Bend(e) => e.get_index(),
-            DstvElementType::Slot(e) => e.get_index(),
-            DstvElementType::Hole(e) => e.get_index(),
-            DstvElementType::Numeration(e) => e.get_index(),
-        }
+        self.as_element().get_index()
     }
 
     /// Returns the face direction of each element
     pub fn get_facing(&self) -> &PartFace {
-        match self {
-            DstvElementType::OuterBorder(e) => e.get_facing(),
-            DstvElementType::InnerBorder(e) => e.get_facing(),
-            DstvElementType::Cut(e) => e.get_facing(),
-            DstvElementType::Bend(e) => e.get_facing(),
-            DstvElementType::Slot(e) => e.get_facing(),
-            DstvElementType::Hole(e) => e.get_facing(),
-            DstvElementType::Numeration(e) => e.get_facing(),
-        }
+        self.as_element().get_facing()
+    }
+
+    /// Returns the point the element is placed at on its face
+    pub fn position(&self) -> Option<Point> {
+        self.as_element().position()
+    }
+
+    /// Returns the lower left and the upper right corner of the area the element takes
+    /// up on its face
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        self.as_element().bounding_box()
     }
 }
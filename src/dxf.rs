@@ -0,0 +1,189 @@
+use crate::{
+    dstv_element_type::DstvElementType,
+    geometry::{contour_segments, Point, Segment, EPSILON},
+    prelude::{BendDirection, Border, BorderPoint, Dstv, DstvElement, PartFace, Slot},
+};
+
+/// The height of the labels of bend lines, in mm
+const LABEL_HEIGHT: f64 = 10.0;
+
+/// A vertex of a polyline with the bulge of the segment starting at it. The bulge is
+/// the tangent of a quarter of the arc angle, positive for counter-clockwise arcs.
+type Vertex = (Point, f64);
+
+/// Writes a closed polyline in R12 style, with a vertex entity per corner
+fn polyline(layer: &str, vertices: &[Vertex]) -> String {
+    let mut dxf = format!("0\nPOLYLINE\n8\n{layer}\n66\n1\n70\n1\n10\n0\n20\n0\n30\n0\n");
+    for (point, bulge) in vertices {
+        dxf.push_str(&format!(
+            "0\nVERTEX\n8\n{layer}\n10\n{}\n20\n{}\n42\n{}\n",
+            point.x, point.y, bulge
+        ));
+    }
+    dxf + &format!("0\nSEQEND\n8\n{layer}\n")
+}
+
+/// Writes a single line of text
+fn text(layer: &str, at: Point, height: f64, angle: f64, value: &str) -> String {
+    format!(
+        "0\nTEXT\n8\n{layer}\n10\n{}\n20\n{}\n40\n{height}\n1\n{value}\n50\n{angle}\n",
+        at.x, at.y
+    )
+}
+
+/// Returns the vertices of a contour, keeping its arcs as bulges
+fn contour_vertices(contour: &[BorderPoint]) -> Vec<Vertex> {
+    contour_segments(contour)
+        .iter()
+        .filter(|segment| segment.chord() > EPSILON)
+        .map(|segment| (segment.start, bulge(segment)))
+        .collect()
+}
+
+/// Writes the bevels of a contour as labels in the middle of their edges
+fn bevel_labels(contour: &[BorderPoint]) -> String {
+    contour_segments(contour)
+        .iter()
+        .filter_map(|segment| {
+            let bevel = segment.bevel?;
+            let middle = Point::new(
+                (segment.start.x + segment.end.x) / 2.0,
+                (segment.start.y + segment.end.y) / 2.0,
+            );
+            let angle = (segment.end.y - segment.start.y)
+                .atan2(segment.end.x - segment.start.x)
+                .to_degrees();
+            Some(text(
+                "BEVEL",
+                middle,
+                LABEL_HEIGHT,
+                angle,
+                &bevel.to_string(),
+            ))
+        })
+        .collect()
+}
+
+/// Returns the bulge of a segment
+fn bulge(segment: &Segment) -> f64 {
+    segment.radius.signum() * (segment.sweep() / 4.0).tan()
+}
+
+/// Returns the vertices of the outline of a slot: the hole stretched into a rectangle
+/// with rounded corners, counter-clockwise
+fn slot_vertices(slot: &Slot) -> Vec<Vertex> {
+    let r = *slot.diameter / 2.0;
+    let (sin, cos) = slot.angle.to_radians().sin_cos();
+    let corner = (std::f64::consts::PI / 8.0).tan();
+    // the centres of the rounded corners and the directions in which their arcs start
+    let centres = [
+        (0.0, 0.0, (-cos, -sin)),
+        (*slot.slot_length, 0.0, (sin, -cos)),
+        (*slot.slot_length, *slot.slot_width, (cos, sin)),
+        (0.0, *slot.slot_width, (-sin, cos)),
+    ];
+    let mut vertices: Vec<Vertex> = vec![];
+    for (i, (u, v, start)) in centres.iter().enumerate() {
+        let centre = Point::new(
+            *slot.x_coord + u * cos - v * sin,
+            *slot.y_coord + u * sin + v * cos,
+        );
+        let end = centres[(i + 1) % 4].2;
+        let arc_start = Point::new(centre.x + r * start.0, centre.y + r * start.1);
+        // the straight side before this corner vanishes when the slot has no length or width
+        if let Some(last) = vertices.last() {
+            if last.0.distance(&arc_start) <= EPSILON {
+                vertices.pop();
+            }
+        }
+        vertices.push((arc_start, corner));
+        vertices.push((Point::new(centre.x + r * end.0, centre.y + r * end.1), 0.0));
+    }
+    if let (Some(first), Some(last)) = (vertices.first(), vertices.last()) {
+        if first.0.distance(&last.0) <= EPSILON {
+            vertices.pop();
+        }
+    }
+    vertices
+}
+
+impl Dstv {
+    /// Converts a face of the part to an ASCII DXF drawing (R12), e.g. for a laser or
+    /// plasma cutter. The drawing uses the coordinates of the face in mm and puts each
+    /// kind of element on its own layer: `CONTOUR`, `INNER`, `HOLES`, `BEND_UP`,
+    /// `BEND_DOWN` and `MARKS`. Arcs in contours are kept as bulges and bevelled edges
+    /// are labelled on the `BEVEL` layer.
+    /// # Arguments
+    /// * `face` - The face to draw
+    /// # Returns
+    /// The contents of the DXF file
+    pub fn to_dxf(&self, face: &PartFace) -> String {
+        let mut entities = String::new();
+        let borders = self
+            .elements
+            .iter()
+            .filter_map(|element| match element {
+                DstvElementType::OuterBorder(border) if border.get_facing() == face => Some(border),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let trimmed = borders
+            .iter()
+            .any(|border| self.is_cut(face, &border.polygon()));
+        match borders.is_empty() || trimmed {
+            // cut or default outlines only have straight edges
+            true => self.face_outlines(face).iter().for_each(|outline| {
+                let vertices = outline.iter().map(|p| (*p, 0.0)).collect::<Vec<_>>();
+                entities.push_str(&polyline("CONTOUR", &vertices));
+            }),
+            false => borders.iter().for_each(|border| {
+                entities.push_str(&polyline("CONTOUR", &contour_vertices(&border.contour)));
+                entities.push_str(&bevel_labels(&border.contour));
+            }),
+        }
+        for element in self
+            .elements
+            .iter()
+            .filter(|element| element.get_facing() == face)
+        {
+            let entity = match element {
+                DstvElementType::InnerBorder(border) => {
+                    polyline("INNER", &contour_vertices(&border.contour))
+                        + &bevel_labels(&border.contour)
+                }
+                DstvElementType::Hole(hole) => format!(
+                    "0\nCIRCLE\n8\nHOLES\n10\n{}\n20\n{}\n40\n{}\n",
+                    hole.x_coord,
+                    hole.y_coord,
+                    hole.diameter / 2.0
+                ),
+                DstvElementType::Slot(slot) => polyline("HOLES", &slot_vertices(slot)),
+                DstvElementType::Bend(bend) => {
+                    let layer = match bend.direction() {
+                        BendDirection::Up => "BEND_UP",
+                        BendDirection::Down => "BEND_DOWN",
+                    };
+                    let (start, end) = (bend.origin(), bend.finish());
+                    let middle = Point::new((start.x + end.x) / 2.0, (start.y + end.y) / 2.0);
+                    let angle = (end.y - start.y).atan2(end.x - start.x).to_degrees();
+                    format!(
+                        "0\nLINE\n8\n{layer}\n10\n{}\n20\n{}\n11\n{}\n21\n{}\n",
+                        start.x, start.y, end.x, end.y
+                    ) + &text(layer, middle, LABEL_HEIGHT, angle, &bend.label())
+                }
+                DstvElementType::Numeration(mark) => text(
+                    "MARKS",
+                    Point::new(*mark.x_coord, *mark.y_coord),
+                    *mark.letterheight,
+                    *mark.angle,
+                    &mark.text,
+                ),
+                _ => String::new(),
+            };
+            entities.push_str(&entity);
+        }
+        format!(
+            "0\nSECTION\n2\nHEADER\n9\n$ACADVER\n1\nAC1009\n0\nENDSEC\n0\nSECTION\n2\nENTITIES\n{entities}0\nENDSEC\n0\nEOF\n"
+        )
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    dstv_element::ParseDstvError,
+    prelude::{Dstv, ElementRegistry, SyntaxTree},
+};
+use std::{fmt::Display, path::Path};
+
+/// The characters of Windows-1252 for the bytes `0x80` to `0x9F`. The bytes Windows-1252
+/// leaves undefined map to the control characters of Latin-1.
+const WINDOWS_1252: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+/// The character encoding of an NC file. The standard only allows ASCII, but files
+/// from German and Scandinavian detailers often hold characters such as `ä`, `ß` or
+/// `ø` in the text fields of the header, written in Latin-1 or Windows-1252.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Encoding {
+    /// UTF-8, which includes plain ASCII
+    Utf8,
+    /// ISO 8859-1, one byte per character
+    Latin1,
+    /// Windows-1252, Latin-1 with printable characters such as `€` in `0x80` to `0x9F`
+    Windows1252,
+}
+
+impl Encoding {
+    /// Detects the encoding of the contents of a file. Contents that are valid UTF-8
+    /// are read as UTF-8, anything else as Windows-1252, which can read any byte and
+    /// reads Latin-1 text the same.
+    /// # Arguments
+    /// * `bytes` - The contents of the file
+    pub fn detect(bytes: &[u8]) -> Self {
+        match std::str::from_utf8(bytes) {
+            Ok(_) => Encoding::Utf8,
+            Err(_) => Encoding::Windows1252,
+        }
+    }
+
+    /// Decodes the contents of a file
+    /// # Arguments
+    /// * `bytes` - The contents of the file
+    /// # Error
+    /// * If the contents are not valid UTF-8 when decoding UTF-8. Latin-1 and
+    ///   Windows-1252 decode any byte.
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, ParseDstvError> {
+        match self {
+            Encoding::Utf8 => String::from_utf8(bytes.to_vec())
+                .map_err(|e| ParseDstvError::from_err("Invalid UTF-8", e)),
+            Encoding::Latin1 => Ok(bytes.iter().map(|byte| char::from(*byte)).collect()),
+            Encoding::Windows1252 => Ok(bytes
+                .iter()
+                .map(|byte| match byte {
+                    0x80..=0x9F => WINDOWS_1252[usize::from(byte - 0x80)],
+                    _ => char::from(*byte),
+                })
+                .collect()),
+        }
+    }
+
+    /// Encodes text for writing to a file
+    /// # Arguments
+    /// * `text` - The contents of the file
+    /// # Error
+    /// * If the text holds a character the encoding cannot represent, e.g. `€` in
+    ///   Latin-1
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, ParseDstvError> {
+        match self {
+            Encoding::Utf8 => Ok(text.as_bytes().to_vec()),
+            Encoding::Latin1 | Encoding::Windows1252 => text
+                .chars()
+                .map(|c| self.encode_char(c))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| {
+                    let c = text.chars().find(|c| self.encode_char(*c).is_none());
+                    ParseDstvError::new(format!(
+                        "Cannot write `{}` as {self}",
+                        c.unwrap_or_default()
+                    ))
+                }),
+        }
+    }
+
+    /// Encodes a single character as a single byte
+    fn encode_char(&self, c: char) -> Option<u8> {
+        let in_table = WINDOWS_1252.iter().position(|known| *known == c);
+        match (self, u32::from(c)) {
+            (Encoding::Windows1252, _) if in_table.is_some() => in_table.map(|i| 0x80 + i as u8),
+            (Encoding::Windows1252, 0x80..=0x9F) => None,
+            (_, code) => u8::try_from(code).ok(),
+        }
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Latin1 => "Latin-1",
+            Encoding::Windows1252 => "Windows-1252",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Dstv {
+    /// Parses a part from the contents of a file in a known encoding
+    /// # Arguments
+    /// * `bytes` - The contents of the file
+    /// * `encoding` - The encoding of the file, e.g. from `Encoding::detect`
+    /// # Error
+    /// * If the contents cannot be decoded
+    /// * If the header is invalid
+    pub fn from_bytes(bytes: &[u8], encoding: Encoding) -> Result<Self, ParseDstvError> {
+        Self::from_bytes_with_registry(bytes, encoding, &ElementRegistry::default())
+    }
+
+    /// Parses a part from the contents of a file in a known encoding, reading the blocks
+    /// beyond the DSTV standard with the parsers of a registry
+    /// # Arguments
+    /// * `bytes` - The contents of the file
+    /// * `encoding` - The encoding of the file, e.g. from `Encoding::detect`
+    /// * `registry` - The registry of the parsers
+    /// # Error
+    /// * If the contents cannot be decoded
+    /// * If the header is invalid
+    pub fn from_bytes_with_registry(
+        bytes: &[u8],
+        encoding: Encoding,
+        registry: &ElementRegistry,
+    ) -> Result<Self, ParseDstvError> {
+        Self::from_str_with_registry(encoding.decode(bytes)?, registry)
+    }
+
+    /// Reads a part from a file in a known encoding. `Dstv::from_file` detects the
+    /// encoding instead.
+    /// # Arguments
+    /// * `file_path` - The path of the file
+    /// * `encoding` - The encoding of the file
+    /// # Error
+    /// * If the file cannot be read or decoded
+    /// * If the header is invalid
+    pub fn from_file_with_encoding<P: AsRef<Path>>(
+        file_path: P,
+        encoding: Encoding,
+    ) -> Result<Self, ParseDstvError> {
+        Self::from_bytes(&read_file(file_path.as_ref())?, encoding)
+    }
+
+    /// Reads a part from a file like `Dstv::from_file`, also returning the encoding that
+    /// was detected, so the part can be written back in it with `to_bytes`
+    /// # Arguments
+    /// * `file_path` - The path of the file
+    /// # Returns
+    /// The part and the encoding of the file
+    /// # Error
+    /// * If the file cannot be read
+    /// * If the header is invalid
+    pub fn from_file_detect<P: AsRef<Path>>(
+        file_path: P,
+    ) -> Result<(Self, Encoding), ParseDstvError> {
+        let bytes = read_file(file_path.as_ref())?;
+        let encoding = Encoding::detect(&bytes);
+        Ok((Self::from_bytes(&bytes, encoding)?, encoding))
+    }
+
+    /// Writes the part as the contents of an NC file in an encoding, e.g. the encoding
+    /// the part was read in
+    /// # Arguments
+    /// * `encoding` - The encoding to write
+    /// # Error
+    /// * If a text of the part cannot be written in the encoding
+    pub fn to_bytes(&self, encoding: Encoding) -> Result<Vec<u8>, ParseDstvError> {
+        encoding.encode(&self.to_dstv())
+    }
+}
+
+impl SyntaxTree {
+    /// Reads the structure of a DSTV file from its contents in a known encoding, which
+    /// the tree is written back in
+    /// # Arguments
+    /// * `bytes` - The contents of the file
+    /// * `encoding` - The encoding of the file, e.g. from `Encoding::detect`
+    /// # Error
+    /// * If the contents cannot be decoded
+    pub fn from_bytes(bytes: &[u8], encoding: Encoding) -> Result<Self, ParseDstvError> {
+        let mut tree = Self::parse(&encoding.decode(bytes)?);
+        tree.encoding = encoding;
+        Ok(tree)
+    }
+
+    /// Reads the structure of a DSTV file, detecting whether it is UTF-8 or
+    /// Windows-1252
+    /// # Arguments
+    /// * `file_path` - The path of the file
+    /// # Error
+    /// * If the file cannot be read
+    pub fn from_file<P: AsRef<Path>>(file_path: P) -> Result<Self, ParseDstvError> {
+        let bytes = read_file(file_path.as_ref())?;
+        Self::from_bytes(&bytes, Encoding::detect(&bytes))
+    }
+
+    /// Writes the file in the encoding it was read in
+    /// # Error
+    /// * If a text of the file cannot be written in the encoding
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ParseDstvError> {
+        self.to_bytes_with_encoding(self.encoding())
+    }
+
+    /// Writes the file in another encoding than it was read in
+    /// # Arguments
+    /// * `encoding` - The encoding to write
+    /// # Error
+    /// * If a text of the file cannot be written in the encoding
+    pub fn to_bytes_with_encoding(&self, encoding: Encoding) -> Result<Vec<u8>, ParseDstvError> {
+        encoding.encode(&self.to_string())
+    }
+}
+
+/// Reads the contents of a file
+pub(crate) fn read_file(file_path: &Path) -> Result<Vec<u8>, ParseDstvError> {
+    std::fs::read(file_path)
+        .map_err(|e| ParseDstvError::new(format!("Unable to read file: `{file_path:#?}`\t{e}")))
+}
@@ -0,0 +1,249 @@
+use crate::{
+    dstv_element_type::DstvElementType,
+    geometry::Point,
+    prelude::{
+        Border, BorderPoint, CodeProfile, Degrees, Dstv, Header, OuterBorder, PartEnd, PartFace,
+    },
+};
+use std::fmt::Display;
+
+/// The saw cut at one end of a part, described by the end cut angles of the header.
+/// The angles are measured from a square cut, in degrees. A positive angle shortens the
+/// part at the upper edge of the face, i.e. at the largest y coordinate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EndCut {
+    /// The end of the part that is cut
+    pub end: PartEnd,
+    /// The angle of the cut seen on the web (front face)
+    pub web_angle: Degrees,
+    /// The angle of the cut seen on the flanges (top and bottom faces)
+    pub flange_angle: Degrees,
+}
+
+impl EndCut {
+    /// Returns true if the cut is perpendicular to the part
+    pub fn is_square(&self) -> bool {
+        self.web_angle == 0.0 && self.flange_angle == 0.0
+    }
+
+    /// Returns true if the cut is mitred both on the web and on the flanges, which
+    /// needs a saw that can turn and tilt
+    pub fn is_compound(&self) -> bool {
+        self.web_angle != 0.0 && self.flange_angle != 0.0
+    }
+}
+
+impl Display for EndCut {
+    /// Describes the cut for the saw line, e.g. `start: web 15°` or `end: square`
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let end = match self.end {
+            PartEnd::Start => "start",
+            PartEnd::End => "end",
+        };
+        match (self.web_angle != 0.0, self.flange_angle != 0.0) {
+            (false, false) => write!(f, "{end}: square"),
+            (true, false) => write!(f, "{end}: web {}°", self.web_angle),
+            (false, true) => write!(f, "{end}: flange {}°", self.flange_angle),
+            (true, true) => write!(
+                f,
+                "{end}: web {}°, flange {}° (compound)",
+                self.web_angle, self.flange_angle
+            ),
+        }
+    }
+}
+
+/// Returns how much a cut with an angle shortens the part at a position across a face
+/// # Arguments
+/// * `angle` - The angle of the cut in degrees
+/// * `width` - The width of the face
+/// * `y` - The position across the face
+fn setback(angle: f64, width: f64, y: f64) -> f64 {
+    let tan = angle.to_radians().tan();
+    tan * y - (tan * width).min(0.0)
+}
+
+impl Header {
+    /// Returns the saw cut at an end of the part
+    /// # Arguments
+    /// * `end` - The end of the part
+    pub fn end_cut(&self, end: PartEnd) -> EndCut {
+        let (web_angle, flange_angle) = match end {
+            PartEnd::Start => (self.web_start_cut, self.flange_start_cut),
+            PartEnd::End => (self.web_end_cut, self.flange_end_cut),
+        };
+        EndCut {
+            end,
+            web_angle,
+            flange_angle,
+        }
+    }
+
+    /// Returns the angle of the end cut as seen on a face. The web angle applies to the
+    /// front and back faces and to plates, the flange angle to the top and bottom faces.
+    /// # Arguments
+    /// * `face` - The face to get the angle for
+    /// * `end` - The end of the part
+    pub fn face_cut_angle(&self, face: &PartFace, end: PartEnd) -> f64 {
+        let cut = self.end_cut(end);
+        match (&self.code_profile, face) {
+            (CodeProfile::B, _) | (_, PartFace::Front) | (_, PartFace::Behind) => *cut.web_angle,
+            (_, PartFace::Top) | (_, PartFace::Bottom) => *cut.flange_angle,
+        }
+    }
+
+    /// Returns the x coordinate where the end cut crosses a line along a face
+    /// # Arguments
+    /// * `face` - The face of the line
+    /// * `end` - The end of the part
+    /// * `y` - The position of the line across the face
+    pub fn end_x(&self, face: &PartFace, end: PartEnd, y: f64) -> f64 {
+        let setback = setback(self.face_cut_angle(face, end), self.face_width(face), y);
+        match end {
+            PartEnd::Start => setback,
+            PartEnd::End => *self.length - setback,
+        }
+    }
+
+    /// Returns the x coordinate of an end plane of the part in 3D. The y axis runs
+    /// along the height of the web and the z axis along the width of the flanges.
+    /// # Arguments
+    /// * `end` - The end of the part
+    /// * `y` - The position along the height of the profile
+    /// * `z` - The position along the width of the flanges
+    pub fn end_plane_x(&self, end: PartEnd, y: f64, z: f64) -> f64 {
+        let cut = self.end_cut(end);
+        let setback = setback(*cut.web_angle, *self.profile_height, y)
+            + setback(*cut.flange_angle, *self.flange_width, z);
+        match end {
+            PartEnd::Start => setback,
+            PartEnd::End => *self.length - setback,
+        }
+    }
+
+    /// Returns the outward unit normal of an end plane of the part in 3D, with the axes
+    /// of `end_plane_x`
+    /// # Arguments
+    /// * `end` - The end of the part
+    pub fn end_plane_normal(&self, end: PartEnd) -> [f64; 3] {
+        let cut = self.end_cut(end);
+        let (web, flange) = (
+            cut.web_angle.to_radians().tan(),
+            cut.flange_angle.to_radians().tan(),
+        );
+        let norm = (1.0 + web * web + flange * flange).sqrt();
+        let x = match end {
+            PartEnd::Start => -1.0,
+            PartEnd::End => 1.0,
+        };
+        [x / norm, web / norm, flange / norm]
+    }
+
+    /// Returns the outline of a face without an outer contour, with the end cuts applied
+    /// # Arguments
+    /// * `face` - The face to get the outline for
+    /// # Returns
+    /// The closed contour of the face, counter-clockwise from the lower start corner
+    pub fn default_outline(&self, face: &PartFace) -> Vec<BorderPoint> {
+        let width = self.face_width(face);
+        [
+            (PartEnd::Start, 0.0),
+            (PartEnd::End, 0.0),
+            (PartEnd::End, width),
+            (PartEnd::Start, width),
+            (PartEnd::Start, 0.0),
+        ]
+        .iter()
+        .map(|(end, y)| BorderPoint {
+            fl_code: face.clone(),
+            x_coord: self.end_x(face, *end, *y).into(),
+            y_coord: (*y).into(),
+            ..BorderPoint::default()
+        })
+        .collect()
+    }
+}
+
+impl Dstv {
+    /// Returns the outlines of the faces without an outer contour that have a skewed
+    /// end cut or are trimmed by a cut, so they are not drawn as rectangles
+    pub fn default_outlines(&self) -> Vec<DstvElementType> {
+        let header = &self.header;
+        header
+            .code_profile
+            .faces()
+            .iter()
+            .filter(|face| {
+                !self.elements.iter().any(|element| {
+                    matches!(element, DstvElementType::OuterBorder(_))
+                        && element.get_facing() == *face
+                })
+            })
+            .filter(|face| {
+                let skewed = [PartEnd::Start, PartEnd::End]
+                    .iter()
+                    .any(|end| header.face_cut_angle(face, *end) != 0.0);
+                let outline = header
+                    .default_outline(face)
+                    .iter()
+                    .skip(1)
+                    .map(|point| Point::new(*point.x_coord, *point.y_coord))
+                    .collect::<Vec<_>>();
+                skewed || self.is_cut(face, &outline)
+            })
+            .filter_map(|face| {
+                let outline = self.face_outlines(face).into_iter().next()?;
+                let contour = outline
+                    .iter()
+                    .chain(outline.first())
+                    .map(|point| BorderPoint {
+                        fl_code: face.clone(),
+                        x_coord: point.x.into(),
+                        y_coord: point.y.into(),
+                        ..BorderPoint::default()
+                    })
+                    .collect();
+                Some(DstvElementType::OuterBorder(OuterBorder { contour }))
+            })
+            .collect()
+    }
+
+    /// Returns the length to saw the part from stock: the saw length of the header when
+    /// given, otherwise the largest extent of the part along its length, including its
+    /// contours and end cuts
+    pub fn saw_length(&self) -> f64 {
+        if let Some(saw_length) = self.header.saw_length {
+            return *saw_length;
+        }
+        let outlines = self.default_outlines();
+        let xs = self
+            .elements
+            .iter()
+            .chain(outlines.iter())
+            .filter_map(|element| match element {
+                DstvElementType::OuterBorder(border) => Some(border.polygon()),
+                _ => None,
+            })
+            .flatten()
+            .map(|point| point.x)
+            .collect::<Vec<_>>();
+        let extent = match xs.is_empty() {
+            true => 0.0,
+            false => {
+                xs.iter().cloned().fold(f64::MIN, f64::max)
+                    - xs.iter().cloned().fold(f64::MAX, f64::min)
+            }
+        };
+        extent.max(*self.header.length)
+    }
+
+    /// Describes the saw cuts at both ends of the part for the saw line
+    /// # Returns
+    /// The cuts at the start and at the end of the part
+    pub fn saw_cuts(&self) -> [EndCut; 2] {
+        [
+            self.header.end_cut(PartEnd::Start),
+            self.header.end_cut(PartEnd::End),
+        ]
+    }
+}
@@ -0,0 +1,394 @@
+use crate::{
+    geometry::{self, Point, Segment, FLATTEN_TOLERANCE},
+    prelude::{
+        Border, BorderPoint, CodeProfile, Dstv, DstvElement, DstvElementType, PartEnd, PartFace,
+    },
+};
+
+/// Points closer than this to the bounding box of a contour lie on its edge, in mm
+const EDGE_TOLERANCE: f64 = 0.1;
+
+/// The edges of the bounding box of a face contour
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FaceEdge {
+    /// The end of the face at the lowest x coordinate
+    Start,
+    /// The end of the face at the highest x coordinate
+    End,
+    /// The long edge of the face at the lowest y coordinate
+    Lower,
+    /// The long edge of the face at the highest y coordinate
+    Upper,
+}
+
+/// A rectangular cut-out at the end of a face, reaching from the end into one of the
+/// long edges, e.g. to let a beam pass under the flange of another beam
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cope {
+    /// The face of the contour
+    pub face: PartFace,
+    /// The end of the part the cope is at
+    pub end: PartEnd,
+    /// The long edge the cope is cut from
+    pub edge: FaceEdge,
+    /// The length of the cope along the part
+    pub length: f64,
+    /// The depth of the cope measured from the long edge
+    pub depth: f64,
+    /// The largest radius in the cope, usually relieving the inner corner
+    pub radius: f64,
+}
+
+/// A cut-out in an edge of a face that does not reach the ends of the part
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notch {
+    /// The face of the contour
+    pub face: PartFace,
+    /// The edge the notch is cut from
+    pub edge: FaceEdge,
+    /// The start of the notch along the edge
+    pub position: f64,
+    /// The length of the notch along the edge
+    pub length: f64,
+    /// The depth of the notch measured from the edge
+    pub depth: f64,
+    /// The largest radius in the notch, a radius-relieved notch has a radius above zero
+    pub radius: f64,
+}
+
+/// A single straight cut removing a corner of a face
+#[derive(Clone, Debug, PartialEq)]
+pub struct CornerSnip {
+    /// The face of the contour
+    pub face: PartFace,
+    /// The end of the part the corner is at
+    pub end: PartEnd,
+    /// The long edge the corner is at
+    pub edge: FaceEdge,
+    /// The leg of the snip along the part
+    pub length: f64,
+    /// The leg of the snip across the part
+    pub depth: f64,
+}
+
+/// A single straight cut across the full width of a face that is not perpendicular to
+/// the part, e.g. a miter cut
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkewedEndCut {
+    /// The face of the contour
+    pub face: PartFace,
+    /// The end of the part that is cut
+    pub end: PartEnd,
+    /// The angle between the cut and a square cut, in degrees
+    pub angle: f64,
+    /// The difference in length between both long edges caused by the cut
+    pub offset: f64,
+}
+
+/// An opening described by an inner contour, e.g. a web penetration for ducts or pipes
+/// or a cut-out in a plate
+#[derive(Clone, Debug, PartialEq)]
+pub struct Opening {
+    /// The face of the contour
+    pub face: PartFace,
+    /// The lowest x coordinate of the opening
+    pub x: f64,
+    /// The lowest y coordinate of the opening
+    pub y: f64,
+    /// The size of the opening along the part
+    pub length: f64,
+    /// The size of the opening across the part
+    pub height: f64,
+    /// The largest radius in the contour of the opening
+    pub radius: f64,
+}
+
+/// A deviation from the bounding box of a face contour that matches none of the other
+/// features, e.g. a combination of cuts at an end
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtherFeature {
+    /// The face of the contour
+    pub face: PartFace,
+    /// The lowest x coordinate of the removed area
+    pub x: f64,
+    /// The lowest y coordinate of the removed area
+    pub y: f64,
+    /// The size of the removed area along the part
+    pub length: f64,
+    /// The size of the removed area across the part
+    pub height: f64,
+}
+
+/// A feature recognised from the contours of a part
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContourFeature {
+    /// A rectangular cut-out at an end of a face
+    Cope(Cope),
+    /// A cut-out in an edge of a face away from the ends
+    Notch(Notch),
+    /// A straight cut removing a corner of a face
+    CornerSnip(CornerSnip),
+    /// A straight cut across the full width of a face at an angle
+    SkewedEndCut(SkewedEndCut),
+    /// An opening in the web of a beam, channel or T section
+    WebPenetration(Opening),
+    /// An opening in a plate, a flange or another face than the web
+    Opening(Opening),
+    /// A deviation from the bounding box matching none of the other features
+    Other(OtherFeature),
+}
+
+impl ContourFeature {
+    /// Returns the face the feature is on
+    pub fn face(&self) -> &PartFace {
+        match self {
+            ContourFeature::Cope(f) => &f.face,
+            ContourFeature::Notch(f) => &f.face,
+            ContourFeature::CornerSnip(f) => &f.face,
+            ContourFeature::SkewedEndCut(f) => &f.face,
+            ContourFeature::WebPenetration(f) => &f.face,
+            ContourFeature::Opening(f) => &f.face,
+            ContourFeature::Other(f) => &f.face,
+        }
+    }
+}
+
+/// The bounding box of a set of points
+#[derive(Clone, Copy, Debug)]
+struct Bounds {
+    min: Point,
+    max: Point,
+}
+
+impl Bounds {
+    fn of(points: &[Point]) -> Option<Self> {
+        let first = *points.first()?;
+        Some(points.iter().fold(
+            Bounds {
+                min: first,
+                max: first,
+            },
+            |bounds, p| Bounds {
+                min: Point::new(bounds.min.x.min(p.x), bounds.min.y.min(p.y)),
+                max: Point::new(bounds.max.x.max(p.x), bounds.max.y.max(p.y)),
+            },
+        ))
+    }
+
+    fn length(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+
+    /// Returns the edges of the bounding box a point lies on
+    fn edges(&self, point: &Point) -> Vec<FaceEdge> {
+        let near = |a: f64, b: f64| (a - b).abs() <= EDGE_TOLERANCE;
+        [
+            (near(point.x, self.min.x), FaceEdge::Start),
+            (near(point.x, self.max.x), FaceEdge::End),
+            (near(point.y, self.min.y), FaceEdge::Lower),
+            (near(point.y, self.max.y), FaceEdge::Upper),
+        ]
+        .iter()
+        .filter(|(on, _)| *on)
+        .map(|(_, edge)| *edge)
+        .collect()
+    }
+}
+
+/// Returns the largest radius of a run of segments
+fn max_radius(segments: &[Segment]) -> f64 {
+    segments
+        .iter()
+        .map(|segment| segment.radius.abs())
+        .fold(0.0, f64::max)
+}
+
+/// Classifies a run of segments that leaves the bounding box of the contour
+/// # Arguments
+/// * `face` - The face of the contour
+/// * `outer` - The bounding box of the contour
+/// * `run` - The segments, starting and ending on the bounding box
+fn classify(face: &PartFace, outer: &Bounds, run: &[Segment]) -> Option<ContourFeature> {
+    let first = run.first()?;
+    let last = run.last()?;
+    let start_edges = outer.edges(&first.start);
+    let end_edges = outer.edges(&last.end);
+    let bounds = Bounds::of(&geometry::flatten(run, FLATTEN_TOLERANCE))?;
+    let touches = |edge: FaceEdge| start_edges.contains(&edge) || end_edges.contains(&edge);
+    let shared = |edge: FaceEdge| start_edges.contains(&edge) && end_edges.contains(&edge);
+    let near = |a: f64, b: f64| (a - b).abs() <= EDGE_TOLERANCE;
+    let end = match (
+        near(bounds.min.x, outer.min.x),
+        near(bounds.max.x, outer.max.x),
+    ) {
+        (true, false) => Some(PartEnd::Start),
+        (false, true) => Some(PartEnd::End),
+        _ => None,
+    };
+    let long_edge = match (touches(FaceEdge::Lower), touches(FaceEdge::Upper)) {
+        (true, false) => Some(FaceEdge::Lower),
+        (false, true) => Some(FaceEdge::Upper),
+        _ => None,
+    };
+    let other = ContourFeature::Other(OtherFeature {
+        face: face.clone(),
+        x: bounds.min.x,
+        y: bounds.min.y,
+        length: bounds.length(),
+        height: bounds.height(),
+    });
+
+    if run.len() == 1 && !first.is_arc() {
+        let feature = match (end, long_edge) {
+            (Some(end), None) => ContourFeature::SkewedEndCut(SkewedEndCut {
+                face: face.clone(),
+                end,
+                angle: bounds.length().atan2(bounds.height()).to_degrees(),
+                offset: bounds.length(),
+            }),
+            (Some(end), Some(edge)) => ContourFeature::CornerSnip(CornerSnip {
+                face: face.clone(),
+                end,
+                edge,
+                length: bounds.length(),
+                depth: bounds.height(),
+            }),
+            _ => other,
+        };
+        return Some(feature);
+    }
+
+    let radius = max_radius(run);
+    let feature = match (end, long_edge) {
+        (Some(end), Some(edge)) => ContourFeature::Cope(Cope {
+            face: face.clone(),
+            end,
+            edge,
+            length: bounds.length(),
+            depth: bounds.height(),
+            radius,
+        }),
+        (None, Some(edge)) if shared(edge) => ContourFeature::Notch(Notch {
+            face: face.clone(),
+            edge,
+            position: bounds.min.x,
+            length: bounds.length(),
+            depth: bounds.height(),
+            radius,
+        }),
+        (Some(end), None) if !touches(FaceEdge::Lower) && !touches(FaceEdge::Upper) => {
+            ContourFeature::Notch(Notch {
+                face: face.clone(),
+                edge: match end {
+                    PartEnd::Start => FaceEdge::Start,
+                    PartEnd::End => FaceEdge::End,
+                },
+                position: bounds.min.y,
+                length: bounds.height(),
+                depth: bounds.length(),
+                radius,
+            })
+        }
+        _ => other,
+    };
+    Some(feature)
+}
+
+/// Recognises the features of an outer contour by walking the runs of segments that
+/// leave the bounding box of the contour
+/// # Arguments
+/// * `contour` - The points of the outer contour
+/// # Returns
+/// The features in the order of the contour
+fn outer_features(contour: &[BorderPoint]) -> Vec<ContourFeature> {
+    let face = match contour.first() {
+        Some(point) => point.fl_code.clone(),
+        None => return vec![],
+    };
+    let segments = geometry::contour_segments(contour)
+        .into_iter()
+        .filter(|segment| segment.chord() > geometry::EPSILON)
+        .collect::<Vec<_>>();
+    let outer = match Bounds::of(&geometry::flatten(&segments, FLATTEN_TOLERANCE)) {
+        Some(bounds) => bounds,
+        None => return vec![],
+    };
+    let on_boundary = |segment: &Segment| {
+        !segment.is_arc()
+            && outer
+                .edges(&segment.start)
+                .iter()
+                .any(|edge| outer.edges(&segment.end).contains(edge))
+    };
+    // start walking after a segment on the bounding box, so no run is split in two
+    let first = match segments.iter().position(on_boundary) {
+        Some(index) => index + 1,
+        None => return vec![],
+    };
+    let mut features = vec![];
+    let mut run: Vec<Segment> = vec![];
+    for i in 0..segments.len() {
+        let segment = &segments[(first + i) % segments.len()];
+        if on_boundary(segment) {
+            features.extend(classify(&face, &outer, &run));
+            run.clear();
+            continue;
+        }
+        run.push(segment.clone());
+        if !outer.edges(&segment.end).is_empty() {
+            features.extend(classify(&face, &outer, &run));
+            run.clear();
+        }
+    }
+    features.extend(classify(&face, &outer, &run));
+    features
+}
+
+impl Dstv {
+    /// Recognises copes, notches, corner snips, skewed end cuts and openings from the
+    /// contours of the part.
+    /// Outer contours are compared with their bounding box, so every run of the contour
+    /// that leaves the bounding box is one feature. Inner contours are openings, which
+    /// are web penetrations on the front face of beams, channels and T sections.
+    /// # Returns
+    /// The features of all contours, in the order of the contours in the file
+    pub fn features(&self) -> Vec<ContourFeature> {
+        self.elements
+            .iter()
+            .flat_map(|element| match element {
+                DstvElementType::OuterBorder(border) => outer_features(&border.contour),
+                DstvElementType::InnerBorder(border) => {
+                    let segments = border.segments();
+                    let face = border.get_facing();
+                    let in_web = face == &PartFace::Front
+                        && matches!(
+                            self.header.code_profile,
+                            CodeProfile::I | CodeProfile::U | CodeProfile::C | CodeProfile::T
+                        );
+                    Bounds::of(&border.polygon())
+                        .map(|bounds| {
+                            let opening = Opening {
+                                face: face.clone(),
+                                x: bounds.min.x,
+                                y: bounds.min.y,
+                                length: bounds.length(),
+                                height: bounds.height(),
+                                radius: max_radius(&segments),
+                            };
+                            match in_web {
+                                true => ContourFeature::WebPenetration(opening),
+                                false => ContourFeature::Opening(opening),
+                            }
+                        })
+                        .into_iter()
+                        .collect()
+                }
+                _ => vec![],
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{
+    dstv_element::ParseDstvError,
+    geometry,
+    prelude::{BorderPoint, CodeProfile, Dstv, DstvElementType, PartFace},
+};
+use std::{collections::HashMap, fmt::Display};
+
+/// The default tolerance for fingerprints in mm, coordinates are rounded to this step
+pub const FINGERPRINT_TOLERANCE: f64 = 0.1;
+
+/// A hash of the geometry of a part.
+/// Two parts with the same profile, steel grade, length and holes, slots, contours,
+/// cuts and bends have the same fingerprint, regardless of their identifications,
+/// quantities, marks and the order of their elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fingerprint(pub u64);
+
+impl Display for Fingerprint {
+    /// Writes the fingerprint as 16 hexadecimal digits
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// Hashes a string with 64 bit FNV-1a, which is stable across platforms and releases
+fn fnv1a(data: &str) -> u64 {
+    data.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Checks that a tolerance can be rounded to
+/// # Arguments
+/// * `tolerance` - The rounding step in mm
+/// # Error
+/// If the tolerance is not a positive, finite number
+fn check_tolerance(tolerance: f64) -> Result<(), ParseDstvError> {
+    match tolerance.is_finite() && tolerance > 0.0 {
+        true => Ok(()),
+        false => Err(ParseDstvError::new(format!(
+            "The fingerprint tolerance must be positive, got {tolerance}"
+        ))),
+    }
+}
+
+/// Rounds a value to a whole number of tolerance steps
+fn round(value: f64, tolerance: f64) -> i64 {
+    (value / tolerance).round() as i64
+}
+
+/// Returns the canonical form of a contour: its segments, each written from its lowest
+/// to its highest end point, sorted, so the starting point and direction do not matter
+fn contour_key(contour: &[BorderPoint], tolerance: f64) -> String {
+    let mut segments = geometry::contour_segments(contour)
+        .into_iter()
+        .filter_map(|segment| {
+            let start = (
+                round(segment.start.x, tolerance),
+                round(segment.start.y, tolerance),
+            );
+            let end = (
+                round(segment.end.x, tolerance),
+                round(segment.end.y, tolerance),
+            );
+            let radius = round(segment.radius, tolerance);
+            match start.cmp(&end) {
+                std::cmp::Ordering::Equal => None,
+                std::cmp::Ordering::Less => Some((start, end, radius)),
+                std::cmp::Ordering::Greater => Some((end, start, -radius)),
+            }
+        })
+        .collect::<Vec<_>>();
+    segments.sort_unstable();
+    segments
+        .iter()
+        .map(|(start, end, radius)| {
+            format!("{},{},{},{},{}", start.0, start.1, end.0, end.1, radius)
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Returns the canonical form of an element, or `None` if the element does not add to
+/// the geometry of the part
+fn element_key(element: &DstvElementType, tolerance: f64) -> Option<String> {
+    let r = |value: f64| round(value, tolerance);
+    let key = match element {
+        DstvElementType::OuterBorder(e) => format!("AK:{}", contour_key(&e.contour, tolerance)),
+        DstvElementType::InnerBorder(e) => format!("IK:{}", contour_key(&e.contour, tolerance)),
+        DstvElementType::Hole(e) => format!(
+            "BO:{},{},{},{}",
+            r(*e.x_coord),
+            r(*e.y_coord),
+            r(*e.diameter),
+            r(*e.depth_or_zero())
+        ),
+        DstvElementType::Slot(e) => format!(
+            "BO:{},{},{},{},{},{},{}",
+            r(*e.x_coord),
+            r(*e.y_coord),
+            r(*e.diameter),
+            r(*e.depth),
+            r(*e.slot_length),
+            r(*e.slot_width),
+            r(e.angle.rem_euclid(360.0))
+        ),
+        DstvElementType::Cut(e) => format!(
+            "SC:{},{},{},{},{},{}",
+            r(*e.sp_point_x),
+            r(*e.sp_point_y),
+            r(*e.sp_point_z),
+            r(e.nor_vec_x),
+            r(e.nor_vec_y),
+            r(e.nor_vec_z)
+        ),
+        DstvElementType::Bend(e) => format!(
+            "KA:{},{},{},{},{},{}",
+            r(*e.origin_x),
+            r(*e.origin_y),
+            r(*e.finish_x),
+            r(*e.finish_y),
+            r(*e.angle),
+            r(*e.radius)
+        ),
+        // marks carry the piece identification, which differs between duplicates
+        DstvElementType::Numeration(_) => return None,
+        // blocks beyond the standard have no geometry that can be compared
+        DstvElementType::Custom(_) | DstvElementType::Unknown(_) => return None,
+    };
+    Some(format!("{}:{key}", element.get_facing().code()))
+}
+
+impl Dstv {
+    /// Returns the canonical description of the geometry of the part that the
+    /// fingerprint is computed from. Useful to find out why two parts differ.
+    /// # Arguments
+    /// * `tolerance` - The step that all dimensions and coordinates are rounded to, in mm
+    /// # Returns
+    /// A string with a line for the profile, the grade and the length, followed by a
+    /// sorted line for every hole, slot, contour, cut and bend
+    /// # Error
+    /// If the tolerance is not positive
+    pub fn canonical_geometry(&self, tolerance: f64) -> Result<String, ParseDstvError> {
+        check_tolerance(tolerance)?;
+        let header = &self.header;
+        let r = |value: f64| round(value, tolerance);
+        // standard sections have several spellings, so use their catalogue name
+        let name = header
+            .catalogue_section()
+            .map_or_else(|_| header.profile.clone(), |section| section.name);
+        let profile = match header.code_profile {
+            // plates are named differently by each exporter, so use their dimensions
+            CodeProfile::B => format!(
+                "B:{}x{}",
+                r(header.face_thickness(&PartFace::Front)),
+                r(header.face_width(&PartFace::Front))
+            ),
+            _ => format!(
+                "{}:{}:{},{},{},{}",
+                header.code_profile.code(),
+                name.split_whitespace().collect::<String>().to_uppercase(),
+                r(*header.profile_height),
+                r(*header.flange_width),
+                r(*header.flange_thickness),
+                r(*header.web_thickness)
+            ),
+        };
+        let grade = header
+            .steel_grade()
+            .map(|grade| grade.name().to_string())
+            .unwrap_or_else(|_| header.steel_quality.trim().to_uppercase());
+        let cuts = [
+            header.web_start_cut,
+            header.web_end_cut,
+            header.flange_start_cut,
+            header.flange_end_cut,
+        ]
+        .iter()
+        .map(|cut| r(**cut).to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+        let mut elements = self
+            .elements
+            .iter()
+            .filter_map(|element| element_key(element, tolerance))
+            .collect::<Vec<_>>();
+        elements.sort_unstable();
+        Ok(format!(
+            "{profile}\n{grade}\n{}:{cuts}\n{}",
+            r(*header.length),
+            elements.join("\n")
+        ))
+    }
+
+    /// Returns a fingerprint of the geometry of the part, to find duplicate parts.
+    /// Dimensions and coordinates are rounded to the tolerance, so values close to a
+    /// rounding boundary may still give different fingerprints.
+    /// # Arguments
+    /// * `tolerance` - The step that all dimensions and coordinates are rounded to, in
+    ///   mm, usually `FINGERPRINT_TOLERANCE`
+    /// # Error
+    /// If the tolerance is not positive
+    pub fn fingerprint(&self, tolerance: f64) -> Result<Fingerprint, ParseDstvError> {
+        Ok(Fingerprint(fnv1a(&self.canonical_geometry(tolerance)?)))
+    }
+}
+
+/// Groups parts with the same geometry, e.g. to merge them into one production order
+/// # Arguments
+/// * `parts` - The parts to group
+/// * `tolerance` - The step that all dimensions and coordinates are rounded to, in mm
+/// # Returns
+/// The groups in the order their first part appears, each with the fingerprint and the
+/// parts that share it in their original order
+/// # Error
+/// If the tolerance is not positive
+pub fn group_by_fingerprint(
+    parts: &[Dstv],
+    tolerance: f64,
+) -> Result<Vec<(Fingerprint, Vec<&Dstv>)>, ParseDstvError> {
+    check_tolerance(tolerance)?;
+    let mut groups: Vec<(Fingerprint, Vec<&Dstv>)> = vec![];
+    let mut index: HashMap<Fingerprint, usize> = HashMap::new();
+    for part in parts {
+        let fingerprint = part.fingerprint(tolerance)?;
+        match index.get(&fingerprint) {
+            Some(&i) => groups[i].1.push(part),
+            None => {
+                index.insert(fingerprint, groups.len());
+                groups.push((fingerprint, vec![part]));
+            }
+        }
+    }
+    Ok(groups)
+}
@@ -0,0 +1,206 @@
+use crate::{
+    dstv_element_type::DstvElementType,
+    geometry::{Point, EPSILON},
+    prelude::{Bend, BendDirection, Dstv, PartFace},
+};
+
+/// The number of steps an edge crossing a bend zone is divided in
+const ARC_STEPS: usize = 8;
+
+/// A bend prepared for folding, in the frame of its bend line
+#[derive(Clone, Debug)]
+struct Fold {
+    /// The start of the bend line
+    origin: Point,
+    /// The unit vector along the bend line
+    along: (f64, f64),
+    /// The unit vector across the bend line, pointing away from the fixed side
+    across: (f64, f64),
+    /// Half the width of the bend zone in the flat pattern
+    half: f64,
+    /// The radius of the neutral fibre
+    neutral: f64,
+    /// The bend angle in radians
+    angle: f64,
+    /// 1 when bending up, -1 when bending down
+    sign: f64,
+}
+
+impl Fold {
+    /// Returns the distance of a point across the bend line, negative on the fixed side
+    fn across(&self, x: f64, y: f64) -> f64 {
+        (x - self.origin.x) * self.across.0 + (y - self.origin.y) * self.across.1
+    }
+
+    /// Folds a point, which may already be lifted from the face by bends further away
+    fn apply(&self, point: [f64; 3]) -> [f64; 3] {
+        let [x, y, z] = point;
+        let a = self.across(x, y);
+        let along = (x - self.origin.x) * self.along.0 + (y - self.origin.y) * self.along.1;
+        // the position along the neutral fibre from the start of the bend zone
+        let t = a + self.half;
+        let phi = match self.neutral > EPSILON {
+            true => (t / self.neutral).clamp(0.0, self.angle),
+            false if t > 0.0 => self.angle,
+            false => 0.0,
+        };
+        let e = t - self.neutral * phi;
+        let (sin, cos) = (self.sign * phi).sin_cos();
+        let u = -self.half + self.neutral * phi.sin() + e * cos - z * sin;
+        let w = self.sign * self.neutral * (1.0 - phi.cos()) + e * sin + z * cos;
+        [
+            self.origin.x + u * self.across.0 + along * self.along.0,
+            self.origin.y + u * self.across.1 + along * self.along.1,
+            w,
+        ]
+    }
+}
+
+/// Folds points of the flat pattern of a plate into its formed shape.
+/// The side of each bend line holding the reference point stays in place, the other
+/// side is rotated around the bend line over the bend angle. The bend zone follows the
+/// neutral fibre, which keeps the length of the flat pattern.
+#[derive(Clone, Debug)]
+pub struct Folding {
+    /// The bends, ordered from the farthest to the nearest to the reference point
+    folds: Vec<Fold>,
+}
+
+impl Folding {
+    /// Prepares the folding of a plate
+    /// # Arguments
+    /// * `bends` - The bend lines of the plate
+    /// * `thickness` - The thickness of the plate
+    /// * `k_factor` - The position of the neutral fibre as a fraction of the thickness
+    /// * `reference` - A point on the part of the plate that stays in place
+    pub fn new(bends: &[&Bend], thickness: f64, k_factor: f64, reference: Point) -> Self {
+        let mut folds = bends
+            .iter()
+            .filter(|bend| bend.length() > EPSILON)
+            .map(|bend| {
+                let length = bend.length();
+                let along = (
+                    (bend.finish_x - bend.origin_x).value() / length,
+                    (bend.finish_y - bend.origin_y).value() / length,
+                );
+                let side = match bend.distance(&reference) > 0.0 {
+                    true => -1.0,
+                    false => 1.0,
+                };
+                let fold = Fold {
+                    origin: bend.origin(),
+                    along,
+                    across: (side * along.1, -side * along.0),
+                    half: bend.allowance(thickness, k_factor) / 2.0,
+                    neutral: *bend.radius + k_factor * thickness,
+                    angle: bend.angle.abs().to_radians(),
+                    sign: match bend.direction() {
+                        BendDirection::Up => 1.0,
+                        BendDirection::Down => -1.0,
+                    },
+                };
+                (bend.distance(&reference).abs(), fold)
+            })
+            .collect::<Vec<_>>();
+        folds.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        Self {
+            folds: folds.into_iter().map(|(_, fold)| fold).collect(),
+        }
+    }
+
+    /// Folds a point of the flat pattern
+    /// # Arguments
+    /// * `point` - The point on the face
+    /// # Returns
+    /// The point in the formed shape, with z pointing towards the viewer of the face
+    pub fn fold(&self, point: &Point) -> [f64; 3] {
+        self.folds
+            .iter()
+            .fold([point.x, point.y, 0.0], |point, fold| fold.apply(point))
+    }
+
+    /// Folds a polygon of the flat pattern. Edges crossing a bend zone are divided, so
+    /// the bend is followed by the folded polygon.
+    /// # Arguments
+    /// * `polygon` - The corner points of the polygon on the face
+    /// # Returns
+    /// The corner points of the polygon in the formed shape
+    pub fn fold_polygon(&self, polygon: &[Point]) -> Vec<[f64; 3]> {
+        let mut points = Vec::new();
+        for (i, start) in polygon.iter().enumerate() {
+            let end = &polygon[(i + 1) % polygon.len()];
+            let mut steps = vec![0.0];
+            for fold in &self.folds {
+                let (a, b) = (fold.across(start.x, start.y), fold.across(end.x, end.y));
+                if (b - a).abs() <= EPSILON {
+                    continue;
+                }
+                steps.extend(
+                    (0..=ARC_STEPS)
+                        .map(|step| -fold.half + 2.0 * fold.half * step as f64 / ARC_STEPS as f64)
+                        .map(|value| (value - a) / (b - a))
+                        .filter(|t| *t > EPSILON && *t < 1.0 - EPSILON),
+                );
+            }
+            steps.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            steps.dedup_by(|a, b| (*a - *b).abs() <= EPSILON);
+            points.extend(steps.iter().map(|t| {
+                self.fold(&Point::new(
+                    start.x + t * (end.x - start.x),
+                    start.y + t * (end.y - start.y),
+                ))
+            }));
+        }
+        points
+    }
+}
+
+impl Dstv {
+    /// Returns the bend lines of the part
+    pub fn bends(&self) -> Vec<&Bend> {
+        self.elements
+            .iter()
+            .filter_map(|element| match element {
+                DstvElementType::Bend(bend) => Some(bend),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Prepares folding the front face of the part, keeping the side of the bend lines
+    /// with the first point of its outer contour in place
+    /// # Arguments
+    /// * `k_factor` - The position of the neutral fibre as a fraction of the thickness
+    pub fn folding(&self, k_factor: f64) -> Folding {
+        let face = PartFace::Front;
+        let reference = self
+            .face_outlines(&face)
+            .first()
+            .and_then(|outline| outline.first().copied())
+            .unwrap_or_default();
+        let bends = self
+            .bends()
+            .into_iter()
+            .filter(|bend| bend.fl_code == face)
+            .collect::<Vec<_>>();
+        Folding::new(
+            &bends,
+            self.header.face_thickness(&face),
+            k_factor,
+            reference,
+        )
+    }
+
+    /// Returns the outlines of the front face in their formed shape
+    /// # Arguments
+    /// * `k_factor` - The position of the neutral fibre as a fraction of the thickness
+    /// # Returns
+    /// The corner points of each outline in 3D, with z pointing towards the viewer
+    pub fn folded_outlines(&self, k_factor: f64) -> Vec<Vec<[f64; 3]>> {
+        let folding = self.folding(k_factor);
+        self.face_outlines(&PartFace::Front)
+            .iter()
+            .map(|outline| folding.fold_polygon(outline))
+            .collect()
+    }
+}
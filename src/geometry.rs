@@ -0,0 +1,363 @@
+use crate::prelude::{Bevel, BorderPoint};
+use std::f64::consts::PI;
+
+/// Tolerance used when comparing coordinates, in millimetres
+pub const EPSILON: f64 = 1e-6;
+
+/// Maximum deviation from the true arc when arcs are approximated by chords, in millimetres
+pub const FLATTEN_TOLERANCE: f64 = 0.01;
+
+/// A point in the coordinate system of a part face
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Point {
+    /// The x coordinate of the point
+    pub x: f64,
+    /// The y coordinate of the point
+    pub y: f64,
+}
+
+impl Point {
+    /// Creates a new point
+    /// # Arguments
+    /// * `x` - The x coordinate of the point
+    /// * `y` - The y coordinate of the point
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the distance between this point and another point
+    /// # Arguments
+    /// * `other` - The point to measure the distance to
+    pub fn distance(&self, other: &Point) -> f64 {
+        (other.x - self.x).hypot(other.y - self.y)
+    }
+}
+
+/// A single segment of a contour, running from one border point to the next.
+/// A segment is either a straight line or a circular arc.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    /// The point the segment starts at
+    pub start: Point,
+    /// The point the segment ends at
+    pub end: Point,
+    /// The radius of the segment. Zero for a straight line, positive for an arc
+    /// running counter-clockwise and negative for an arc running clockwise.
+    pub radius: f64,
+    /// The weld preparation of the edge, if any
+    pub bevel: Option<Bevel>,
+}
+
+impl Segment {
+    /// Returns true if the segment is a circular arc
+    pub fn is_arc(&self) -> bool {
+        self.radius != 0.0 && self.chord() > EPSILON
+    }
+
+    /// Returns the length of the straight line between the start and the end point
+    pub fn chord(&self) -> f64 {
+        self.start.distance(&self.end)
+    }
+
+    /// Returns the absolute radius of the arc, widened to half the chord if the
+    /// radius is too small to span the chord
+    fn arc_radius(&self) -> f64 {
+        self.radius.abs().max(self.chord() / 2.0)
+    }
+
+    /// Returns the angle enclosed by the arc in radians, or zero for a straight line.
+    /// Arcs in a DSTV contour always take the shortest way around.
+    pub fn sweep(&self) -> f64 {
+        if !self.is_arc() {
+            return 0.0;
+        }
+        let half_chord = self.chord() / 2.0;
+        2.0 * (half_chord / self.arc_radius()).min(1.0).asin()
+    }
+
+    /// Returns the centre of the arc, or `None` for a straight line
+    pub fn center(&self) -> Option<Point> {
+        if !self.is_arc() {
+            return None;
+        }
+        let chord = self.chord();
+        let r = self.arc_radius();
+        let offset = (r * r - chord * chord / 4.0).max(0.0).sqrt();
+        let (nx, ny) = self.left_normal();
+        let sign = self.radius.signum();
+        Some(Point::new(
+            (self.start.x + self.end.x) / 2.0 + sign * nx * offset,
+            (self.start.y + self.end.y) / 2.0 + sign * ny * offset,
+        ))
+    }
+
+    /// Returns the length of the segment, following the arc if it is one
+    pub fn length(&self) -> f64 {
+        match self.is_arc() {
+            true => self.arc_radius() * self.sweep(),
+            false => self.chord(),
+        }
+    }
+
+    /// Returns the signed area between the chord and the arc. The area is positive
+    /// when the arc bulges out of a counter-clockwise contour.
+    fn bulge_area(&self) -> f64 {
+        let sweep = self.sweep();
+        let r = self.arc_radius();
+        self.radius.signum() * r * r / 2.0 * (sweep - sweep.sin())
+    }
+
+    /// Returns the centroid of the area between the chord and the arc
+    fn bulge_centroid(&self) -> Point {
+        let center = self.center().unwrap_or_default();
+        let sweep = self.sweep();
+        let r = self.arc_radius();
+        let distance = 4.0 * r * (sweep / 2.0).sin().powi(3) / (3.0 * (sweep - sweep.sin()));
+        let (nx, ny) = self.left_normal();
+        let sign = self.radius.signum();
+        Point::new(
+            center.x - sign * nx * distance,
+            center.y - sign * ny * distance,
+        )
+    }
+
+    /// Returns the unit vector perpendicular to the chord, pointing to the left
+    fn left_normal(&self) -> (f64, f64) {
+        let chord = self.chord();
+        if chord <= EPSILON {
+            return (0.0, 0.0);
+        }
+        (
+            -(self.end.y - self.start.y) / chord,
+            (self.end.x - self.start.x) / chord,
+        )
+    }
+
+    /// Returns points along the segment, excluding the start point and including the
+    /// end point. Arcs are approximated by chords deviating at most `tolerance`
+    /// from the true arc.
+    /// # Arguments
+    /// * `tolerance` - The maximum deviation from the arc, in millimetres
+    pub fn flatten(&self, tolerance: f64) -> Vec<Point> {
+        let center = match self.center() {
+            Some(center) => center,
+            None => return vec![self.end],
+        };
+        let r = self.arc_radius();
+        let sweep = self.sweep();
+        let step = match tolerance > 0.0 && tolerance < r {
+            true => 2.0 * (1.0 - tolerance / r).acos(),
+            false => PI / 2.0,
+        };
+        let count = ((sweep / step).ceil() as usize).clamp(1, 1024);
+        let start_angle = (self.start.y - center.y).atan2(self.start.x - center.x);
+        let direction = self.radius.signum();
+        (1..count)
+            .map(|i| {
+                let angle = start_angle + direction * sweep * i as f64 / count as f64;
+                Point::new(center.x + r * angle.cos(), center.y + r * angle.sin())
+            })
+            .chain(std::iter::once(self.end))
+            .collect()
+    }
+}
+
+/// Splits a contour into its segments.
+/// The radius and bevel of a border point describe the segment towards the next point.
+/// If the contour does not end on its starting point, a straight closing segment is
+/// added, with the bevel of the last point.
+/// # Arguments
+/// * `contour` - The border points of the contour
+/// # Returns
+/// A vector of segments describing the closed contour
+pub fn contour_segments(contour: &[BorderPoint]) -> Vec<Segment> {
+    let mut segments = contour
+        .windows(2)
+        .map(|pair| Segment {
+            start: Point::new(*pair[0].x_coord, *pair[0].y_coord),
+            end: Point::new(*pair[1].x_coord, *pair[1].y_coord),
+            radius: *pair[0].radius,
+            bevel: pair[0].bevel,
+        })
+        .collect::<Vec<_>>();
+    if let (Some(first), Some(last)) = (contour.first(), contour.last()) {
+        let start = Point::new(*last.x_coord, *last.y_coord);
+        let end = Point::new(*first.x_coord, *first.y_coord);
+        if start.distance(&end) > EPSILON {
+            segments.push(Segment {
+                start,
+                end,
+                radius: 0.0,
+                bevel: last.bevel,
+            });
+        }
+    }
+    segments
+}
+
+/// Returns the signed area enclosed by a closed set of segments.
+/// The area is positive for counter-clockwise contours and negative for clockwise ones.
+/// # Arguments
+/// * `segments` - The segments of a closed contour
+pub fn signed_area(segments: &[Segment]) -> f64 {
+    segments
+        .iter()
+        .map(|s| (s.start.x * s.end.y - s.end.x * s.start.y) / 2.0 + s.bulge_area())
+        .sum()
+}
+
+/// Returns the centroid of the area enclosed by a closed set of segments.
+/// # Arguments
+/// * `segments` - The segments of a closed contour
+/// # Returns
+/// The centroid, or `None` if the contour does not enclose any area
+pub fn centroid(segments: &[Segment]) -> Option<Point> {
+    let area = signed_area(segments);
+    if area.abs() <= EPSILON {
+        return None;
+    }
+    let (mx, my) = segments.iter().fold((0.0, 0.0), |(mx, my), s| {
+        let cross = s.start.x * s.end.y - s.end.x * s.start.y;
+        let bulge = s.bulge_area();
+        let (bx, by) = match s.is_arc() {
+            true => {
+                let c = s.bulge_centroid();
+                (bulge * c.x, bulge * c.y)
+            }
+            false => (0.0, 0.0),
+        };
+        (
+            mx + (s.start.x + s.end.x) * cross / 6.0 + bx,
+            my + (s.start.y + s.end.y) * cross / 6.0 + by,
+        )
+    });
+    Some(Point::new(mx / area, my / area))
+}
+
+/// Returns the total length of a set of segments
+/// # Arguments
+/// * `segments` - The segments to measure
+pub fn perimeter(segments: &[Segment]) -> f64 {
+    segments.iter().map(Segment::length).sum()
+}
+
+/// Approximates a closed set of segments by a polygon, flattening arcs.
+/// # Arguments
+/// * `segments` - The segments of a closed contour
+/// * `tolerance` - The maximum deviation from arcs, in millimetres
+/// # Returns
+/// The corner points of the polygon, without repeating the first point
+pub fn flatten(segments: &[Segment], tolerance: f64) -> Vec<Point> {
+    let mut points = segments
+        .first()
+        .map(|segment| vec![segment.start])
+        .unwrap_or_default();
+    for segment in segments {
+        points.extend(segment.flatten(tolerance));
+    }
+    if points.len() > 1 && points[0].distance(&points[points.len() - 1]) <= EPSILON {
+        points.pop();
+    }
+    points
+}
+
+/// Checks whether a point lies inside a polygon, using the even-odd rule
+/// # Arguments
+/// * `polygon` - The corner points of the polygon
+/// * `point` - The point to check
+pub fn polygon_contains(polygon: &[Point], point: &Point) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for (i, a) in polygon.iter().enumerate() {
+        let b = &polygon[j];
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Returns the distance from a point to the straight line piece between `a` and `b`
+/// # Arguments
+/// * `point` - The point to measure from
+/// * `a` - The start of the line piece
+/// * `b` - The end of the line piece
+pub fn distance_to_line(point: &Point, a: &Point, b: &Point) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length_squared = dx * dx + dy * dy;
+    if length_squared <= EPSILON * EPSILON {
+        return point.distance(a);
+    }
+    let t = (((point.x - a.x) * dx + (point.y - a.y) * dy) / length_squared).clamp(0.0, 1.0);
+    point.distance(&Point::new(a.x + t * dx, a.y + t * dy))
+}
+
+/// Returns the shortest distance from a point to the edges of a polygon
+/// # Arguments
+/// * `polygon` - The corner points of the polygon
+/// * `point` - The point to measure from
+pub fn distance_to_polygon(polygon: &[Point], point: &Point) -> f64 {
+    (0..polygon.len())
+        .map(|i| distance_to_line(point, &polygon[i], &polygon[(i + 1) % polygon.len()]))
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Returns the smallest rectangle along the axes holding a number of points
+/// # Arguments
+/// * `points` - The points to hold
+/// # Returns
+/// The lower left and the upper right corner, or `None` if there are no points
+pub fn bounding_box(points: &[Point]) -> Option<(Point, Point)> {
+    let first = *points.first()?;
+    Some(points.iter().fold((first, first), |(min, max), p| {
+        (
+            Point::new(min.x.min(p.x), min.y.min(p.y)),
+            Point::new(max.x.max(p.x), max.y.max(p.y)),
+        )
+    }))
+}
+
+/// Checks whether the line pieces `a1`-`a2` and `b1`-`b2` cross each other.
+/// Pieces that only touch at their end points do not count as crossing.
+pub fn lines_cross(a1: &Point, a2: &Point, b1: &Point, b2: &Point) -> bool {
+    let orientation = |p: &Point, q: &Point, r: &Point| {
+        let value = (q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x);
+        match value {
+            v if v > EPSILON => 1,
+            v if v < -EPSILON => -1,
+            _ => 0,
+        }
+    };
+    let (o1, o2) = (orientation(a1, a2, b1), orientation(a1, a2, b2));
+    let (o3, o4) = (orientation(b1, b2, a1), orientation(b1, b2, a2));
+    o1 * o2 < 0 && o3 * o4 < 0
+}
+
+/// Clips a polygon by a half-plane, keeping the part where `a * x + b * y + c <= 0`
+/// # Arguments
+/// * `polygon` - The corner points of the polygon
+/// * `a`, `b`, `c` - The coefficients of the line bounding the half-plane
+/// # Returns
+/// The corner points of the clipped polygon, empty if nothing is kept
+pub fn clip_polygon(polygon: &[Point], a: f64, b: f64, c: f64) -> Vec<Point> {
+    let value = |p: &Point| a * p.x + b * p.y + c;
+    let mut clipped = Vec::new();
+    for (i, current) in polygon.iter().enumerate() {
+        let next = &polygon[(i + 1) % polygon.len()];
+        let (v1, v2) = (value(current), value(next));
+        if v1 <= 0.0 {
+            clipped.push(*current);
+        }
+        if (v1 < 0.0 && v2 > 0.0) || (v1 > 0.0 && v2 < 0.0) {
+            let t = v1 / (v1 - v2);
+            clipped.push(Point::new(
+                current.x + t * (next.x - current.x),
+                current.y + t * (next.y - current.y),
+            ));
+        }
+    }
+    clipped
+}
@@ -1,8 +1,15 @@
 use std::{fmt::Debug, str::FromStr};
 
-use crate::dstv_element::ParseDstvError;
+use crate::{
+    dialect::HEADER_VALUE_LINES,
+    dstv_element::ParseDstvError,
+    get_str_from_f64,
+    prelude::{Degrees, KilogramsPerMetre, Millimetres, PartFace, SquareMetresPerMetre},
+    syntax::HEADER_LINES,
+};
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// All available profiles in a DSTV file
 pub enum CodeProfile {
     I,  // I Profiles
@@ -37,6 +44,47 @@ impl CodeProfile {
             CodeProfile::SO => "Special Profile",
         }
     }
+
+    /// Returns the code of the profile as used in DSTV files, e.g. `RO`
+    /// # Arguments
+    /// * `self` - A CodeProfile
+    /// # Returns
+    /// * A string slice holding the code of the CodeProfile
+    pub fn code(&self) -> &str {
+        match self {
+            CodeProfile::I => "I",
+            CodeProfile::L => "L",
+            CodeProfile::U => "U",
+            CodeProfile::B => "B",
+            CodeProfile::RU => "RU",
+            CodeProfile::RO => "RO",
+            CodeProfile::M => "M",
+            CodeProfile::C => "C",
+            CodeProfile::T => "T",
+            CodeProfile::SO => "SO",
+        }
+    }
+
+    /// Returns the faces a profile can carry elements on
+    /// # Arguments
+    /// * `self` - A CodeProfile
+    /// # Returns
+    /// * A slice of the faces available for the profile type
+    pub fn faces(&self) -> &'static [PartFace] {
+        match self {
+            CodeProfile::I | CodeProfile::U | CodeProfile::C | CodeProfile::T => {
+                &[PartFace::Front, PartFace::Top, PartFace::Bottom]
+            }
+            CodeProfile::L => &[PartFace::Front, PartFace::Bottom],
+            CodeProfile::B | CodeProfile::RU | CodeProfile::RO => &[PartFace::Front],
+            CodeProfile::M | CodeProfile::SO => &[
+                PartFace::Front,
+                PartFace::Top,
+                PartFace::Bottom,
+                PartFace::Behind,
+            ],
+        }
+    }
 }
 
 impl FromStr for CodeProfile {
@@ -67,7 +115,8 @@ impl FromStr for CodeProfile {
 }
 
 /// The header of a DSTV file
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Header {
     /// The order identification of the order the pieces belong to
     pub order_identification: String,
@@ -86,31 +135,31 @@ pub struct Header {
     /// The profile type of the piece
     pub code_profile: CodeProfile,
     /// The length of the piece
-    pub length: f64,
+    pub length: Millimetres,
     /// The saw length of the piece
-    pub saw_length: Option<f64>,
+    pub saw_length: Option<Millimetres>,
     /// The profile height of the piece
-    pub profile_height: f64,
+    pub profile_height: Millimetres,
     /// The flange width of the piece
-    pub flange_width: f64,
+    pub flange_width: Millimetres,
     /// The flange thickness of the piece
-    pub flange_thickness: f64,
+    pub flange_thickness: Millimetres,
     /// The web thickness of the piece
-    pub web_thickness: f64,
+    pub web_thickness: Millimetres,
     /// The radius of the piece
-    pub radius: f64,
+    pub radius: Millimetres,
     /// The weight by meter of the piece
-    pub weight_by_meter: f64,
+    pub weight_by_meter: KilogramsPerMetre,
     /// The painting surface by meter of the piece
-    pub painting_surface_by_meter: f64,
+    pub painting_surface_by_meter: SquareMetresPerMetre,
     /// The web start cut of the piece
-    pub web_start_cut: f64,
+    pub web_start_cut: Degrees,
     /// The web end cut of the piece
-    pub web_end_cut: f64,
+    pub web_end_cut: Degrees,
     /// The flange start cut of the piece
-    pub flange_start_cut: f64,
+    pub flange_start_cut: Degrees,
     /// The flange end cut of the piece
-    pub flange_end_cut: f64,
+    pub flange_end_cut: Degrees,
     /// The text1 info on piece of the piece
     pub text1_info_on_piece: String,
     /// The text2 info on piece of the piece
@@ -122,26 +171,61 @@ pub struct Header {
 }
 
 impl Header {
+    /// Returns the width of a face, measured perpendicular to the length of the piece.
+    /// For plates the larger of the profile height and the flange width is used, as
+    /// exporters disagree on which of the two holds the plate width.
+    /// # Arguments
+    /// * `face` - The face to get the width for
+    pub fn face_width(&self, face: &PartFace) -> f64 {
+        match (&self.code_profile, face) {
+            (CodeProfile::B, _) => self.profile_height.max(*self.flange_width),
+            (_, PartFace::Front) | (_, PartFace::Behind) => *self.profile_height,
+            (_, PartFace::Top) | (_, PartFace::Bottom) => *self.flange_width,
+        }
+    }
+
+    /// Returns the material thickness of a face.
+    /// The web thickness is used for the front and back faces and for plates, the flange
+    /// thickness for the top and bottom faces.
+    /// # Arguments
+    /// * `face` - The face to get the thickness for
+    pub fn face_thickness(&self, face: &PartFace) -> f64 {
+        let thickness = match (&self.code_profile, face) {
+            (CodeProfile::B, _) | (_, PartFace::Front) | (_, PartFace::Behind) => {
+                *self.web_thickness
+            }
+            (_, PartFace::Top) | (_, PartFace::Bottom) => *self.flange_thickness,
+        };
+        match (thickness > 0.0, &self.code_profile) {
+            (true, _) => thickness,
+            // plates exported without a web thickness carry it in the smaller dimension
+            (false, CodeProfile::B) => self.profile_height.min(*self.flange_width),
+            (false, _) => self.web_thickness.max(*self.flange_thickness),
+        }
+    }
+
     /// Creates a new Header from a vector of string slices
     /// # Arguments
-    /// * `lines` - A vector of string slices that holds the header
+    /// * `lines` - A vector of string slices that holds the header. The text lines
+    ///   after the 20 values may be left out, and are read as empty.
     /// # Returns
     /// A `Result` containing either a `Header` or an error message
-    pub fn from_lines(lines: Vec<&str>) -> Result<Self, ParseDstvError> {
-        if lines.len() < 24 {
+    pub fn from_lines(mut lines: Vec<&str>) -> Result<Self, ParseDstvError> {
+        if lines.len() < HEADER_VALUE_LINES {
             return Err(ParseDstvError::new("Invalid Header"));
         }
+        lines.resize(lines.len().max(HEADER_LINES), "");
         let lengths: String = dstv_str(lines.get(8))?;
         let lengths = lengths.split_once(',');
         // the length and the saw length of the piece are stored on the same line,
         // separated by a comma
         let (length, saw_length) = match lengths {
-            Some((one, two)) => (dstv_str(Some(one))?, dstv_str(Some(two)).ok()),
-            _ => (dstv_str(lines.get(8))?, None),
+            Some((one, two)) => (dstv_f64(Some(one))?, dstv_f64(Some(two)).ok()),
+            _ => (dstv_f64(lines.get(8))?, None),
         };
 
         Ok(Self {
-            order_identification: dstv_str(lines.get(0))?,
+            order_identification: dstv_str(lines.first())?,
             drawing_identification: dstv_str(lines.get(1))?,
             phase_identification: dstv_str(lines.get(2))?,
             piece_identification: dstv_str(lines.get(3))?,
@@ -154,23 +238,68 @@ impl Header {
             },
             length,
             saw_length,
-            profile_height: dstv_str(lines.get(9))?,
-            flange_width: dstv_str(lines.get(10))?,
-            flange_thickness: dstv_str(lines.get(11))?,
-            web_thickness: dstv_str(lines.get(12))?,
-            radius: dstv_str(lines.get(13))?,
-            weight_by_meter: dstv_str(lines.get(14))?,
-            painting_surface_by_meter: dstv_str(lines.get(15))?,
-            web_start_cut: dstv_str(lines.get(16))?,
-            web_end_cut: dstv_str(lines.get(17))?,
-            flange_start_cut: dstv_str(lines.get(18))?,
-            flange_end_cut: dstv_str(lines.get(19))?,
+            profile_height: dstv_f64(lines.get(9))?,
+            flange_width: dstv_f64(lines.get(10))?,
+            flange_thickness: dstv_f64(lines.get(11))?,
+            web_thickness: dstv_f64(lines.get(12))?,
+            radius: dstv_f64(lines.get(13))?,
+            weight_by_meter: dstv_f64(lines.get(14))?,
+            painting_surface_by_meter: dstv_f64(lines.get(15))?,
+            web_start_cut: dstv_f64(lines.get(16))?,
+            web_end_cut: dstv_f64(lines.get(17))?,
+            flange_start_cut: dstv_f64(lines.get(18))?,
+            flange_end_cut: dstv_f64(lines.get(19))?,
             text1_info_on_piece: dstv_str(lines.get(20))?,
             text2_info_on_piece: dstv_str(lines.get(21))?,
             text3_info_on_piece: dstv_str(lines.get(22))?,
             text4_info_on_piece: dstv_str(lines.get(23))?,
         })
     }
+
+    /// Converts the header to the header lines of a DSTV file, without the `ST` block
+    /// identifier. Each line ends with a newline.
+    /// # Returns
+    /// A string holding the 24 lines of the header
+    pub fn to_dstv(&self) -> String {
+        let length = match self.saw_length {
+            Some(saw_length) => format!(
+                "{},{}",
+                get_str_from_f64(self.length),
+                get_str_from_f64(saw_length).trim()
+            ),
+            None => get_str_from_f64(self.length),
+        };
+        let lines = [
+            format!("  {}", self.order_identification),
+            format!("  {}", self.drawing_identification),
+            format!("  {}", self.phase_identification),
+            format!("  {}", self.piece_identification),
+            format!("  {}", self.steel_quality),
+            format!("  {}", self.quantity_of_pieces),
+            format!("  {}", self.profile),
+            format!("  {}", self.code_profile.code()),
+            format!(" {length}"),
+            format!(" {}", get_str_from_f64(self.profile_height)),
+            format!(" {}", get_str_from_f64(self.flange_width)),
+            format!(" {}", get_str_from_f64(self.flange_thickness)),
+            format!(" {}", get_str_from_f64(self.web_thickness)),
+            format!(" {}", get_str_from_f64(self.radius)),
+            format!(" {}", get_str_from_f64(self.weight_by_meter)),
+            format!(" {}", get_str_from_f64(self.painting_surface_by_meter)),
+            format!(" {}", get_str_from_f64(self.web_start_cut)),
+            format!(" {}", get_str_from_f64(self.web_end_cut)),
+            format!(" {}", get_str_from_f64(self.flange_start_cut)),
+            format!(" {}", get_str_from_f64(self.flange_end_cut)),
+            format!("  {}", self.text1_info_on_piece),
+            format!("  {}", self.text2_info_on_piece),
+            format!("  {}", self.text3_info_on_piece),
+            format!("  {}", self.text4_info_on_piece),
+        ];
+        lines
+            .iter()
+            .map(|line| format!("{}\n", line.trim_end()))
+            .collect()
+    }
 }
 
 fn dstv_str<S, T>(inp: Option<S>) -> Result<T, ParseDstvError>
@@ -185,3 +314,12 @@ where
         .parse()
         .map_err(|e| ParseDstvError::from_err("Could not parse from DSTV", e))
 }
+
+/// Parses a header value as a finite f64
+fn dstv_f64<S: AsRef<str>, T: From<f64>>(inp: Option<S>) -> Result<T, ParseDstvError> {
+    let value: f64 = dstv_str(inp)?;
+    match value.is_finite() {
+        true => Ok(value.into()),
+        false => Err(ParseDstvError::new(format!("Not a finite value: {value}"))),
+    }
+}
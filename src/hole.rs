@@ -1,34 +1,78 @@
 pub use crate::prelude::DstvElement;
-use crate::{dstv_element::ParseDstvError, get_f64_from_str, prelude::PartFace};
-use std::str::FromStr;
+use crate::{
+    dstv_element::{Block, ParseDstvError},
+    geometry::Point,
+    get_f64_from_str, get_optional_f64_from_str, get_str_from_f64,
+    prelude::{Millimetres, PartFace},
+};
+use std::{
+    f64::consts::PI,
+    fmt::{self, Write},
+    str::FromStr,
+};
 /// Represents a hole in a plate
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hole {
     /// Diameter of the hole
-    pub diameter: f64,
-    /// Depth of the hole
-    pub depth: f64,
+    pub diameter: Millimetres,
+    /// Depth of the hole, if given
+    pub depth: Option<Millimetres>,
     /// X coordinate of the hole
-    pub x_coord: f64,
+    pub x_coord: Millimetres,
     /// Y coordinate of the hole
-    pub y_coord: f64,
+    pub y_coord: Millimetres,
     /// Flange code of the hole
     pub fl_code: PartFace,
 }
 
+impl Hole {
+    /// Returns the centre point of the hole
+    pub fn center(&self) -> Point {
+        Point::new(*self.x_coord, *self.y_coord)
+    }
+
+    /// Returns the area of the hole
+    pub fn area(&self) -> f64 {
+        PI * *self.diameter * *self.diameter / 4.0
+    }
+
+    /// Returns the circumference of the hole, which equals its cut length
+    pub fn perimeter(&self) -> f64 {
+        PI * *self.diameter
+    }
+
+    /// Returns the depth of the hole, or zero if no depth is given, the way
+    /// `Slot::depth` gives the depth of a through slot
+    pub fn depth_or_zero(&self) -> Millimetres {
+        self.depth.unwrap_or_default()
+    }
+
+    /// Checks whether a point lies within the hole
+    /// # Arguments
+    /// * `point` - The point to check, in the coordinates of the face of the hole
+    pub fn contains(&self, point: &Point) -> bool {
+        self.center().distance(point) <= *self.diameter / 2.0
+    }
+}
+
 impl DstvElement for Hole {
-    /// Parses a hole from a line of text
+    /// Parses a hole from the line of its block
     /// # Arguments
-    /// * `line` - A line of text from a DSTV file
+    /// * `block` - A block of a DSTV file holding a single hole
     /// # Returns
     /// A `Result` containing either a `Hole` or an error message
-    fn from_str(line: &str) -> Result<Self, ParseDstvError> {
-        let mut iter = line.split_whitespace();
+    fn from_block(block: &Block) -> Result<Self, ParseDstvError> {
+        let mut iter = block.first_line().split_whitespace();
         let fl_code = PartFace::from_str(iter.next().ok_or(ParseDstvError::new("No Hole Found"))?)?;
-        let x_coord = get_f64_from_str(iter.next(), "x_coord")?;
-        let y_coord = get_f64_from_str(iter.next(), "y_coord")?;
-        let diameter = get_f64_from_str(iter.next(), "diameter")?;
-        let depth = get_f64_from_str(iter.next(), "depth").unwrap_or_default();
+        let x_coord = get_f64_from_str(iter.next(), "x_coord")?.into();
+        let y_coord = get_f64_from_str(iter.next(), "y_coord")?.into();
+        let diameter = get_f64_from_str(iter.next(), "diameter")?.into();
+        // an unreadable depth must not lose the hole, so it is read as not given
+        let depth = get_optional_f64_from_str(iter.next(), "depth")
+            .ok()
+            .flatten()
+            .map(Millimetres);
         Ok(Self {
             diameter,
             depth,
@@ -50,6 +94,19 @@ impl DstvElement for Hole {
         )
     }
 
+    fn write(&self, out: &mut dyn Write) -> fmt::Result {
+        let depth = self.depth.map(get_str_from_f64).unwrap_or_default();
+        write!(
+            out,
+            "BO\n  {}{}{}{}{}\n",
+            self.fl_code.code(),
+            get_str_from_f64(self.x_coord),
+            get_str_from_f64(self.y_coord),
+            get_str_from_f64(self.diameter),
+            depth
+        )
+    }
+
     fn get_index(&self) -> usize {
         2
     }
@@ -57,4 +114,16 @@ impl DstvElement for Hole {
     fn get_facing(&self) -> &PartFace {
         &self.fl_code
     }
+
+    fn position(&self) -> Option<Point> {
+        Some(self.center())
+    }
+
+    fn bounding_box(&self) -> Option<(Point, Point)> {
+        let r = *self.diameter / 2.0;
+        Some((
+            Point::new(*self.x_coord - r, *self.y_coord - r),
+            Point::new(*self.x_coord + r, *self.y_coord + r),
+        ))
+    }
 }
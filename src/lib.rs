@@ -1,14 +1,36 @@
 mod bend;
+mod bevel;
 mod border;
+mod builder;
+mod catalogue;
 mod cut;
+mod dialect;
+mod diff;
 mod dstv;
 mod dstv_element;
 mod dstv_element_type;
+mod dxf;
+mod encoding;
+mod end_cut;
+mod features;
+mod fingerprint;
+mod folding;
+mod geometry;
 mod header;
 mod hole;
 mod numeration;
 mod part_face;
+mod properties;
+mod query;
+mod reader;
+mod registry;
+mod section;
 mod slot;
+mod steel_grade;
+mod syntax;
+mod transform;
+mod units;
+mod validation;
 
 use std::str::FromStr;
 
@@ -17,16 +39,37 @@ use prelude::ParseDstvError;
 /// Re-export all the modules
 pub mod prelude {
     pub use crate::bend::*;
+    pub use crate::bevel::*;
     pub use crate::border::*;
+    pub use crate::builder::*;
+    pub use crate::catalogue::*;
     pub use crate::cut::*;
+    pub use crate::dialect::*;
+    pub use crate::diff::*;
     pub use crate::dstv::*;
     pub use crate::dstv_element::*;
     pub use crate::dstv_element_type::*;
+    pub use crate::encoding::*;
+    pub use crate::end_cut::*;
+    pub use crate::features::*;
+    pub use crate::fingerprint::*;
+    pub use crate::folding::*;
+    pub use crate::geometry::*;
     pub use crate::header::*;
     pub use crate::hole::*;
     pub use crate::numeration::*;
     pub use crate::part_face::*;
+    pub use crate::properties::*;
+    pub use crate::query::*;
+    pub use crate::reader::*;
+    pub use crate::registry::*;
+    pub use crate::section::*;
     pub use crate::slot::*;
+    pub use crate::steel_grade::*;
+    pub use crate::syntax::*;
+    pub use crate::transform::*;
+    pub use crate::units::*;
+    pub use crate::validation::*;
 }
 
 /// Validate if flange is either u v o or h
@@ -62,6 +105,7 @@ pub fn validate_flange(flange: &str) -> bool {
 /// assert_eq!(get_f64_from_str(Some("1.0o"), "test"), Ok(1.0));
 /// assert_eq!(get_f64_from_str(Some("1.0"), "test"), Ok(1.0));
 /// assert_eq!(get_f64_from_str(None, "test"), Ok(0.0));
+/// assert!(get_f64_from_str(Some("NaN"), "test").is_err());
 /// ```
 pub fn get_f64_from_str(line: Option<&str>, name: &str) -> Result<f64, ParseDstvError> {
     match line {
@@ -72,7 +116,55 @@ pub fn get_f64_from_str(line: Option<&str>, name: &str) -> Result<f64, ParseDstv
             .replace("u", "")
             .replace("o", "")
             .parse::<f64>()
-            .map_err(|_| ParseDstvError::new(format!("`{name}` not a f64: got `{x}`"))),
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| ParseDstvError::new(format!("`{name}` not a f64: got `{x}`"))),
         None => Ok(0.0),
     }
 }
+
+/// Get an optional f64 from string, telling a missing value apart from a zero
+/// # arguments
+/// * `line` - line to parse
+/// * `name` - name of the element
+/// # return
+/// * `Option<f64>` - parsed f64, or `None` if the value is missing
+/// # example
+/// ```
+/// use dstv::get_optional_f64_from_str;
+/// assert_eq!(get_optional_f64_from_str(Some("0.0"), "test"), Ok(Some(0.0)));
+/// assert_eq!(get_optional_f64_from_str(None, "test"), Ok(None));
+/// ```
+pub fn get_optional_f64_from_str(
+    line: Option<&str>,
+    name: &str,
+) -> Result<Option<f64>, ParseDstvError> {
+    line.map(|value| get_f64_from_str(Some(value), name))
+        .transpose()
+}
+
+/// Formats a f64 as a right aligned DSTV value, preceded by at least one space.
+/// Values are written with two decimals, or up to four if the value needs them.
+/// # arguments
+/// * `value` - value to format
+/// # return
+/// * `String` - formatted value
+/// # example
+/// ```
+/// use dstv::get_str_from_f64;
+/// assert_eq!(get_str_from_f64(1.0), "      1.00");
+/// assert_eq!(get_str_from_f64(-20.125), "   -20.125");
+/// assert_eq!(get_str_from_f64(12345678.5), " 12345678.50");
+/// ```
+pub fn get_str_from_f64<V: Into<f64>>(value: V) -> String {
+    let value = value.into();
+    if !value.is_finite() {
+        return format!(" {value:>9}");
+    }
+    // adding zero turns a negative zero into a positive one
+    let formatted = format!("{:.4}", value + 0.0);
+    let trimmed = formatted.trim_end_matches('0');
+    let decimals = trimmed.len() - trimmed.find('.').unwrap_or(trimmed.len()) - 1;
+    let formatted = &formatted[..formatted.len() - 4 + decimals.max(2)];
+    format!(" {formatted:>9}")
+}
@@ -1,38 +1,50 @@
 pub use crate::prelude::DstvElement;
-use crate::{dstv_element::ParseDstvError, get_f64_from_str, prelude::PartFace};
-use std::str::FromStr;
+use crate::{
+    dstv_element::{Block, ParseDstvError},
+    geometry::Point,
+    get_f64_from_str, get_str_from_f64,
+    prelude::{Degrees, Millimetres, PartFace},
+};
+use std::{
+    fmt::{self, Write},
+    str::FromStr,
+};
 
 /// Represents a numeration element
 /// A numeration element is a text element that is used to label a part
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Numeration {
     /// Angle of the text
-    pub angle: f64,
+    pub angle: Degrees,
     /// Height of the text
-    pub letterheight: f64,
+    pub letterheight: Millimetres,
     /// Text to be displayed
     pub text: String,
     /// X coordinate of the text
-    pub x_coord: f64,
+    pub x_coord: Millimetres,
     /// Y coordinate of the text
-    pub y_coord: f64,
+    pub y_coord: Millimetres,
     /// Flange code of the text
     pub fl_code: PartFace,
 }
 
 impl DstvElement for Numeration {
-    /// Parses a numeration element from a line of text
+    /// Parses a numeration element from the line of its block
     /// # Arguments
-    /// * `line` - A line of text from a DSTV file
+    /// * `block` - A block of a DSTV file holding a numeration element
     /// # Returns
     /// A `Result` containing either a `Numeration` or an error message
-    fn from_str(line: &str) -> Result<Self, ParseDstvError> {
-        let mut iter = line.split_whitespace();
-        let fl_code = PartFace::from_str(iter.next().ok_or(ParseDstvError::new("No Numeration Found"))?)?;
-        let x_coord = get_f64_from_str(iter.next(), "x_coord")?;
-        let y_coord = get_f64_from_str(iter.next(), "y_coord")?;
-        let angle = get_f64_from_str(iter.next(), "angle")?;
-        let letterheight = get_f64_from_str(iter.next(), "letterheight")?;
+    fn from_block(block: &Block) -> Result<Self, ParseDstvError> {
+        let mut iter = block.first_line().split_whitespace();
+        let fl_code = PartFace::from_str(
+            iter.next()
+                .ok_or(ParseDstvError::new("No Numeration Found"))?,
+        )?;
+        let x_coord = get_f64_from_str(iter.next(), "x_coord")?.into();
+        let y_coord = get_f64_from_str(iter.next(), "y_coord")?.into();
+        let angle = get_f64_from_str(iter.next(), "angle")?.into();
+        let letterheight = get_f64_from_str(iter.next(), "letterheight")?.into();
         let text = iter
             .next()
             .ok_or(ParseDstvError::new("Text element not found"))?
@@ -54,6 +66,19 @@ impl DstvElement for Numeration {
         "".to_string()
     }
 
+    fn write(&self, out: &mut dyn Write) -> fmt::Result {
+        write!(
+            out,
+            "SI\n  {}{}{}{}{} {}\n",
+            self.fl_code.code(),
+            get_str_from_f64(self.x_coord),
+            get_str_from_f64(self.y_coord),
+            get_str_from_f64(self.angle),
+            get_str_from_f64(self.letterheight),
+            self.text
+        )
+    }
+
     fn get_index(&self) -> usize {
         2
     }
@@ -61,4 +86,8 @@ impl DstvElement for Numeration {
     fn get_facing(&self) -> &PartFace {
         &self.fl_code
     }
+
+    fn position(&self) -> Option<Point> {
+        Some(Point::new(*self.x_coord, *self.y_coord))
+    }
 }
@@ -2,17 +2,34 @@ use crate::prelude::ParseDstvError;
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PartFace {
     /// The Front or `v` Face
+    #[cfg_attr(feature = "serde", serde(rename = "v"))]
     Front,
     /// The Top or `o` Face
+    #[cfg_attr(feature = "serde", serde(rename = "o"))]
     Top,
     /// The Bottom or `u` Face
+    #[cfg_attr(feature = "serde", serde(rename = "u"))]
     Bottom,
     /// The Behind or `h` Face
+    #[cfg_attr(feature = "serde", serde(rename = "h"))]
     Behind,
 }
 
+impl PartFace {
+    /// Returns the flange code of the face as used in DSTV files, e.g. `v`
+    pub fn code(&self) -> &str {
+        match self {
+            PartFace::Front => "v",
+            PartFace::Top => "o",
+            PartFace::Bottom => "u",
+            PartFace::Behind => "h",
+        }
+    }
+}
+
 impl FromStr for PartFace {
     type Err = ParseDstvError;
 
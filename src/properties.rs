@@ -0,0 +1,178 @@
+use crate::{
+    dstv_element_type::DstvElementType,
+    geometry::Point,
+    prelude::{Border, CodeProfile, Dstv, PartFace},
+};
+
+/// The density of structural steel in kg/m³
+pub const STEEL_DENSITY: f64 = 7850.0;
+
+/// Converts a volume in mm³ and a density in kg/m³ to a weight in kg
+fn weight_of(volume: f64, density: f64) -> f64 {
+    volume * density * 1e-9
+}
+
+impl Dstv {
+    /// Returns the material of a face as a list of signed areas and their centroids.
+    /// Outer borders add material, inner borders, holes and slots remove it. A face
+    /// without an outer border is taken to be the full face of the profile.
+    fn face_material(&self, face: &PartFace) -> Vec<(f64, Point)> {
+        let mut material = self
+            .elements
+            .iter()
+            .filter(|element| element.get_facing() == face)
+            .filter_map(|element| match element {
+                DstvElementType::OuterBorder(e) => e.centroid().map(|c| (e.area(), c)),
+                DstvElementType::InnerBorder(e) => e.centroid().map(|c| (-e.area(), c)),
+                DstvElementType::Hole(e) => Some((-e.area(), e.center())),
+                DstvElementType::Slot(e) => Some((-e.area(), e.centroid())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let has_outer_border = self.elements.iter().any(|element| {
+            matches!(element, DstvElementType::OuterBorder(_)) && element.get_facing() == face
+        });
+        if !has_outer_border {
+            let width = self.header.face_width(face);
+            material.push((
+                width * *self.header.length,
+                Point::new(*self.header.length / 2.0, width / 2.0),
+            ));
+        }
+        material
+    }
+
+    /// Returns the net area of a face: the area within its outer borders minus inner
+    /// borders, holes and slots. A face without an outer border is taken to span the
+    /// full length and width of the piece.
+    /// # Arguments
+    /// * `face` - The face to calculate the net area for
+    /// # Returns
+    /// The net area in mm²
+    pub fn net_area(&self, face: &PartFace) -> f64 {
+        self.face_material(face).iter().map(|(area, _)| area).sum()
+    }
+
+    /// Returns the net centroid of a face, taking inner borders, holes and slots into account
+    /// # Arguments
+    /// * `face` - The face to calculate the centroid for
+    /// # Returns
+    /// The centroid in the coordinates of the face, or `None` if the face has no material
+    pub fn net_centroid(&self, face: &PartFace) -> Option<Point> {
+        let material = self.face_material(face);
+        let area: f64 = material.iter().map(|(area, _)| area).sum();
+        if area <= 0.0 {
+            return None;
+        }
+        let (mx, my) = material
+            .iter()
+            .fold((0.0, 0.0), |(mx, my), (a, c)| (mx + a * c.x, my + a * c.y));
+        Some(Point::new(mx / area, my / area))
+    }
+
+    /// Returns the total cut length of the piece: the perimeters of all borders plus the
+    /// circumferences of all holes and slots.
+    /// # Returns
+    /// The cut length in mm
+    pub fn cut_length(&self) -> f64 {
+        self.elements
+            .iter()
+            .map(|element| match element {
+                DstvElementType::OuterBorder(e) => e.perimeter(),
+                DstvElementType::InnerBorder(e) => e.perimeter(),
+                DstvElementType::Hole(e) => e.perimeter(),
+                DstvElementType::Slot(e) => e.perimeter(),
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    /// Returns the gross weight of the piece before processing.
+    /// Plates are weighed by their dimensions, other profiles by the weight by meter
+    /// from the header. If the header has no weight by meter, the profile is weighed by
+    /// the area of its cross-section, or else its faces are weighed as flat strips of
+    /// their width and thickness.
+    /// # Arguments
+    /// * `density` - The density of the material in kg/m³, e.g. `STEEL_DENSITY`
+    /// # Returns
+    /// The gross weight in kg
+    pub fn gross_weight(&self, density: f64) -> f64 {
+        let header = &self.header;
+        match &header.code_profile {
+            CodeProfile::B => weight_of(
+                header.face_width(&PartFace::Front)
+                    * header.face_thickness(&PartFace::Front)
+                    * *header.length,
+                density,
+            ),
+            _ if *header.weight_by_meter > 0.0 => *header.weight_by_meter * *header.length / 1000.0,
+            profile => match header.section_properties() {
+                Some(section) => section.weight_by_meter(density) * *header.length / 1000.0,
+                None => profile
+                    .faces()
+                    .iter()
+                    .map(|face| {
+                        weight_of(
+                            header.face_width(face) * header.face_thickness(face) * *header.length,
+                            density,
+                        )
+                    })
+                    .sum(),
+            },
+        }
+    }
+
+    /// Returns the net weight of the piece: the gross weight minus all material removed by
+    /// contours, holes and slots. Material removed where two faces meet is counted for
+    /// both faces.
+    /// # Arguments
+    /// * `density` - The density of the material in kg/m³, e.g. `STEEL_DENSITY`
+    /// # Returns
+    /// The net weight in kg
+    pub fn net_weight(&self, density: f64) -> f64 {
+        let header = &self.header;
+        let removed = header
+            .code_profile
+            .faces()
+            .iter()
+            .map(|face| {
+                let gross_area = header.face_width(face) * *header.length;
+                let removed_area = (gross_area - self.net_area(face)).max(0.0);
+                weight_of(removed_area * header.face_thickness(face), density)
+            })
+            .sum::<f64>();
+        (self.gross_weight(density) - removed).max(0.0)
+    }
+
+    /// Returns the centre of gravity of the piece in the coordinates of the front face:
+    /// `x` along the length of the piece and `y` over its height. The flanges of a
+    /// profile are taken to lie at half their thickness from the top and bottom.
+    /// # Returns
+    /// The centre of gravity, or `None` if the piece has no material
+    pub fn centre_of_gravity(&self) -> Option<Point> {
+        let header = &self.header;
+        let (mass, mx, my) = header
+            .code_profile
+            .faces()
+            .iter()
+            .filter_map(|face| {
+                let centroid = self.net_centroid(face)?;
+                let mass = self.net_area(face) * header.face_thickness(face);
+                let y = match (&header.code_profile, face) {
+                    (CodeProfile::B, _) | (_, PartFace::Front) | (_, PartFace::Behind) => {
+                        centroid.y
+                    }
+                    (_, PartFace::Top) => *header.profile_height - *header.flange_thickness / 2.0,
+                    (_, PartFace::Bottom) => *header.flange_thickness / 2.0,
+                };
+                Some((mass, centroid.x, y))
+            })
+            .fold((0.0, 0.0, 0.0), |(m, mx, my), (mass, x, y)| {
+                (m + mass, mx + mass * x, my + mass * y)
+            });
+        match mass > 0.0 {
+            true => Some(Point::new(mx / mass, my / mass)),
+            false => None,
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use crate::{
+    geometry::{bounding_box, polygon_contains, Point},
+    prelude::{
+        Bend, Border, Cut, Dstv, DstvElement, DstvElementType, Hole, InnerBorder, Numeration,
+        OuterBorder, PartFace, Slot,
+    },
+};
+use std::{cmp::Ordering, ops::RangeBounds};
+
+/// An element type that can be picked out of the elements of a part, so they can be
+/// iterated as that type with `Dstv::elements_of`
+pub trait ElementKind: DstvElement {
+    /// Returns the element if it is of this type
+    /// # Arguments
+    /// * `element` - The element to check
+    fn from_element(element: &DstvElementType) -> Option<&Self>;
+}
+
+macro_rules! element_kind {
+    ($($variant:ident),*) => {
+        $(
+            impl ElementKind for $variant {
+                fn from_element(element: &DstvElementType) -> Option<&Self> {
+                    match element {
+                        DstvElementType::$variant(e) => Some(e),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+element_kind!(OuterBorder, InnerBorder, Cut, Bend, Slot, Hole, Numeration);
+
+impl Dstv {
+    /// Returns the elements of one type, e.g. `dstv.elements_of::<Hole>()`
+    pub fn elements_of<'a, T: ElementKind + 'a>(&'a self) -> impl Iterator<Item = &'a T> {
+        self.elements.iter().filter_map(T::from_element)
+    }
+
+    /// Returns the elements of one type on a face
+    /// # Arguments
+    /// * `face` - The face to get the elements of
+    pub fn elements_on<'a, T: ElementKind + 'a>(
+        &'a self,
+        face: &'a PartFace,
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        self.elements_of::<T>()
+            .filter(move |element| element.get_facing() == face)
+    }
+
+    /// Returns the holes of the part
+    pub fn holes(&self) -> impl Iterator<Item = &Hole> {
+        self.elements_of()
+    }
+
+    /// Returns the holes on a face
+    /// # Arguments
+    /// * `face` - The face to get the holes of
+    pub fn holes_on<'a>(&'a self, face: &'a PartFace) -> impl Iterator<Item = &'a Hole> + 'a {
+        self.elements_on(face)
+    }
+
+    /// Returns the holes with a diameter in a range, e.g. `20.0..=24.0` for M20 bolts
+    /// # Arguments
+    /// * `diameters` - The range of diameters in mm
+    pub fn holes_with_diameter<R: RangeBounds<f64>>(
+        &self,
+        diameters: R,
+    ) -> impl Iterator<Item = &Hole> {
+        self.holes()
+            .filter(move |hole| diameters.contains(&*hole.diameter))
+    }
+
+    /// Returns the holes on a face whose centre lies within a region
+    /// # Arguments
+    /// * `face` - The face to get the holes of
+    /// * `region` - The corner points of the region, e.g. the four corners of a rectangle
+    pub fn holes_in<'a>(
+        &'a self,
+        face: &'a PartFace,
+        region: &'a [Point],
+    ) -> impl Iterator<Item = &'a Hole> + 'a {
+        self.holes_on(face)
+            .filter(move |hole| polygon_contains(region, &hole.center()))
+    }
+
+    /// Returns the slots of the part
+    pub fn slots(&self) -> impl Iterator<Item = &Slot> {
+        self.elements_of()
+    }
+
+    /// Returns the slots on a face
+    /// # Arguments
+    /// * `face` - The face to get the slots of
+    pub fn slots_on<'a>(&'a self, face: &'a PartFace) -> impl Iterator<Item = &'a Slot> + 'a {
+        self.elements_on(face)
+    }
+
+    /// Returns the outer border of a face
+    /// # Arguments
+    /// * `face` - The face to get the outer border of
+    /// # Returns
+    /// The first outer border on the face, or `None` if the face has none
+    pub fn outer_border(&self, face: &PartFace) -> Option<&OuterBorder> {
+        self.elements_of::<OuterBorder>()
+            .find(|border| border.get_facing() == face)
+    }
+
+    /// Returns the inner borders, such as cut-outs, of a face
+    /// # Arguments
+    /// * `face` - The face to get the inner borders of
+    pub fn inner_borders<'a>(
+        &'a self,
+        face: &'a PartFace,
+    ) -> impl Iterator<Item = &'a InnerBorder> + 'a {
+        self.elements_on(face)
+    }
+
+    /// Returns the marks of the part
+    pub fn marks(&self) -> impl Iterator<Item = &Numeration> {
+        self.elements_of()
+    }
+
+    /// Builds a spatial index of a face for nearest-hole and point-in-contour queries
+    /// # Arguments
+    /// * `face` - The face to index
+    pub fn spatial_index(&self, face: &PartFace) -> SpatialIndex<'_> {
+        let mut holes = self
+            .holes()
+            .filter(|hole| hole.get_facing() == face)
+            .collect::<Vec<_>>();
+        holes.sort_unstable_by(|a, b| a.x_coord.partial_cmp(&b.x_coord).unwrap_or(Ordering::Equal));
+        SpatialIndex {
+            holes,
+            slots: self
+                .slots()
+                .filter(|slot| slot.get_facing() == face)
+                .collect(),
+            outlines: self
+                .face_outlines(face)
+                .into_iter()
+                .map(Outline::new)
+                .collect(),
+            cut_outs: self
+                .elements_of::<InnerBorder>()
+                .filter(|border| border.get_facing() == face)
+                .map(|border| Outline::new(border.polygon()))
+                .collect(),
+        }
+    }
+}
+
+/// A polygon with its bounding box, to skip it quickly for points far away
+#[derive(Clone, Debug)]
+struct Outline {
+    /// The corner points of the polygon
+    polygon: Vec<Point>,
+    /// The lower left corner of the bounding box
+    min: Point,
+    /// The upper right corner of the bounding box
+    max: Point,
+}
+
+impl Outline {
+    fn new(polygon: Vec<Point>) -> Self {
+        // an empty polygon gets an empty box, which holds no points
+        let (min, max) = bounding_box(&polygon).unwrap_or((
+            Point::new(f64::INFINITY, f64::INFINITY),
+            Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY),
+        ));
+        Self { polygon, min, max }
+    }
+
+    fn contains(&self, point: &Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && polygon_contains(&self.polygon, point)
+    }
+}
+
+/// The holes, slots and contours of one face, indexed for queries by position.
+/// The holes are sorted along the length of the part, so the nearest hole is found
+/// without measuring the distance to every hole. The index borrows the part, so build
+/// a new one after changing it.
+#[derive(Clone, Debug)]
+pub struct SpatialIndex<'a> {
+    /// The holes of the face, sorted by x coordinate
+    holes: Vec<&'a Hole>,
+    /// The slots of the face
+    slots: Vec<&'a Slot>,
+    /// The outlines of the face after cuts, from its outer borders or the header
+    outlines: Vec<Outline>,
+    /// The inner borders of the face
+    cut_outs: Vec<Outline>,
+}
+
+impl<'a> SpatialIndex<'a> {
+    /// Returns the hole whose centre is nearest to a point
+    /// # Arguments
+    /// * `point` - The point to search from
+    /// # Returns
+    /// The nearest hole, or `None` if the face has no holes
+    pub fn nearest_hole(&self, point: &Point) -> Option<&'a Hole> {
+        let start = self.holes.partition_point(|hole| *hole.x_coord < point.x);
+        let mut nearest: Option<(f64, &'a Hole)> = None;
+        // walk away from the point in both directions until the holes are further
+        // away along x than the nearest hole found so far
+        let mut visit = |hole: &'a Hole| {
+            let best = nearest.map_or(f64::INFINITY, |(distance, _)| distance);
+            if (*hole.x_coord - point.x).abs() > best {
+                return false;
+            }
+            let distance = hole.center().distance(point);
+            if distance < best {
+                nearest = Some((distance, hole));
+            }
+            true
+        };
+        let (before, after) = self.holes.split_at(start);
+        before
+            .iter()
+            .rev()
+            .take_while(|hole| visit(hole))
+            .for_each(drop);
+        after.iter().take_while(|hole| visit(hole)).for_each(drop);
+        nearest.map(|(_, hole)| hole)
+    }
+
+    /// Returns the holes whose centre lies within a distance of a point
+    /// # Arguments
+    /// * `point` - The point to search from
+    /// * `distance` - The largest distance in mm
+    /// # Returns
+    /// The holes, nearest first
+    pub fn holes_within(&self, point: &Point, distance: f64) -> Vec<&'a Hole> {
+        let start = self
+            .holes
+            .partition_point(|hole| *hole.x_coord < point.x - distance);
+        let mut holes = self.holes[start..]
+            .iter()
+            .take_while(|hole| *hole.x_coord <= point.x + distance)
+            .map(|hole| (hole.center().distance(point), *hole))
+            .filter(|(d, _)| *d <= distance)
+            .collect::<Vec<_>>();
+        holes.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        holes.into_iter().map(|(_, hole)| hole).collect()
+    }
+
+    /// Returns the hole a point lies in
+    /// # Arguments
+    /// * `point` - The point to check
+    pub fn hole_at(&self, point: &Point) -> Option<&'a Hole> {
+        self.holes.iter().copied().find(|hole| hole.contains(point))
+    }
+
+    /// Returns the slot a point lies in
+    /// # Arguments
+    /// * `point` - The point to check
+    pub fn slot_at(&self, point: &Point) -> Option<&'a Slot> {
+        self.slots.iter().copied().find(|slot| slot.contains(point))
+    }
+
+    /// Checks whether a point lies within the outline of the face, ignoring its
+    /// cut-outs, holes and slots
+    /// # Arguments
+    /// * `point` - The point to check
+    pub fn in_outline(&self, point: &Point) -> bool {
+        self.outlines.iter().any(|outline| outline.contains(point))
+    }
+
+    /// Checks whether a point lies within one of the inner borders of the face
+    /// # Arguments
+    /// * `point` - The point to check
+    pub fn in_cut_out(&self, point: &Point) -> bool {
+        self.cut_outs.iter().any(|cut_out| cut_out.contains(point))
+    }
+
+    /// Checks whether a point lies on material of the face: within its outline and
+    /// outside its cut-outs, holes and slots
+    /// # Arguments
+    /// * `point` - The point to check
+    pub fn is_material(&self, point: &Point) -> bool {
+        self.in_outline(point)
+            && !self.in_cut_out(point)
+            && self.hole_at(point).is_none()
+            && self.slot_at(point).is_none()
+    }
+}
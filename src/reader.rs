@@ -0,0 +1,280 @@
+use crate::{
+    dialect::{HeaderLine, HeaderScanner},
+    dstv::{parse_dstv_element, END, HOLE_TYPE},
+    dstv_element::{Block, ParseDstvError},
+    prelude::{Dialect, Dstv, DstvElementType, ElementRegistry, Encoding, Header},
+    syntax::{is_comment, is_data_line},
+};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+/// Reads a DSTV file from a stream one element at a time, e.g. from stdin, a network
+/// stream or a zip archive. Only the block being read is held in memory.
+/// The header is read first, the elements follow as an iterator. Like
+/// `Dstv::from_str`, invalid blocks are skipped unless the reader is strict, and
+/// unknown blocks are kept as an `UnknownBlock`.
+pub struct DstvReader<R> {
+    /// The stream being read
+    reader: R,
+    /// The encoding of the stream, or `None` to detect it for each line
+    encoding: Option<Encoding>,
+    /// The parsers for blocks beyond the DSTV standard
+    registry: ElementRegistry,
+    /// True to return invalid blocks as errors instead of skipping them
+    strict: bool,
+    /// The header and the dialect of the stream, once read
+    header: Option<(Header, Dialect)>,
+    /// The number of lines read so far
+    line_number: usize,
+    /// The block code and the lines of the block being read, with their line numbers
+    block: Option<(String, Vec<(usize, String)>)>,
+    /// True once the stream is exhausted or failed
+    finished: bool,
+}
+
+impl<R: BufRead> DstvReader<R> {
+    /// Creates a reader over a stream. Lines that are valid UTF-8 are read as UTF-8,
+    /// other lines as Windows-1252.
+    /// # Arguments
+    /// * `reader` - The stream holding the DSTV file
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            encoding: None,
+            registry: ElementRegistry::default(),
+            strict: false,
+            header: None,
+            line_number: 0,
+            block: None,
+            finished: false,
+        }
+    }
+
+    /// Creates a reader over a stream in a known encoding
+    /// # Arguments
+    /// * `reader` - The stream holding the DSTV file
+    /// * `encoding` - The encoding of the stream
+    pub fn with_encoding(reader: R, encoding: Encoding) -> Self {
+        Self {
+            encoding: Some(encoding),
+            ..Self::new(reader)
+        }
+    }
+
+    /// Sets the parsers for blocks beyond the DSTV standard, before reading the elements
+    /// # Arguments
+    /// * `registry` - The registry of the parsers
+    pub fn set_registry(&mut self, registry: ElementRegistry) {
+        self.registry = registry;
+    }
+
+    /// Sets whether invalid blocks are returned as errors naming their line, instead of
+    /// being skipped
+    /// # Arguments
+    /// * `strict` - True to return invalid blocks as errors
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Reads the next line, without its line ending
+    fn read_line(&mut self) -> Result<Option<String>, ParseDstvError> {
+        let mut bytes = vec![];
+        let read = self
+            .reader
+            .read_until(b'\n', &mut bytes)
+            .map_err(|e| ParseDstvError::new(format!("Unable to read line: {e}")))?;
+        if read == 0 {
+            return Ok(None);
+        }
+        self.line_number += 1;
+        let line = self
+            .encoding
+            .unwrap_or_else(|| Encoding::detect(&bytes))
+            .decode(&bytes)?;
+        Ok(Some(
+            line.trim_end_matches('\n')
+                .trim_end_matches('\r')
+                .to_string(),
+        ))
+    }
+
+    /// Reads the next line that is not a comment, without its line ending
+    fn next_line(&mut self) -> Result<Option<String>, ParseDstvError> {
+        while let Some(line) = self.read_line()? {
+            if !is_comment(&line) {
+                return Ok(Some(line));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Reads the header and the dialect, unless they were read before. The header
+    /// ends after its text lines or at the first block, so nothing after it is read.
+    fn read_header(&mut self) -> Result<&(Header, Dialect), ParseDstvError> {
+        if self.header.is_none() {
+            let mut scanner = HeaderScanner::default();
+            let mut lines = vec![];
+            while !scanner.is_complete() {
+                let line = match self.read_line()? {
+                    Some(line) => line,
+                    None => break,
+                };
+                match scanner.next(&line) {
+                    HeaderLine::Header => lines.push(line.trim().to_string()),
+                    HeaderLine::End if line.trim() != END => {
+                        self.block = Some((line.trim().to_string(), vec![]))
+                    }
+                    _ => {}
+                }
+            }
+            let header = Header::from_lines(lines.iter().map(String::as_str).collect())
+                .map_err(|e| ParseDstvError::from_err("Invalid Header", e))?;
+            self.header = Some((header, scanner.dialect()));
+        }
+        Ok(self.header.as_ref().expect("the header was just read"))
+    }
+
+    /// Returns the header, reading it if needed. Nothing after the header is read, so
+    /// this is all that is needed to get the header of a large file.
+    /// # Error
+    /// * If the stream cannot be read
+    /// * If the header is invalid
+    pub fn header(&mut self) -> Result<&Header, ParseDstvError> {
+        Ok(&self.read_header()?.0)
+    }
+
+    /// Returns the variant of the DSTV format the stream was written in, reading the
+    /// header if needed
+    /// # Error
+    /// * If the stream cannot be read
+    /// * If the header is invalid
+    pub fn dialect(&mut self) -> Result<Dialect, ParseDstvError> {
+        Ok(self.read_header()?.1)
+    }
+
+    /// Starts a new block, returning the element of the block that was read before, or
+    /// the error reading it if the reader is strict
+    fn start_block(
+        &mut self,
+        code: String,
+        lines: Vec<(usize, String)>,
+    ) -> Option<Result<DstvElementType, ParseDstvError>> {
+        let (code, lines) = self.block.replace((code, lines))?;
+        if lines.is_empty() {
+            return None;
+        }
+        let (numbers, texts): (Vec<_>, Vec<_>) = lines
+            .iter()
+            .map(|(number, line)| (*number, line.as_str()))
+            .unzip();
+        let block = Block::new(&code, &texts).with_line_numbers(&numbers);
+        match parse_dstv_element(&block, &self.registry) {
+            Ok(element) => element.map(Ok),
+            Err(e) if self.strict => Some(Err(e)),
+            Err(_) => None,
+        }
+    }
+
+    /// Reads the remaining elements into a part
+    /// # Error
+    /// * If the stream cannot be read
+    /// * If the header is invalid
+    /// * If a block is invalid and the reader is strict
+    pub fn into_dstv(mut self) -> Result<Dstv, ParseDstvError> {
+        let header = self.header()?.clone();
+        let elements = self.collect::<Result<Vec<_>, _>>()?;
+        Ok(Dstv { header, elements })
+    }
+}
+
+impl<R: BufRead> Iterator for DstvReader<R> {
+    type Item = Result<DstvElementType, ParseDstvError>;
+
+    /// Reads the next element, reading the header first if needed
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        if let Err(e) = self.header() {
+            self.finished = true;
+            return Some(Err(e));
+        }
+        loop {
+            let line = match self.next_line() {
+                Ok(Some(line)) => line,
+                Ok(None) => {
+                    self.finished = true;
+                    return self.start_block(String::new(), vec![]);
+                }
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            };
+            if line.trim().is_empty() || line.trim() == END {
+                continue;
+            }
+            let number = self.line_number;
+            let element = match (is_data_line(&line), self.block.as_mut()) {
+                // every hole of a `BO` block is an element of its own
+                (true, Some((code, lines))) if code == HOLE_TYPE && lines.len() == 1 => {
+                    self.start_block(HOLE_TYPE.to_string(), vec![(number, line)])
+                }
+                (true, Some((_, lines))) => {
+                    lines.push((number, line));
+                    None
+                }
+                (true, None) => None,
+                (false, _) => self.start_block(line.trim().to_string(), vec![]),
+            };
+            if element.is_some() {
+                return element;
+            }
+        }
+    }
+}
+
+impl Dstv {
+    /// Reads a part from a stream
+    /// # Arguments
+    /// * `reader` - The stream holding the DSTV file
+    /// # Error
+    /// * If the stream cannot be read
+    /// * If the header is invalid
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ParseDstvError> {
+        DstvReader::new(reader).into_dstv()
+    }
+
+    /// Reads only the header of a file, e.g. to list many parts quickly. Reading stops
+    /// after the header, so contours and holes are never read.
+    /// # Arguments
+    /// * `file_path` - The path of the file
+    /// # Error
+    /// * If the file cannot be read
+    /// * If the header is invalid
+    pub fn read_header<P: AsRef<Path>>(file_path: P) -> Result<Header, ParseDstvError> {
+        let file_path = file_path.as_ref();
+        let file = File::open(file_path).map_err(|e| {
+            ParseDstvError::new(format!("Unable to read file: `{file_path:#?}`\t{e}"))
+        })?;
+        Header::from_reader(BufReader::new(file))
+    }
+}
+
+impl Header {
+    /// Reads the header from the start of a stream, without reading the elements
+    /// after it
+    /// # Arguments
+    /// * `reader` - The stream holding the DSTV file
+    /// # Error
+    /// * If the stream cannot be read
+    /// * If the header is invalid
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ParseDstvError> {
+        let mut reader = DstvReader::new(reader);
+        let header = reader.header()?;
+        Ok(header.clone())
+    }
+}
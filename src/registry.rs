@@ -0,0 +1,229 @@
+use crate::{
+    dstv_element::{Block, ParseDstvError},
+    prelude::{DstvElement, PartFace},
+};
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt::{self, Debug, Write},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// An element of a block that is not part of the DSTV standard, e.g. a proprietary
+/// block of a vendor. Any type implementing `DstvElement`, `Clone`, `Debug` and
+/// `PartialEq` is a custom element, stored in `DstvElementType::Custom`.
+pub trait CustomElement: DstvElement + Debug + Send + Sync {
+    /// Returns the element as a `DstvElement`
+    fn as_element(&self) -> &dyn DstvElement;
+
+    /// Returns the element as `Any`, to get back its type with `downcast_ref`
+    fn as_any(&self) -> &dyn Any;
+
+    /// Clones the element into a new box
+    fn clone_box(&self) -> Box<dyn CustomElement>;
+
+    /// Checks whether the element equals another custom element, which is only the
+    /// case if both are of the same type
+    fn eq_dyn(&self, other: &dyn CustomElement) -> bool;
+}
+
+impl<T> CustomElement for T
+where
+    T: DstvElement + Clone + Debug + PartialEq + Send + Sync + 'static,
+{
+    fn as_element(&self) -> &dyn DstvElement {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn CustomElement> {
+        Box::new(self.clone())
+    }
+
+    fn eq_dyn(&self, other: &dyn CustomElement) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+}
+
+impl Clone for Box<dyn CustomElement> {
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
+
+impl PartialEq for Box<dyn CustomElement> {
+    fn eq(&self, other: &Self) -> bool {
+        (**self).eq_dyn(&**other)
+    }
+}
+
+/// A block with an identifier that is neither part of the DSTV standard nor
+/// registered, kept as it was read so it is written back unchanged
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnknownBlock {
+    /// The identifier of the block, e.g. `KO`
+    pub code: String,
+    /// The data lines of the block as they were read
+    pub lines: Vec<String>,
+    /// The face given at the start of the first line, or the front face if there is
+    /// none
+    pub fl_code: PartFace,
+}
+
+impl UnknownBlock {
+    /// Creates an unknown block from its lines
+    /// # Arguments
+    /// * `code` - The identifier of the block
+    /// * `lines` - The data lines of the block
+    pub fn new<S: AsRef<str>>(code: &str, lines: &[S]) -> Self {
+        let fl_code = lines
+            .first()
+            .and_then(|line| line.as_ref().split_whitespace().next())
+            .and_then(|token| PartFace::from_str(token).ok())
+            .unwrap_or_default();
+        Self {
+            code: code.trim().to_string(),
+            lines: lines.iter().map(|line| line.as_ref().to_string()).collect(),
+            fl_code,
+        }
+    }
+}
+
+impl DstvElement for UnknownBlock {
+    /// Keeps the lines of a block as they are
+    fn from_block(block: &Block) -> Result<Self, ParseDstvError> {
+        Ok(Self::new(block.code, block.lines))
+    }
+
+    /// Reads an unknown block from its text: the identifier on the first line, followed
+    /// by the data lines
+    fn from_str(block: &str) -> Result<Self, ParseDstvError> {
+        let mut lines = block.lines();
+        let code = lines
+            .next()
+            .filter(|code| !code.trim().is_empty())
+            .ok_or_else(|| ParseDstvError::new("No block identifier found"))?;
+        Ok(Self::new(code, &lines.collect::<Vec<_>>()))
+    }
+
+    /// Unknown blocks are not drawn
+    fn to_svg(&self) -> String {
+        String::new()
+    }
+
+    fn write(&self, out: &mut dyn Write) -> fmt::Result {
+        writeln!(out, "{}", self.code)?;
+        self.lines
+            .iter()
+            .try_for_each(|line| writeln!(out, "{line}"))
+    }
+
+    fn get_facing(&self) -> &PartFace {
+        &self.fl_code
+    }
+}
+
+/// Reads a block into a custom element
+type Parser = Arc<dyn Fn(&Block) -> Result<Box<dyn CustomElement>, ParseDstvError> + Send + Sync>;
+
+/// The parsers for blocks beyond the DSTV standard, by block identifier.
+/// Registered parsers take precedence over the standard blocks. Blocks that are
+/// neither standard nor registered are kept as an `UnknownBlock`, unless the registry
+/// skips them.
+#[derive(Clone, Default)]
+pub struct ElementRegistry {
+    parsers: HashMap<String, Parser>,
+    skip_unknown: bool,
+}
+
+impl ElementRegistry {
+    /// Creates a registry without parsers, which keeps unknown blocks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a parser for a block identifier
+    /// # Arguments
+    /// * `code` - The identifier of the block, e.g. `XM`
+    /// * `parse` - Reads a block into an element
+    pub fn register<T, F>(mut self, code: &str, parse: F) -> Self
+    where
+        T: CustomElement + 'static,
+        F: Fn(&Block) -> Result<T, ParseDstvError> + Send + Sync + 'static,
+    {
+        let parser: Parser = Arc::new(move |block: &Block| {
+            parse(block).map(|element| Box::new(element) as Box<dyn CustomElement>)
+        });
+        self.parsers.insert(code.trim().to_string(), parser);
+        self
+    }
+
+    /// Registers an element type for a block identifier, reading the blocks with
+    /// `DstvElement::from_block`, e.g. `registry.register_element::<Weld>("XW")`
+    /// # Arguments
+    /// * `code` - The identifier of the block
+    pub fn register_element<T: CustomElement + 'static>(self, code: &str) -> Self {
+        self.register(code, T::from_block)
+    }
+
+    /// Skips blocks that are neither standard nor registered instead of keeping them
+    pub fn skip_unknown(mut self) -> Self {
+        self.skip_unknown = true;
+        self
+    }
+
+    /// Checks whether a parser is registered for a block identifier
+    /// # Arguments
+    /// * `code` - The identifier of the block
+    pub fn is_registered(&self, code: &str) -> bool {
+        self.parsers.contains_key(code.trim())
+    }
+
+    /// Reads a block with the parser registered for it
+    /// # Returns
+    /// The element, or `None` if no parser is registered for the block
+    pub(crate) fn parse(
+        &self,
+        block: &Block,
+    ) -> Option<Result<Box<dyn CustomElement>, ParseDstvError>> {
+        self.parsers
+            .get(block.code.trim())
+            .map(|parse| parse(block))
+    }
+
+    /// Returns true if unknown blocks are skipped
+    pub(crate) fn skips_unknown(&self) -> bool {
+        self.skip_unknown
+    }
+}
+
+impl PartialEq for ElementRegistry {
+    /// Registries are equal if they skip the same blocks and read the same blocks with
+    /// the same parsers
+    fn eq(&self, other: &Self) -> bool {
+        self.skip_unknown == other.skip_unknown
+            && self.parsers.len() == other.parsers.len()
+            && self.parsers.iter().all(|(code, parser)| {
+                other
+                    .parsers
+                    .get(code)
+                    .map_or(false, |other| Arc::ptr_eq(parser, other))
+            })
+    }
+}
+
+impl Debug for ElementRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut codes = self.parsers.keys().collect::<Vec<_>>();
+        codes.sort_unstable();
+        f.debug_struct("ElementRegistry")
+            .field("codes", &codes)
+            .field("skip_unknown", &self.skip_unknown)
+            .finish()
+    }
+}
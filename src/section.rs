@@ -0,0 +1,485 @@
+use crate::{
+    catalogue::hollow_corner_radii,
+    prelude::{CodeProfile, Header, PartFace, SectionFamily},
+};
+use std::f64::consts::PI;
+
+/// Number of bisection steps used to find the plastic neutral axis
+const BISECTION_STEPS: usize = 64;
+
+/// Properties of the cross-section of a piece.
+/// The `y` axis runs horizontally over the width of the section and the `z` axis
+/// vertically over its height, both through the centroid, so `y` is the major axis of
+/// an I-beam. Angles and Z-sections are described about these geometric axes, not
+/// about their principal axes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SectionProperties {
+    /// The cross-sectional area in mm²
+    pub area: f64,
+    /// The distance of the centroid from the left of the section in mm
+    pub centroid_y: f64,
+    /// The distance of the centroid from the bottom of the section in mm
+    pub centroid_z: f64,
+    /// The second moment of area about the `y` axis, Iy, in mm⁴
+    pub second_moment_y: f64,
+    /// The second moment of area about the `z` axis, Iz, in mm⁴
+    pub second_moment_z: f64,
+    /// The elastic section modulus about the `y` axis, Wel,y, in mm³
+    pub elastic_modulus_y: f64,
+    /// The elastic section modulus about the `z` axis, Wel,z, in mm³
+    pub elastic_modulus_z: f64,
+    /// The plastic section modulus about the `y` axis, Wpl,y, in mm³
+    pub plastic_modulus_y: f64,
+    /// The plastic section modulus about the `z` axis, Wpl,z, in mm³
+    pub plastic_modulus_z: f64,
+    /// The radius of gyration about the `y` axis, iy, in mm
+    pub radius_of_gyration_y: f64,
+    /// The radius of gyration about the `z` axis, iz, in mm
+    pub radius_of_gyration_z: f64,
+    /// The St. Venant torsion constant, It, in mm⁴
+    pub torsion_constant: f64,
+}
+
+impl SectionProperties {
+    /// Returns the weight of the section per meter length
+    /// # Arguments
+    /// * `density` - The density of the material in kg/m³, e.g. `STEEL_DENSITY`
+    /// # Returns
+    /// The weight by meter in kg/m
+    pub fn weight_by_meter(&self, density: f64) -> f64 {
+        self.area * density * 1e-6
+    }
+
+    /// Calculates the properties of a section from its dimensions, as they are given in
+    /// the header. Root radii of rolled sections and corner radii of hollow sections are
+    /// taken into account, the taper of UPN flanges and the lips of cold formed sections
+    /// are not.
+    /// # Arguments
+    /// * `profile` - The profile type of the section. `SO` is taken to be a Z-section.
+    /// * `height` - The height of the section, or the outside diameter of round sections
+    /// * `width` - The width of the section, or of a single flange of a Z-section
+    /// * `web_thickness` - The thickness of the web, or the wall thickness of tubes
+    /// * `flange_thickness` - The thickness of the flanges
+    /// * `radius` - The root radius, or the outer corner radius of rectangular tubes
+    /// # Returns
+    /// The section properties, or `None` if a required dimension is missing
+    pub fn from_dimensions(
+        profile: &CodeProfile,
+        height: f64,
+        width: f64,
+        web_thickness: f64,
+        flange_thickness: f64,
+        radius: f64,
+    ) -> Option<Self> {
+        Self::from_corner_radii(
+            profile,
+            height,
+            width,
+            web_thickness,
+            flange_thickness,
+            radius,
+            None,
+        )
+    }
+
+    /// Calculates the properties of a section from its dimensions, like `from_dimensions`
+    /// # Arguments
+    /// * `inner_radius` - The inner corner radius of rectangular tubes, or `None` to take
+    ///   the inner corners to be concentric with the outer corners
+    fn from_corner_radii(
+        profile: &CodeProfile,
+        height: f64,
+        width: f64,
+        web_thickness: f64,
+        flange_thickness: f64,
+        radius: f64,
+        inner_radius: Option<f64>,
+    ) -> Option<Self> {
+        let (h, b, tw, tf, r) = (
+            height,
+            width,
+            web_thickness,
+            flange_thickness,
+            radius.max(0.0),
+        );
+        if h <= 0.0 {
+            return None;
+        }
+        let shape = match profile {
+            CodeProfile::RU => return Some(Self::circle(h, h / 2.0)),
+            CodeProfile::RO if tw > 0.0 && tw < h / 2.0 => return Some(Self::circle(h, tw)),
+            CodeProfile::RO => return Some(Self::circle(h, h / 2.0)),
+            _ if b <= 0.0 || tw <= 0.0 || tw >= b => return None,
+            CodeProfile::B => Shape::new().rect(0.0, tw, 0.0, h),
+            _ if tf <= 0.0 || 2.0 * tf >= h => return None,
+            CodeProfile::I => {
+                let (w0, w1) = ((b - tw) / 2.0, (b + tw) / 2.0);
+                Shape::new()
+                    .rect(0.0, b, 0.0, tf)
+                    .rect(0.0, b, h - tf, h)
+                    .rect(w0, w1, tf, h - tf)
+                    .corner(w0, tf, -1.0, 1.0, r, 1.0)
+                    .corner(w1, tf, 1.0, 1.0, r, 1.0)
+                    .corner(w0, h - tf, -1.0, -1.0, r, 1.0)
+                    .corner(w1, h - tf, 1.0, -1.0, r, 1.0)
+            }
+            CodeProfile::U | CodeProfile::C => Shape::new()
+                .rect(0.0, tw, 0.0, h)
+                .rect(tw, b, 0.0, tf)
+                .rect(tw, b, h - tf, h)
+                .corner(tw, tf, 1.0, 1.0, r, 1.0)
+                .corner(tw, h - tf, 1.0, -1.0, r, 1.0),
+            CodeProfile::L => Shape::new()
+                .rect(0.0, tw, 0.0, h)
+                .rect(tw, b, 0.0, tf)
+                .corner(tw, tf, 1.0, 1.0, r, 1.0),
+            CodeProfile::T => {
+                let (w0, w1) = ((b - tw) / 2.0, (b + tw) / 2.0);
+                Shape::new()
+                    .rect(0.0, b, h - tf, h)
+                    .rect(w0, w1, 0.0, h - tf)
+                    .corner(w0, h - tf, -1.0, -1.0, r, 1.0)
+                    .corner(w1, h - tf, 1.0, -1.0, r, 1.0)
+            }
+            CodeProfile::M => {
+                let ri = inner_radius.unwrap_or(r - tw.max(tf)).max(0.0);
+                Shape::new()
+                    .rect(0.0, b, 0.0, h)
+                    .void(tw, b - tw, tf, h - tf)
+                    .corner(0.0, 0.0, 1.0, 1.0, r, -1.0)
+                    .corner(b, 0.0, -1.0, 1.0, r, -1.0)
+                    .corner(0.0, h, 1.0, -1.0, r, -1.0)
+                    .corner(b, h, -1.0, -1.0, r, -1.0)
+                    .corner(tw, tf, 1.0, 1.0, ri, 1.0)
+                    .corner(b - tw, tf, -1.0, 1.0, ri, 1.0)
+                    .corner(tw, h - tf, 1.0, -1.0, ri, 1.0)
+                    .corner(b - tw, h - tf, -1.0, -1.0, ri, 1.0)
+            }
+            CodeProfile::SO => Shape::new()
+                .rect(0.0, b, 0.0, tf)
+                .rect(b - tw, b, tf, h - tf)
+                .rect(b - tw, 2.0 * b - tw, h - tf, h),
+        };
+        let torsion_constant = match profile {
+            CodeProfile::B => h * tw.powi(3) / 3.0 * (1.0 - 0.63 * tw / h).max(0.0),
+            CodeProfile::I => i_beam_torsion(h, b, tw, tf, r),
+            CodeProfile::U | CodeProfile::C => {
+                (2.0 * b * tf.powi(3) + (h - 2.0 * tf) * tw.powi(3)) / 3.0
+            }
+            CodeProfile::L => (h * tw.powi(3) + (b - tw) * tf.powi(3)) / 3.0,
+            CodeProfile::T => (b * tf.powi(3) + (h - tf) * tw.powi(3)) / 3.0,
+            CodeProfile::SO => (2.0 * b * tf.powi(3) + (h - 2.0 * tf) * tw.powi(3)) / 3.0,
+            // thin walled closed section, following Bredt
+            _ => {
+                let (bm, hm) = (b - tw, h - tf);
+                let area = bm * hm;
+                4.0 * area * area / (2.0 * bm / tf + 2.0 * hm / tw)
+            }
+        };
+        Some(shape.properties(torsion_constant))
+    }
+
+    /// Calculates the properties of a round bar or a round tube
+    /// # Arguments
+    /// * `diameter` - The outside diameter
+    /// * `thickness` - The wall thickness, or half the diameter for a round bar
+    fn circle(diameter: f64, thickness: f64) -> Self {
+        let inner = diameter - 2.0 * thickness;
+        let area = PI / 4.0 * (diameter.powi(2) - inner.powi(2));
+        let second_moment = PI / 64.0 * (diameter.powi(4) - inner.powi(4));
+        let elastic_modulus = second_moment / (diameter / 2.0);
+        let plastic_modulus = (diameter.powi(3) - inner.powi(3)) / 6.0;
+        let radius_of_gyration = (second_moment / area).sqrt();
+        Self {
+            area,
+            centroid_y: diameter / 2.0,
+            centroid_z: diameter / 2.0,
+            second_moment_y: second_moment,
+            second_moment_z: second_moment,
+            elastic_modulus_y: elastic_modulus,
+            elastic_modulus_z: elastic_modulus,
+            plastic_modulus_y: plastic_modulus,
+            plastic_modulus_z: plastic_modulus,
+            radius_of_gyration_y: radius_of_gyration,
+            radius_of_gyration_z: radius_of_gyration,
+            torsion_constant: 2.0 * second_moment,
+        }
+    }
+}
+
+/// Returns the torsion constant of a rolled I-section, including the contribution of
+/// the root radii, as used by the tables of the steel producers
+fn i_beam_torsion(h: f64, b: f64, tw: f64, tf: f64, r: f64) -> f64 {
+    let plates = 2.0 / 3.0 * b * tf.powi(3) + (h - 2.0 * tf) * tw.powi(3) / 3.0;
+    if r <= 0.0 {
+        return plates;
+    }
+    let alpha = -0.042 + 0.2204 * tw / tf + 0.1355 * r / tf
+        - 0.0865 * r * tw / tf.powi(2)
+        - 0.0725 * tw.powi(2) / tf.powi(2);
+    let d = ((tf + r).powi(2) + tw * (r + tw / 4.0)) / (2.0 * r + tf);
+    plates + 2.0 * alpha * d.powi(4) - 0.420 * tf.powi(4)
+}
+
+/// A rectangular piece of a section, adding or removing material
+struct Rect {
+    y0: f64,
+    y1: f64,
+    z0: f64,
+    z1: f64,
+    sign: f64,
+}
+
+/// A small piece of a section, such as a fillet, taken as its area at its centroid
+struct Lump {
+    y: f64,
+    z: f64,
+    area: f64,
+}
+
+/// A section built up from rectangles and lumps
+struct Shape {
+    rects: Vec<Rect>,
+    lumps: Vec<Lump>,
+}
+
+impl Shape {
+    fn new() -> Self {
+        Self {
+            rects: vec![],
+            lumps: vec![],
+        }
+    }
+
+    /// Adds a rectangle of material
+    fn rect(mut self, y0: f64, y1: f64, z0: f64, z1: f64) -> Self {
+        self.rects.push(Rect {
+            y0,
+            y1,
+            z0,
+            z1,
+            sign: 1.0,
+        });
+        self
+    }
+
+    /// Removes a rectangle of material
+    fn void(mut self, y0: f64, y1: f64, z0: f64, z1: f64) -> Self {
+        self.rects.push(Rect {
+            y0,
+            y1,
+            z0,
+            z1,
+            sign: -1.0,
+        });
+        self
+    }
+
+    /// Adds or removes the material between a corner and a quarter circle of radius `r`
+    /// fitted in it. The direction points from the corner into the rounded quadrant.
+    fn corner(mut self, y: f64, z: f64, dy: f64, dz: f64, r: f64, sign: f64) -> Self {
+        if r > 0.0 {
+            let offset = r * (10.0 - 3.0 * PI) / (3.0 * (4.0 - PI));
+            self.lumps.push(Lump {
+                y: y + dy * offset,
+                z: z + dz * offset,
+                area: sign * (1.0 - PI / 4.0) * r * r,
+            });
+        }
+        self
+    }
+
+    /// Returns the distribution of the section along the vertical or the horizontal axis
+    fn axis(&self, vertical: bool) -> Axis {
+        let strips = self
+            .rects
+            .iter()
+            .map(|r| match vertical {
+                true => (r.z0, r.z1, r.sign * (r.y1 - r.y0)),
+                false => (r.y0, r.y1, r.sign * (r.z1 - r.z0)),
+            })
+            .collect();
+        let lumps = self
+            .lumps
+            .iter()
+            .map(|l| match vertical {
+                true => (l.z, l.area),
+                false => (l.y, l.area),
+            })
+            .collect();
+        Axis::new(strips, lumps)
+    }
+
+    fn properties(&self, torsion_constant: f64) -> SectionProperties {
+        let z = self.axis(true);
+        let y = self.axis(false);
+        let area = z.area;
+        SectionProperties {
+            area,
+            centroid_y: y.centroid,
+            centroid_z: z.centroid,
+            second_moment_y: z.second_moment,
+            second_moment_z: y.second_moment,
+            elastic_modulus_y: z.second_moment / z.extreme_fibre(),
+            elastic_modulus_z: y.second_moment / y.extreme_fibre(),
+            plastic_modulus_y: z.plastic_modulus(),
+            plastic_modulus_z: y.plastic_modulus(),
+            radius_of_gyration_y: (z.second_moment / area).sqrt(),
+            radius_of_gyration_z: (y.second_moment / area).sqrt(),
+            torsion_constant,
+        }
+    }
+}
+
+/// The distribution of a section along one axis: strips with their range along the
+/// axis and their signed breadth, and lumps with their position and area
+struct Axis {
+    strips: Vec<(f64, f64, f64)>,
+    lumps: Vec<(f64, f64)>,
+    area: f64,
+    centroid: f64,
+    second_moment: f64,
+}
+
+impl Axis {
+    fn new(strips: Vec<(f64, f64, f64)>, lumps: Vec<(f64, f64)>) -> Self {
+        let mut area = 0.0;
+        let mut first = 0.0;
+        let mut second = 0.0;
+        for (lo, hi, breadth) in &strips {
+            area += breadth * (hi - lo);
+            first += breadth * (hi * hi - lo * lo) / 2.0;
+            second += breadth * (hi.powi(3) - lo.powi(3)) / 3.0;
+        }
+        for (at, a) in &lumps {
+            area += a;
+            first += a * at;
+            second += a * at * at;
+        }
+        let centroid = first / area;
+        Self {
+            strips,
+            lumps,
+            area,
+            centroid,
+            second_moment: second - area * centroid * centroid,
+        }
+    }
+
+    /// Returns the largest distance from the centroid to the edge of the section
+    fn extreme_fibre(&self) -> f64 {
+        self.strips
+            .iter()
+            .filter(|(_, _, breadth)| *breadth > 0.0)
+            .map(|(lo, hi, _)| (self.centroid - lo).max(hi - self.centroid))
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns the area of the section below a coordinate
+    fn area_below(&self, at: f64) -> f64 {
+        let strips = self
+            .strips
+            .iter()
+            .map(|(lo, hi, breadth)| breadth * (at.min(*hi) - lo).max(0.0))
+            .sum::<f64>();
+        let lumps = self
+            .lumps
+            .iter()
+            .filter(|(position, _)| *position < at)
+            .map(|(_, a)| a)
+            .sum::<f64>();
+        strips + lumps
+    }
+
+    /// Returns the plastic section modulus: the first moment of area of both halves of
+    /// the section about the axis that divides it in two equal areas
+    fn plastic_modulus(&self) -> f64 {
+        let (mut lo, mut hi) = self
+            .strips
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), (a, b, _)| {
+                (lo.min(*a), hi.max(*b))
+            });
+        for _ in 0..BISECTION_STEPS {
+            let mid = (lo + hi) / 2.0;
+            match self.area_below(mid) < self.area / 2.0 {
+                true => lo = mid,
+                false => hi = mid,
+            }
+        }
+        let axis = (lo + hi) / 2.0;
+        let strips = self
+            .strips
+            .iter()
+            .map(|(lo, hi, breadth)| {
+                let below = (axis.min(*hi) - lo).max(0.0);
+                let above = (hi - axis.max(*lo)).max(0.0);
+                let (a, b) = ((axis - lo).max(0.0), (hi - axis).max(0.0));
+                breadth
+                    * ((a * a - (a - below).powi(2)) / 2.0 + (b * b - (b - above).powi(2)) / 2.0)
+            })
+            .sum::<f64>();
+        let lumps = self
+            .lumps
+            .iter()
+            .map(|(position, a)| a * (position - axis).abs())
+            .sum::<f64>();
+        strips + lumps
+    }
+}
+
+impl Header {
+    /// Calculates the properties of the cross-section of the piece from the dimensions in
+    /// the header. Plates are taken to stand on their edge, with their width as height.
+    /// Special profiles are looked up in the catalogue of standard sections, or taken to
+    /// be a Z-section if their profile name starts with a `Z`.
+    /// # Returns
+    /// The section properties, or `None` if the dimensions do not describe a section
+    pub fn section_properties(&self) -> Option<SectionProperties> {
+        match &self.code_profile {
+            CodeProfile::B => SectionProperties::from_dimensions(
+                &CodeProfile::B,
+                self.face_width(&PartFace::Front),
+                self.face_width(&PartFace::Front),
+                self.face_thickness(&PartFace::Front),
+                0.0,
+                0.0,
+            ),
+            CodeProfile::SO => match self.catalogue_section() {
+                Ok(section) => SectionProperties::from_corner_radii(
+                    &section.family.code_profile(),
+                    section.height,
+                    section.width,
+                    section.web_thickness,
+                    section.flange_thickness,
+                    section.radius.unwrap_or_default(),
+                    match section.family {
+                        SectionFamily::Rhs | SectionFamily::Shs => {
+                            Some(hollow_corner_radii(section.web_thickness).1)
+                        }
+                        _ => None,
+                    },
+                ),
+                Err(_) if self.profile.trim().to_uppercase().starts_with('Z') => {
+                    SectionProperties::from_dimensions(
+                        &CodeProfile::SO,
+                        *self.profile_height,
+                        *self.flange_width,
+                        *self.web_thickness,
+                        *self.flange_thickness,
+                        0.0,
+                    )
+                }
+                Err(_) => None,
+            },
+            profile => SectionProperties::from_dimensions(
+                profile,
+                *self.profile_height,
+                *self.flange_width,
+                *self.web_thickness,
+                *self.flange_thickness,
+                *self.radius,
+            ),
+        }
+    }
+}
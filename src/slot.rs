@@ -1,46 +1,103 @@
-use crate::dstv_element::ParseDstvError;
-use crate::get_f64_from_str;
-use crate::prelude::{DstvElement, PartFace};
-use std::str::FromStr;
+use crate::dstv_element::{Block, ParseDstvError};
+use crate::geometry::{bounding_box, distance_to_polygon, polygon_contains, Point};
+use crate::prelude::{Degrees, DstvElement, Millimetres, PartFace};
+use crate::{get_f64_from_str, get_str_from_f64};
+use std::{
+    f64::consts::PI,
+    fmt::{self, Write},
+    str::FromStr,
+};
 
 /// Represents a slot element
 /// A slot element is a hole that has been cut out of a plate but is not a circle shaped hole
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Slot {
     /// Angle of the slot
-    pub angle: f64,
+    pub angle: Degrees,
     /// Length of the slot
-    pub slot_length: f64,
+    pub slot_length: Millimetres,
     /// Width of the slot
-    pub slot_width: f64,
+    pub slot_width: Millimetres,
     /// Diameter of the pub
-    pub diameter: f64,
-    /// Depth of the pub
-    pub depth: f64,
+    pub diameter: Millimetres,
+    /// Depth of the slot, zero for a through slot. Unlike the depth of a hole it is
+    /// always given, as the length, width and angle of the slot follow it.
+    pub depth: Millimetres,
     /// X coordinate of the pub
-    pub x_coord: f64,
+    pub x_coord: Millimetres,
     /// Y coordinate of the pub
-    pub y_coord: f64,
+    pub y_coord: Millimetres,
     /// Flange code of the pub
     pub fl_code: PartFace,
 }
 
+impl Slot {
+    /// Returns the area of the slot.
+    /// The slot is the hole stretched by its length along the slot angle and by its
+    /// width perpendicular to it.
+    pub fn area(&self) -> f64 {
+        let r = self.diameter / 2.0;
+        (self.slot_length + self.diameter) * (self.slot_width + self.diameter) - (4.0 - PI) * r * r
+    }
+
+    /// Returns the length of the outline of the slot, which equals its cut length
+    pub fn perimeter(&self) -> f64 {
+        (PI * self.diameter + 2.0 * (self.slot_length + self.slot_width)).value()
+    }
+
+    /// Checks whether a point lies within the slot
+    /// # Arguments
+    /// * `point` - The point to check, in the coordinates of the face of the slot
+    pub fn contains(&self, point: &Point) -> bool {
+        // the slot is the rectangle between the centres of its roundings, widened by
+        // the radius of the hole
+        let centres = self.centres();
+        polygon_contains(&centres, point)
+            || distance_to_polygon(&centres, point) <= *self.diameter / 2.0
+    }
+
+    /// Returns the centres of the roundings of the slot, counter-clockwise
+    pub(crate) fn centres(&self) -> Vec<Point> {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let (length, width) = (*self.slot_length, *self.slot_width);
+        [(0.0, 0.0), (length, 0.0), (length, width), (0.0, width)]
+            .iter()
+            .map(|(u, v)| {
+                Point::new(
+                    *self.x_coord + u * cos - v * sin,
+                    *self.y_coord + u * sin + v * cos,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the centre point of the slot
+    pub fn centroid(&self) -> Point {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        Point::new(
+            (self.x_coord + (self.slot_length * cos - self.slot_width * sin) / 2.0).value(),
+            (self.y_coord + (self.slot_length * sin + self.slot_width * cos) / 2.0).value(),
+        )
+    }
+}
+
 impl DstvElement for Slot {
-    /// Parses a slot from a line of text
+    /// Parses a slot from the line of its block
     /// # Arguments
-    /// * `line` - A line of text from a DSTV file
+    /// * `block` - A block of a DSTV file holding a single slot
     /// # Returns
     /// A `Result` containing either a `Slot` or an error message
-    fn from_str(line: &str) -> Result<Self, ParseDstvError> {
-        let mut iter = line.split_whitespace();
+    fn from_block(block: &Block) -> Result<Self, ParseDstvError> {
+        let mut iter = block.first_line().split_whitespace();
         let fl_code = PartFace::from_str(iter.next().ok_or(ParseDstvError::new("No Slot Found"))?)?;
-        let x_coord = get_f64_from_str(iter.next(), "x_coord")?;
-        let y_coord = get_f64_from_str(iter.next(), "y_coord")?;
-        let diameter = get_f64_from_str(iter.next(), "diameter")?;
-        let depth = get_f64_from_str(iter.next(), "depth")?;
-        let slot_length = get_f64_from_str(iter.next(), "slot_length")?;
-        let slot_width = get_f64_from_str(iter.next(), "slot_width")?;
-        let angle = get_f64_from_str(iter.next(), "angle")?;
+        let x_coord = get_f64_from_str(iter.next(), "x_coord")?.into();
+        let y_coord = get_f64_from_str(iter.next(), "y_coord")?.into();
+        let diameter = get_f64_from_str(iter.next(), "diameter")?.into();
+        let depth = get_f64_from_str(iter.next(), "depth")?.into();
+        let slot_length = get_f64_from_str(iter.next(), "slot_length")?.into();
+        let slot_width = get_f64_from_str(iter.next(), "slot_width")?.into();
+        let angle = get_f64_from_str(iter.next(), "angle")?.into();
         Ok(Self {
             angle,
             slot_length,
@@ -67,6 +124,21 @@ impl DstvElement for Slot {
         )
     }
 
+    fn write(&self, out: &mut dyn Write) -> fmt::Result {
+        write!(
+            out,
+            "BO\n  {}{}{}{}{}{}{}{}\n",
+            self.fl_code.code(),
+            get_str_from_f64(self.x_coord),
+            get_str_from_f64(self.y_coord),
+            get_str_from_f64(self.diameter),
+            get_str_from_f64(self.depth),
+            get_str_from_f64(self.slot_length),
+            get_str_from_f64(self.slot_width),
+            get_str_from_f64(self.angle)
+        )
+    }
+
     fn get_index(&self) -> usize {
         2
     }
@@ -74,4 +146,18 @@ impl DstvElement for Slot {
     fn get_facing(&self) -> &PartFace {
         &self.fl_code
     }
+
+    fn position(&self) -> Option<Point> {
+        Some(Point::new(*self.x_coord, *self.y_coord))
+    }
+
+    fn bounding_box(&self) -> Option<(Point, Point)> {
+        let r = *self.diameter / 2.0;
+        bounding_box(&self.centres()).map(|(min, max)| {
+            (
+                Point::new(min.x - r, min.y - r),
+                Point::new(max.x + r, max.y + r),
+            )
+        })
+    }
 }
@@ -0,0 +1,395 @@
+use crate::{
+    dstv_element::ParseDstvError,
+    prelude::{Header, STEEL_DENSITY},
+};
+use std::str::FromStr;
+
+/// The standard a steel grade is specified by
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SteelStandard {
+    /// EN 10025, hot rolled structural steel
+    En10025,
+    /// EN 10219, cold formed hollow sections. Hot finished hollow sections to EN 10210
+    /// carry the same designations.
+    En10219,
+    /// ASTM structural steel, e.g. A36 or A992
+    Astm,
+}
+
+/// The delivery condition of a steel grade, given after the `+` in the designation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeliveryCondition {
+    /// `+AR`, as rolled
+    AsRolled,
+    /// `+N`, normalized or normalized rolled
+    Normalized,
+    /// `+M`, thermomechanically rolled
+    Thermomechanical,
+    /// `+Q`, quenched and tempered
+    QuenchedTempered,
+}
+
+impl DeliveryCondition {
+    /// Returns the designation of the delivery condition, e.g. `+N`
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            DeliveryCondition::AsRolled => "+AR",
+            DeliveryCondition::Normalized => "+N",
+            DeliveryCondition::Thermomechanical => "+M",
+            DeliveryCondition::QuenchedTempered => "+Q",
+        }
+    }
+}
+
+impl FromStr for DeliveryCondition {
+    type Err = ParseDstvError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().trim_start_matches('+') {
+            "AR" => Ok(DeliveryCondition::AsRolled),
+            "N" => Ok(DeliveryCondition::Normalized),
+            "M" => Ok(DeliveryCondition::Thermomechanical),
+            "Q" => Ok(DeliveryCondition::QuenchedTempered),
+            _ => Err(ParseDstvError::new(format!(
+                "Invalid delivery condition: {s}"
+            ))),
+        }
+    }
+}
+
+/// A steel grade parsed from the steel quality in the header
+#[derive(Clone, Debug, PartialEq)]
+pub struct SteelGrade {
+    /// The standard the grade is specified by
+    pub standard: SteelStandard,
+    /// The nominal minimum yield strength in MPa, for the thinnest products
+    pub yield_strength: f64,
+    /// The quality or toughness class, e.g. `J2` or `NL`. Empty for ASTM grades.
+    pub quality: String,
+    /// The delivery condition, if the designation includes one
+    pub delivery_condition: Option<DeliveryCondition>,
+    /// The grade was given by a legacy name, such as `St37-2` or `Fe510`
+    pub legacy: bool,
+    name: String,
+}
+
+/// Legacy DIN 17100 and EN 10025:1990 names and their EN 10025 successors
+const LEGACY_GRADES: &[(&str, &str)] = &[
+    ("ST37", "S235JR"),
+    ("ST372", "S235JR"),
+    ("RST372", "S235JR"),
+    ("UST372", "S235JR"),
+    ("ST373", "S235J2"),
+    ("ST373N", "S235J2"),
+    ("ST373U", "S235J0"),
+    ("ST442", "S275JR"),
+    ("ST443", "S275J2"),
+    ("ST443N", "S275J2"),
+    ("ST443U", "S275J0"),
+    ("ST52", "S355J2+N"),
+    ("ST523", "S355J2+N"),
+    ("ST523N", "S355J2+N"),
+    ("ST523U", "S355J0"),
+    ("FE360", "S235JR"),
+    ("FE360B", "S235JR"),
+    ("FE360C", "S235J0"),
+    ("FE360D", "S235J2"),
+    ("FE430", "S275JR"),
+    ("FE430B", "S275JR"),
+    ("FE430C", "S275J0"),
+    ("FE430D", "S275J2"),
+    ("FE510", "S355JR"),
+    ("FE510B", "S355JR"),
+    ("FE510C", "S355J0"),
+    ("FE510D", "S355J2"),
+];
+
+/// ASTM grades and their minimum yield strength in MPa
+const ASTM_GRADES: &[(&str, &str, f64)] = &[
+    ("A36", "A36", 250.0),
+    ("A992", "A992", 345.0),
+    ("A572GR50", "A572 Gr.50", 345.0),
+    ("A57250", "A572 Gr.50", 345.0),
+    ("A572GR55", "A572 Gr.55", 380.0),
+    ("A572GR60", "A572 Gr.60", 415.0),
+    ("A572GR65", "A572 Gr.65", 450.0),
+    ("A500GRB", "A500 Gr.B", 315.0),
+    ("A500GRC", "A500 Gr.C", 345.0),
+];
+
+/// Yield strength classes defined by EN 10025 and EN 10219
+const STRENGTHS: &[u32] = &[
+    185, 235, 275, 355, 420, 450, 460, 500, 550, 620, 690, 890, 960,
+];
+
+/// Quality classes defined by EN 10025 and EN 10219
+const QUALITIES: &[&str] = &[
+    "", "JR", "J0", "J2", "K2", "N", "NL", "M", "ML", "Q", "QL", "QL1", "J0W", "J2W", "K2W",
+];
+
+/// The minimum yield strengths of the grades of a standard by nominal thickness
+struct StrengthTable {
+    /// The upper limits of the thickness ranges in mm
+    bands: &'static [f64],
+    /// The yield strength class and the minimum yield strength in MPa for each
+    /// thickness range the standard covers
+    grades: &'static [(u32, &'static [f64])],
+}
+
+/// EN 10025-2 Table 7, also used for the weathering grades of EN 10025-5
+const EN_10025_2: StrengthTable = StrengthTable {
+    bands: &[16.0, 40.0, 63.0, 80.0, 100.0, 150.0, 200.0, 250.0],
+    grades: &[
+        (
+            185,
+            &[185.0, 175.0, 175.0, 175.0, 175.0, 165.0, 155.0, 145.0],
+        ),
+        (
+            235,
+            &[235.0, 225.0, 215.0, 215.0, 215.0, 195.0, 185.0, 175.0],
+        ),
+        (
+            275,
+            &[275.0, 265.0, 255.0, 245.0, 235.0, 225.0, 215.0, 205.0],
+        ),
+        (
+            355,
+            &[355.0, 345.0, 335.0, 325.0, 315.0, 295.0, 285.0, 275.0],
+        ),
+        (450, &[450.0, 430.0, 410.0, 390.0, 380.0, 380.0]),
+        (460, &[460.0, 440.0, 420.0, 400.0, 390.0, 390.0]),
+    ],
+};
+
+/// EN 10025-3, normalized grades
+const EN_10025_3: StrengthTable = StrengthTable {
+    bands: &[16.0, 40.0, 63.0, 80.0, 100.0, 150.0, 200.0, 250.0],
+    grades: &[
+        (
+            275,
+            &[275.0, 265.0, 255.0, 245.0, 235.0, 225.0, 215.0, 205.0],
+        ),
+        (
+            355,
+            &[355.0, 345.0, 335.0, 325.0, 315.0, 295.0, 285.0, 275.0],
+        ),
+        (
+            420,
+            &[420.0, 400.0, 390.0, 370.0, 360.0, 340.0, 330.0, 320.0],
+        ),
+        (
+            460,
+            &[460.0, 440.0, 430.0, 410.0, 400.0, 380.0, 370.0, 370.0],
+        ),
+    ],
+};
+
+/// EN 10025-4, thermomechanically rolled grades
+const EN_10025_4: StrengthTable = StrengthTable {
+    bands: &[16.0, 40.0, 63.0, 80.0, 100.0, 120.0],
+    grades: &[
+        (275, &[275.0, 265.0, 255.0, 245.0, 245.0, 240.0]),
+        (355, &[355.0, 345.0, 335.0, 325.0, 325.0, 320.0]),
+        (420, &[420.0, 400.0, 390.0, 380.0, 370.0, 365.0]),
+        (460, &[460.0, 440.0, 430.0, 410.0, 400.0, 385.0]),
+    ],
+};
+
+/// EN 10025-6, quenched and tempered grades
+const EN_10025_6: StrengthTable = StrengthTable {
+    bands: &[50.0, 100.0, 150.0],
+    grades: &[
+        (460, &[460.0, 440.0, 400.0]),
+        (500, &[500.0, 480.0, 440.0]),
+        (550, &[550.0, 530.0, 490.0]),
+        (620, &[620.0, 580.0, 560.0]),
+        (690, &[690.0, 650.0, 630.0]),
+        (890, &[890.0, 830.0]),
+        (960, &[960.0]),
+    ],
+};
+
+/// EN 10219, cold formed hollow sections up to 40 mm
+const EN_10219: StrengthTable = StrengthTable {
+    bands: &[16.0, 40.0],
+    grades: &[
+        (235, &[235.0, 225.0]),
+        (275, &[275.0, 265.0]),
+        (355, &[355.0, 345.0]),
+        (420, &[420.0, 400.0]),
+        (460, &[460.0, 440.0]),
+    ],
+};
+
+impl SteelGrade {
+    /// Returns the normalized name of the grade, e.g. `S355J2+N` or `S355J2H`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the density of the grade in kg/m³
+    pub fn density(&self) -> f64 {
+        STEEL_DENSITY
+    }
+
+    /// Returns the minimum yield strength for a given product thickness.
+    /// The strength of EN grades reduces with thickness as tabulated by their standard:
+    /// EN 10025-2 (Table 7) and EN 10025-5 for non-alloy and weathering grades, EN
+    /// 10025-3 for normalized, EN 10025-4 for thermomechanically rolled and EN 10025-6
+    /// for quenched and tempered grades, and EN 10219 for hollow sections. Products
+    /// thicker than the standard covers get the value of its thickest range. ASTM
+    /// grades and grades the standard does not tabulate keep their nominal strength.
+    /// # Arguments
+    /// * `thickness` - The nominal thickness of the product in mm
+    /// # Returns
+    /// The minimum yield strength in MPa
+    pub fn yield_strength_at(&self, thickness: f64) -> f64 {
+        let table = match self.table() {
+            Some(table) => table,
+            None => return self.yield_strength,
+        };
+        let strengths = match table
+            .grades
+            .iter()
+            .find(|(grade, _)| *grade as f64 == self.yield_strength)
+        {
+            Some((_, strengths)) => strengths,
+            None => return self.yield_strength,
+        };
+        let band = table
+            .bands
+            .iter()
+            .position(|limit| thickness <= *limit)
+            .unwrap_or(table.bands.len());
+        strengths[band.min(strengths.len() - 1)]
+    }
+
+    /// Returns the table of yield strengths by thickness of the standard of the grade
+    fn table(&self) -> Option<&'static StrengthTable> {
+        match (self.standard, self.quality.as_str()) {
+            (SteelStandard::Astm, _) => None,
+            (SteelStandard::En10219, _) => Some(&EN_10219),
+            (_, "N" | "NL") => Some(&EN_10025_3),
+            (_, "M" | "ML") => Some(&EN_10025_4),
+            (_, "Q" | "QL" | "QL1") => Some(&EN_10025_6),
+            _ => match self.delivery_condition {
+                Some(DeliveryCondition::Thermomechanical) => Some(&EN_10025_4),
+                Some(DeliveryCondition::QuenchedTempered) => Some(&EN_10025_6),
+                _ => Some(&EN_10025_2),
+            },
+        }
+    }
+
+    /// Parses an EN designation such as `S355J2+N`, `S235JRH` or the older `S355J2G3`
+    fn parse_en(designation: &str) -> Option<Self> {
+        let (grade, condition) = match designation.split_once('+') {
+            Some((grade, condition)) => (grade, Some(DeliveryCondition::from_str(condition).ok()?)),
+            None => (designation, None),
+        };
+        let rest = grade.strip_prefix('S')?;
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        let strength = rest[..digits].parse::<u32>().ok()?;
+        if !STRENGTHS.contains(&strength) {
+            return None;
+        }
+        let mut quality = &rest[digits..];
+        let mut delivery_condition = condition;
+        let mut legacy = false;
+        // EN 10025:1993 designations carry the deoxidation type, G3 meaning normalized
+        for (suffix, implied) in [
+            ("G2", None),
+            ("G3", Some(DeliveryCondition::Normalized)),
+            ("G4", None),
+        ] {
+            if let Some(stripped) = quality.strip_suffix(suffix) {
+                quality = stripped;
+                delivery_condition = delivery_condition.or(implied);
+                legacy = true;
+            }
+        }
+        let (quality, standard) = match quality.strip_suffix('H') {
+            Some(quality) => (quality, SteelStandard::En10219),
+            None => (quality, SteelStandard::En10025),
+        };
+        if !QUALITIES.contains(&quality) {
+            return None;
+        }
+        let hollow = match standard {
+            SteelStandard::En10219 => "H",
+            _ => "",
+        };
+        Some(Self {
+            standard,
+            yield_strength: strength as f64,
+            quality: quality.to_string(),
+            delivery_condition,
+            legacy,
+            name: format!(
+                "S{strength}{quality}{hollow}{}",
+                delivery_condition.map(|c| c.to_str()).unwrap_or_default()
+            ),
+        })
+    }
+}
+
+impl FromStr for SteelGrade {
+    type Err = ParseDstvError;
+
+    /// Parses a steel grade from a designation such as `S235JR`, `S355J2+N`, `S355J2H`,
+    /// legacy names like `RST37-2` or `Fe510` and common ASTM grades like `A992`
+    /// # Arguments
+    /// * `s` - The steel quality, e.g. from `Header::steel_quality`
+    /// # Returns
+    /// The parsed steel grade
+    /// # Error
+    /// * If the designation is not a known steel grade
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let designation = s
+            .trim()
+            .to_uppercase()
+            .replace(|c: char| c.is_whitespace(), "");
+        let key = designation
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>();
+        if let Some((_, name, strength)) = ASTM_GRADES.iter().find(|(k, _, _)| *k == key) {
+            return Ok(Self {
+                standard: SteelStandard::Astm,
+                yield_strength: *strength,
+                quality: String::new(),
+                delivery_condition: None,
+                legacy: false,
+                name: name.to_string(),
+            });
+        }
+        let successor = LEGACY_GRADES
+            .iter()
+            .find(|(k, _)| *k == key)
+            .and_then(|(_, successor)| Self::parse_en(successor));
+        if let Some(grade) = successor {
+            return Ok(Self {
+                legacy: true,
+                ..grade
+            });
+        }
+        Self::parse_en(&designation)
+            .ok_or_else(|| ParseDstvError::new(format!("Unknown steel grade: {}", s.trim())))
+    }
+}
+
+impl Header {
+    /// Parses the steel quality of the piece into a steel grade
+    /// # Returns
+    /// The steel grade, or an error if the steel quality is not a known grade
+    pub fn steel_grade(&self) -> Result<SteelGrade, ParseDstvError> {
+        SteelGrade::from_str(&self.steel_quality)
+    }
+
+    /// Returns the density of the material of the piece in kg/m³.
+    /// Falls back to the density of structural steel if the steel grade is unknown.
+    pub fn density(&self) -> f64 {
+        self.steel_grade()
+            .map(|grade| grade.density())
+            .unwrap_or(STEEL_DENSITY)
+    }
+}
@@ -0,0 +1,608 @@
+use crate::{
+    dialect::{HeaderLine, HeaderScanner},
+    dstv::{parse_dstv_element, END, HOLE_TYPE, START},
+    dstv_element::{Block, ParseDstvError},
+    prelude::{Dialect, Dstv, DstvElementType, ElementRegistry, Encoding, Header, PartFace},
+};
+use std::{fmt::Display, str::FromStr};
+
+/// The number of lines of the header
+pub(crate) const HEADER_LINES: usize = 24;
+/// The largest number of decimals used when writing a changed number
+const MAX_DECIMALS: usize = 6;
+/// Numbers closer than this are written the same, so they are not changed
+const NUMBER_TOLERANCE: f64 = 1e-9;
+
+/// A range of bytes within the text of a line
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// The first byte of the range
+    pub start: usize,
+    /// The byte after the range
+    pub end: usize,
+}
+
+/// The kinds of tokens on a line
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// A number, possibly followed by letters, e.g. `239.41o`
+    Number,
+    /// Any other text, e.g. a flange code or an identification
+    Word,
+}
+
+/// A run of text between whitespace on a line
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    /// The kind of the token
+    pub kind: TokenKind,
+    /// The position of the token on its line
+    pub span: Span,
+}
+
+/// The role of a line in the file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineKind {
+    /// A line holding only whitespace
+    Blank,
+    /// A comment, starting with `*`
+    Comment,
+    /// A line of the header
+    Header,
+    /// A line starting a block, e.g. `ST`, `BO` or `EN`
+    Block,
+    /// A line holding the values of an element
+    Data,
+}
+
+/// A line of the file, holding its text exactly as it was read
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxLine {
+    /// The role of the line
+    pub kind: LineKind,
+    /// The text of the line, without the line ending
+    pub text: String,
+    /// The line ending, `\n`, `\r\n` or empty for the last line
+    pub ending: String,
+    /// The tokens on the line
+    pub tokens: Vec<Token>,
+}
+
+impl SyntaxLine {
+    fn new(kind: LineKind, text: &str, ending: &str) -> Self {
+        let mut tokens = vec![];
+        let mut start = None;
+        for (i, c) in text
+            .char_indices()
+            .chain(std::iter::once((text.len(), ' ')))
+        {
+            match (c.is_whitespace(), start) {
+                (true, Some(first)) => {
+                    let span = Span {
+                        start: first,
+                        end: i,
+                    };
+                    let kind = match number(&text[first..i]) {
+                        Some(_) => TokenKind::Number,
+                        None => TokenKind::Word,
+                    };
+                    tokens.push(Token { kind, span });
+                    start = None;
+                }
+                (false, None) => start = Some(i),
+                _ => {}
+            }
+        }
+        Self {
+            kind,
+            text: text.to_string(),
+            ending: ending.to_string(),
+            tokens,
+        }
+    }
+
+    /// Returns the text of a token on the line
+    pub fn token(&self, token: &Token) -> &str {
+        &self.text[token.span.start..token.span.end]
+    }
+
+    /// Returns the text of all tokens on the line
+    pub fn words(&self) -> Vec<&str> {
+        self.tokens.iter().map(|token| self.token(token)).collect()
+    }
+}
+
+/// A header or an element of the file with the lines it was read from
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxNode {
+    /// The code of the block, e.g. `BO`
+    pub code: String,
+    /// The index of the line starting the block. Holes after the first one in a `BO`
+    /// block share the line of the block.
+    pub block: usize,
+    /// The indices of the lines holding the values of the element
+    pub lines: Vec<usize>,
+    /// The element read from the lines, or `None` for invalid or skipped blocks, which
+    /// are kept as they are
+    pub element: Option<DstvElementType>,
+    /// The error reading an invalid block, naming its line
+    pub error: Option<ParseDstvError>,
+}
+
+/// A lossless representation of a DSTV file. Every line is kept with its comments,
+/// whitespace and number formatting, so writing the tree gives back the exact file.
+/// Changes to the typed model are written back with `apply`, which only touches the
+/// values that changed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxTree {
+    lines: Vec<SyntaxLine>,
+    header: Vec<usize>,
+    nodes: Vec<SyntaxNode>,
+    dialect: Dialect,
+    registry: ElementRegistry,
+    pub(crate) encoding: Encoding,
+}
+
+/// Checks whether a line is a comment, which starts with `*`
+pub(crate) fn is_comment(text: &str) -> bool {
+    text.trim().starts_with('*')
+}
+
+/// Checks whether a line holds the values of an element, which are indented by at
+/// least two characters. Other lines start a block.
+pub(crate) fn is_data_line(text: &str) -> bool {
+    text.get(..2).map_or(false, |s| s.trim().is_empty())
+}
+
+/// Reads a number token, ignoring the letters DSTV allows after a value
+fn number(token: &str) -> Option<f64> {
+    token
+        .trim_end_matches(|c: char| c.is_ascii_alphabetic())
+        .parse()
+        .ok()
+}
+
+/// Checks whether two tokens hold the same value
+fn same(old: &str, new: &str) -> bool {
+    match (number(old), number(new)) {
+        (Some(a), Some(b)) => (a - b).abs() <= NUMBER_TOLERANCE,
+        _ => old == new,
+    }
+}
+
+/// Writes a number like another one, with at least as many decimals and the same
+/// letters after it
+fn format_like(original: &str, value: f64) -> String {
+    let digits = original.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let suffix = &original[digits.len()..];
+    let decimals = digits.split_once('.').map_or(0, |(_, d)| d.len());
+    // adding zero turns a negative zero into a positive one
+    let value = value + 0.0;
+    let text = (decimals..=MAX_DECIMALS.max(decimals))
+        .map(|decimals| format!("{value:.decimals$}"))
+        .find(|text| same(text, &value.to_string()))
+        .unwrap_or_else(|| value.to_string());
+    text + suffix
+}
+
+/// Replaces a token in the text of a line. Spaces in front of the token are added or
+/// removed, so the end of the token stays in the same column when possible.
+fn replace_token(text: &mut String, span: Span, value: &str) {
+    let before = &text[..span.start];
+    let spaces = before.len() - before.trim_end_matches(' ').len();
+    let whitespace = before.len() - before.trim_end().len();
+    // keep a separator, or the indent marking a data line for the first token
+    let keep = match before.trim().is_empty() {
+        true => 2,
+        false => 1,
+    };
+    let (old, new) = (span.end - span.start, value.len());
+    let mut start = span.start;
+    let mut value = value.to_string();
+    if spaces == whitespace && new < old {
+        value = " ".repeat(old - new) + &value;
+    } else if spaces == whitespace && new > old {
+        start -= (new - old).min(spaces.saturating_sub(keep));
+    }
+    text.replace_range(start..span.end, &value);
+}
+
+/// Merges the new text of a line into an existing line, changing only the tokens
+/// that hold another value
+/// # Arguments
+/// * `line` - The existing line
+/// * `text` - The new text of the line
+/// # Returns
+/// The merged text of the line, or the new text when the tokens cannot be matched
+fn merge_line(line: &SyntaxLine, text: &str) -> String {
+    let old = line.words();
+    let mut new = text.split_whitespace().collect::<Vec<_>>();
+    // the flange code in front of the values is optional and defaults to the front
+    let is_face = |token: Option<&&str>| token.map_or(false, |t| PartFace::from_str(t).is_ok());
+    if line.kind == LineKind::Data
+        && is_face(new.first())
+        && !is_face(old.first())
+        && new.first() == Some(&PartFace::Front.code())
+    {
+        new.remove(0);
+    }
+    if old.len() == new.len() && old.iter().zip(&new).all(|(a, b)| same(a, b)) {
+        return line.text.clone();
+    }
+    // trailing zeros of the old line may be left out of the new line
+    if old.len() > new.len() && old[new.len()..].iter().any(|t| number(t) != Some(0.0)) {
+        return text.to_string();
+    }
+    let mut merged = line.text.clone();
+    for (token, value) in line.tokens.iter().zip(&new).rev() {
+        let original = line.token(token);
+        if same(original, value) {
+            continue;
+        }
+        let value = match (number(original), number(value)) {
+            (Some(_), Some(number)) => format_like(original, number),
+            _ => value.to_string(),
+        };
+        replace_token(&mut merged, token.span, &value);
+    }
+    for value in new.iter().skip(old.len()) {
+        merged = format!("{} {value}", merged.trim_end());
+    }
+    merged
+}
+
+impl SyntaxTree {
+    /// Reads the structure of a DSTV file. Reading never fails, lines that cannot be
+    /// understood are kept as they are.
+    /// # Arguments
+    /// * `source` - The contents of the file
+    pub fn parse(source: &str) -> Self {
+        Self::parse_with_registry(source, &ElementRegistry::default())
+    }
+
+    /// Reads the structure of a DSTV file, reading the blocks beyond the DSTV standard
+    /// with the parsers of a registry
+    /// # Arguments
+    /// * `source` - The contents of the file
+    /// * `registry` - The registry of the parsers, which is kept for `apply`
+    pub fn parse_with_registry(source: &str, registry: &ElementRegistry) -> Self {
+        let mut lines = source
+            .split_inclusive('\n')
+            .map(|line| {
+                let text = line.trim_end_matches('\n').trim_end_matches('\r');
+                let kind = match text.trim().is_empty() {
+                    true => LineKind::Blank,
+                    false if is_comment(text) => LineKind::Comment,
+                    false => LineKind::Data,
+                };
+                SyntaxLine::new(kind, text, &line[text.len()..])
+            })
+            .collect::<Vec<_>>();
+
+        // the header follows `ST`, skipping comments, and ends after the text lines or at
+        // the first block
+        let mut scanner = HeaderScanner::default();
+        let mut header = vec![];
+        let mut index = 0;
+        while index < lines.len() && !scanner.is_complete() {
+            let line = &mut lines[index];
+            match scanner.next(&line.text) {
+                HeaderLine::End => break,
+                HeaderLine::Skip if line.text.trim() == START => line.kind = LineKind::Block,
+                HeaderLine::Skip => {}
+                HeaderLine::Header => {
+                    line.kind = LineKind::Header;
+                    header.push(index);
+                }
+            }
+            index += 1;
+        }
+
+        let mut nodes: Vec<SyntaxNode> = vec![];
+        for (i, line) in lines.iter_mut().enumerate().skip(index) {
+            if line.kind != LineKind::Data {
+                continue;
+            }
+            if line.text.trim() == END || !is_data_line(&line.text) {
+                line.kind = LineKind::Block;
+                if line.text.trim() != END {
+                    nodes.push(SyntaxNode {
+                        code: line.text.trim().to_string(),
+                        block: i,
+                        lines: vec![],
+                        element: None,
+                        error: None,
+                    });
+                }
+                continue;
+            }
+            match nodes.last_mut() {
+                // every hole of a `BO` block is an element of its own
+                Some(node) if node.code == HOLE_TYPE && node.lines.len() == 1 => {
+                    let block = node.block;
+                    nodes.push(SyntaxNode {
+                        code: HOLE_TYPE.to_string(),
+                        block,
+                        lines: vec![i],
+                        element: None,
+                        error: None,
+                    });
+                }
+                Some(node) => node.lines.push(i),
+                None => {}
+            }
+        }
+        for node in nodes.iter_mut().filter(|node| !node.lines.is_empty()) {
+            let texts = node
+                .lines
+                .iter()
+                .map(|i| lines[*i].text.as_str())
+                .collect::<Vec<_>>();
+            let numbers = node.lines.iter().map(|i| i + 1).collect::<Vec<_>>();
+            let block = Block::new(&node.code, &texts).with_line_numbers(&numbers);
+            match parse_dstv_element(&block, registry) {
+                Ok(element) => node.element = element,
+                Err(e) => node.error = Some(e),
+            }
+        }
+        Self {
+            lines,
+            header,
+            nodes,
+            dialect: scanner.dialect(),
+            registry: registry.clone(),
+            encoding: Encoding::Utf8,
+        }
+    }
+
+    /// Returns all lines of the file
+    pub fn lines(&self) -> &[SyntaxLine] {
+        &self.lines
+    }
+
+    /// Returns the indices of the lines of the header
+    pub fn header_lines(&self) -> &[usize] {
+        &self.header
+    }
+
+    /// Returns the blocks of the file after the header, in the order of the file
+    pub fn nodes(&self) -> &[SyntaxNode] {
+        &self.nodes
+    }
+
+    /// Returns the variant of the DSTV format the file was written in
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// Returns the encoding the file was read in, which `to_bytes` writes it in.
+    /// Trees parsed from text are UTF-8.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Returns the comment lines of the file
+    pub fn comments(&self) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter(|line| line.kind == LineKind::Comment)
+            .map(|line| line.text.as_str())
+            .collect()
+    }
+
+    /// Reads the header from the header lines
+    pub fn header(&self) -> Result<Header, ParseDstvError> {
+        let lines = self
+            .header
+            .iter()
+            .map(|i| self.lines[*i].text.trim())
+            .collect::<Vec<_>>();
+        Header::from_lines(lines)
+    }
+
+    /// Returns the line ending used by the file
+    fn ending(&self) -> &str {
+        self.lines
+            .iter()
+            .map(|line| line.ending.as_str())
+            .find(|ending| !ending.is_empty())
+            .unwrap_or("\n")
+    }
+
+    /// Writes the changes of a part back into the file, keeping comments, unknown
+    /// blocks and the formatting of everything that did not change.
+    /// Elements are matched in order: changed elements are rewritten value by value,
+    /// removed elements are taken out and added elements are written after the element
+    /// before them.
+    /// # Arguments
+    /// * `dstv` - The changed part, usually read from this tree
+    pub fn apply(&mut self, dstv: &Dstv) {
+        if self.lines.is_empty() {
+            self.reparse(&dstv.to_dstv());
+            return;
+        }
+        let ending = self.ending().to_string();
+        let mut edits = self
+            .lines
+            .iter()
+            .map(|line| vec![line.text.clone()])
+            .collect::<Vec<_>>();
+
+        if self.header().ok().as_ref() != Some(&dstv.header) {
+            let text = dstv.header.to_dstv();
+            for (i, new) in text.lines().enumerate() {
+                match self.header.get(i) {
+                    Some(index) => edits[*index] = vec![merge_line(&self.lines[*index], new)],
+                    None => match self.header.last() {
+                        Some(last) => edits[*last].push(new.to_string()),
+                        None => edits[0].insert(i, new.to_string()),
+                    },
+                }
+            }
+        }
+
+        let old = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, node)| node.element.as_ref().map(|element| (i, element)))
+            .collect::<Vec<_>>();
+        let new = &dstv.elements;
+        let prefix = old
+            .iter()
+            .zip(new)
+            .take_while(|((_, a), b)| *a == *b)
+            .count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|((_, a), b)| *a == *b)
+            .count();
+        let old_changed = &old[prefix..old.len() - suffix];
+        let new_changed = &new[prefix..new.len() - suffix];
+
+        let mut removed = vec![];
+        // the line after which added elements are written
+        let mut anchor = match prefix {
+            0 => self.header.last().copied(),
+            _ => self.nodes[old[prefix - 1].0].lines.last().copied(),
+        };
+        let mut added = vec![];
+        for i in 0..old_changed.len().max(new_changed.len()) {
+            match (old_changed.get(i), new_changed.get(i)) {
+                (Some((node, a)), Some(b))
+                    if std::mem::discriminant(*a) == std::mem::discriminant(b) =>
+                {
+                    let node = &self.nodes[*node];
+                    let text = b.to_dstv();
+                    let values = text.lines().skip(1).collect::<Vec<_>>();
+                    for (j, line) in node.lines.iter().enumerate() {
+                        edits[*line] = match values.get(j) {
+                            Some(value) => vec![merge_line(&self.lines[*line], value)],
+                            None => vec![],
+                        };
+                    }
+                    let last = *node.lines.last().unwrap_or(&node.block);
+                    let extra = values.iter().skip(node.lines.len()).map(|v| v.to_string());
+                    edits[last].extend(extra);
+                    anchor = Some(last);
+                }
+                (Some((node, _)), b) => {
+                    removed.push(*node);
+                    anchor = self.nodes[*node].lines.last().copied();
+                    added.extend(b.map(|b| (anchor, b)));
+                }
+                (None, Some(b)) => added.push((anchor, b)),
+                (None, None) => {}
+            }
+        }
+        for node in &removed {
+            let node = &self.nodes[*node];
+            for line in &node.lines {
+                edits[*line].clear();
+            }
+            // the block line goes when no element is left in the block
+            let shared = self
+                .nodes
+                .iter()
+                .enumerate()
+                .any(|(i, other)| other.block == node.block && !removed.contains(&i));
+            if !shared {
+                edits[node.block].clear();
+            }
+        }
+        for (anchor, element) in added {
+            let lines = element
+                .to_dstv()
+                .lines()
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            match anchor {
+                Some(anchor) => edits[anchor].extend(lines),
+                // without a header or elements before it, the element goes before `EN`
+                None => {
+                    let end = self
+                        .lines
+                        .iter()
+                        .rposition(|line| line.text.trim() == END)
+                        .unwrap_or(self.lines.len().saturating_sub(1));
+                    match edits.get_mut(end) {
+                        Some(edit) => {
+                            let last = edit.pop();
+                            edit.extend(lines);
+                            edit.extend(last);
+                        }
+                        None => edits.push(lines),
+                    }
+                }
+            }
+        }
+
+        let mut source = String::new();
+        for (edit, line) in edits.iter().zip(&self.lines) {
+            for (i, text) in edit.iter().enumerate() {
+                source.push_str(text);
+                // the original ending goes after the last line of an edit, so the file
+                // keeps ending without a newline if it did
+                match i + 1 == edit.len() {
+                    true => source.push_str(&line.ending),
+                    false => source.push_str(&ending),
+                }
+            }
+        }
+        self.reparse(&source);
+    }
+
+    /// Reads the structure of the tree again from its edited source, keeping the
+    /// registry and the encoding it was read with
+    fn reparse(&mut self, source: &str) {
+        let encoding = self.encoding;
+        *self = Self::parse_with_registry(source, &self.registry);
+        self.encoding = encoding;
+    }
+}
+
+impl Display for SyntaxTree {
+    /// Writes the file exactly as it was read, including any applied changes
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for line in &self.lines {
+            write!(f, "{}{}", line.text, line.ending)?;
+        }
+        Ok(())
+    }
+}
+
+impl Dstv {
+    /// Reads a part from the syntax tree of a file. Invalid blocks are skipped, their
+    /// errors are kept in `SyntaxNode::error`.
+    /// # Arguments
+    /// * `tree` - The syntax tree
+    /// # Error
+    /// * If the header is invalid
+    pub fn from_syntax(tree: &SyntaxTree) -> Result<Self, ParseDstvError> {
+        let header = tree
+            .header()
+            .map_err(|e| ParseDstvError::from_err("Invalid Header", e))?;
+        let elements = tree
+            .nodes
+            .iter()
+            .filter_map(|node| node.element.clone())
+            .collect();
+        Ok(Self { header, elements })
+    }
+
+    /// Reads a part from the syntax tree of a file, failing on the first invalid block
+    /// instead of skipping it
+    /// # Arguments
+    /// * `tree` - The syntax tree
+    /// # Error
+    /// * If the header is invalid
+    /// * If a block is invalid, naming the block and its line
+    pub fn from_syntax_strict(tree: &SyntaxTree) -> Result<Self, ParseDstvError> {
+        match tree.nodes.iter().find_map(|node| node.error.clone()) {
+            Some(error) => Err(error),
+            None => Self::from_syntax(tree),
+        }
+    }
+}
@@ -0,0 +1,458 @@
+use crate::{
+    dstv_element::ParseDstvError,
+    geometry::EPSILON,
+    prelude::{
+        BorderPoint, CodeProfile, Degrees, Dstv, DstvElementType, Header, Millimetres, PartFace,
+    },
+};
+
+/// The planes a part can be mirrored in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MirrorPlane {
+    /// The plane across the middle of the length of the part, swapping its start and end
+    EndToEnd,
+    /// The horizontal plane through the axis of the part, swapping the top and the bottom
+    /// faces. Plates are mirrored about the axis along their length.
+    Horizontal,
+    /// The vertical plane through the axis of the part, swapping the front and the back
+    /// faces. Mirroring a plate in this plane does not change it. Channels and angles
+    /// cannot be mirrored in this plane, as their flanges would point the other way.
+    Vertical,
+}
+
+/// The ends of a part
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PartEnd {
+    /// The end at x = 0
+    Start,
+    /// The end at x = length
+    End,
+}
+
+/// The transformations that map the coordinates of a part
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Transformation {
+    /// A mirror in one of the planes of the part
+    Mirror(MirrorPlane),
+    /// A mirror in the plane bisecting the legs of an equal angle, swapping its legs
+    SwapLegs,
+}
+
+/// Describes how the coordinates of a face map onto the transformed part
+struct FaceMapping {
+    /// The face the coordinates end up on
+    face: PartFace,
+    /// The length to reflect x in, if x is reflected
+    length: Option<Millimetres>,
+    /// The width to reflect y in, if y is reflected
+    width: Option<Millimetres>,
+}
+
+impl FaceMapping {
+    fn point(&self, x: Millimetres, y: Millimetres) -> (Millimetres, Millimetres) {
+        (
+            self.length.map(|length| length - x).unwrap_or(x),
+            self.width.map(|width| width - y).unwrap_or(y),
+        )
+    }
+
+    /// Returns true if the mapping turns the face over, reversing the direction of arcs
+    fn is_reflection(&self) -> bool {
+        self.length.is_some() != self.width.is_some()
+    }
+
+    /// Maps an angle in degrees, measured from the x axis
+    fn angle(&self, angle: Degrees) -> Degrees {
+        let angle = *angle;
+        let angle = match (self.length.is_some(), self.width.is_some()) {
+            (false, false) => angle,
+            (true, false) => 180.0 - angle,
+            (false, true) => -angle,
+            (true, true) => angle + 180.0,
+        };
+        Degrees(angle.rem_euclid(360.0))
+    }
+}
+
+impl Header {
+    /// Returns how the coordinates on a face map onto the transformed part
+    fn face_mapping(
+        &self,
+        transformation: Transformation,
+        face: &PartFace,
+    ) -> Result<FaceMapping, ParseDstvError> {
+        use Transformation::{Mirror, SwapLegs};
+        let profile = &self.code_profile;
+        let (face, length, width) = match (transformation, profile, face) {
+            (Mirror(MirrorPlane::EndToEnd), _, face) => (face.clone(), Some(self.length), None),
+            (Mirror(MirrorPlane::Horizontal), CodeProfile::B, face) => {
+                (face.clone(), None, Some(Millimetres(self.face_width(face))))
+            }
+            (Mirror(MirrorPlane::Horizontal), _, PartFace::Top) => (PartFace::Bottom, None, None),
+            (Mirror(MirrorPlane::Horizontal), _, PartFace::Bottom) => (PartFace::Top, None, None),
+            (Mirror(MirrorPlane::Horizontal), _, face) => {
+                (face.clone(), None, Some(Millimetres(self.face_width(face))))
+            }
+            (Mirror(MirrorPlane::Vertical), _, PartFace::Front)
+                if profile.faces().contains(&PartFace::Behind) =>
+            {
+                (PartFace::Behind, None, None)
+            }
+            (Mirror(MirrorPlane::Vertical), _, PartFace::Behind) => (PartFace::Front, None, None),
+            (Mirror(MirrorPlane::Vertical), _, PartFace::Front) => (PartFace::Front, None, None),
+            (Mirror(MirrorPlane::Vertical), _, face) => {
+                (face.clone(), None, Some(Millimetres(self.face_width(face))))
+            }
+            // both legs are measured from the heel, so their coordinates carry over
+            (SwapLegs, _, PartFace::Front) => (PartFace::Bottom, None, None),
+            (SwapLegs, _, PartFace::Bottom) => (PartFace::Front, None, None),
+            (SwapLegs, _, face) => (face.clone(), None, None),
+        };
+        if !profile.faces().contains(&face) {
+            let action = match transformation {
+                Mirror(plane) => format!("mirror {plane:?}"),
+                SwapLegs => "swap the legs".to_string(),
+            };
+            return Err(ParseDstvError::new(format!(
+                "Cannot {action}: face `{}` is not available for {}",
+                face.code(),
+                profile.to_str()
+            )));
+        }
+        Ok(FaceMapping {
+            face,
+            length,
+            width,
+        })
+    }
+}
+
+/// Maps the points of a contour, reversing the direction of arcs on reflected faces
+fn map_contour<F>(contour: &[BorderPoint], mapping: F) -> Result<Vec<BorderPoint>, ParseDstvError>
+where
+    F: Fn(&PartFace) -> Result<FaceMapping, ParseDstvError>,
+{
+    contour
+        .iter()
+        .map(|point| {
+            let mapping = mapping(&point.fl_code)?;
+            let (x_coord, y_coord) = mapping.point(point.x_coord, point.y_coord);
+            let radius = match mapping.is_reflection() {
+                true => -point.radius,
+                false => point.radius,
+            };
+            Ok(BorderPoint {
+                fl_code: mapping.face,
+                x_coord,
+                y_coord,
+                radius,
+                bevel: point.bevel,
+            })
+        })
+        .collect()
+}
+
+impl Dstv {
+    /// Mirrors the part in a plane, e.g. to create the right-hand version of a
+    /// left-hand part. Holes, slots, contours and marks move along with their faces.
+    /// Marks keep their angle, so they stay readable. Custom and unknown blocks are kept
+    /// as they are.
+    /// # Arguments
+    /// * `plane` - The plane to mirror the part in
+    /// # Returns
+    /// The mirrored part
+    /// # Error
+    /// * If an element ends up on a face the profile does not have, e.g. when mirroring
+    ///   the leg of an angle to the top
+    /// * If a channel or an angle is mirrored in the vertical plane
+    pub fn mirror(&self, plane: MirrorPlane) -> Result<Self, ParseDstvError> {
+        let profile = &self.header.code_profile;
+        match (plane, profile) {
+            (MirrorPlane::Vertical, CodeProfile::B) => Ok(self.clone()),
+            (MirrorPlane::Vertical, CodeProfile::U | CodeProfile::C | CodeProfile::L) => {
+                Err(ParseDstvError::new(format!(
+                    "Cannot mirror Vertical: the flanges of {} would point the other way",
+                    profile.to_str()
+                )))
+            }
+            _ => self.transform(Transformation::Mirror(plane)),
+        }
+    }
+
+    /// Maps the elements and the header of the part onto the transformed part
+    fn transform(&self, transformation: Transformation) -> Result<Self, ParseDstvError> {
+        let header = &self.header;
+        let mapping = |face: &PartFace| header.face_mapping(transformation, face);
+        let elements = self
+            .elements
+            .iter()
+            .map(|element| {
+                Ok(match element {
+                    DstvElementType::OuterBorder(e) => {
+                        let mut e = e.clone();
+                        e.contour = map_contour(&e.contour, mapping)?;
+                        DstvElementType::OuterBorder(e)
+                    }
+                    DstvElementType::InnerBorder(e) => {
+                        let mut e = e.clone();
+                        e.contour = map_contour(&e.contour, mapping)?;
+                        DstvElementType::InnerBorder(e)
+                    }
+                    DstvElementType::Hole(e) => {
+                        let mut e = e.clone();
+                        let m = mapping(&e.fl_code)?;
+                        let (x, y) = m.point(e.x_coord, e.y_coord);
+                        e.x_coord = x;
+                        e.y_coord = y;
+                        e.fl_code = m.face;
+                        DstvElementType::Hole(e)
+                    }
+                    DstvElementType::Slot(e) => {
+                        let mut e = e.clone();
+                        let m = mapping(&e.fl_code)?;
+                        // a reflection puts the width of the slot on the other side of
+                        // its reference point, so the reference moves across the width
+                        let (reference_x, reference_y) = match m.is_reflection() {
+                            true => {
+                                let (sin, cos) = e.angle.to_radians().sin_cos();
+                                (
+                                    e.x_coord - e.slot_width * sin,
+                                    e.y_coord + e.slot_width * cos,
+                                )
+                            }
+                            false => (e.x_coord, e.y_coord),
+                        };
+                        let (x, y) = m.point(reference_x, reference_y);
+                        e.x_coord = x;
+                        e.y_coord = y;
+                        e.angle = m.angle(e.angle);
+                        e.fl_code = m.face;
+                        DstvElementType::Slot(e)
+                    }
+                    DstvElementType::Numeration(e) => {
+                        let mut e = e.clone();
+                        let m = mapping(&e.fl_code)?;
+                        let (x, y) = m.point(e.x_coord, e.y_coord);
+                        e.x_coord = x;
+                        e.y_coord = y;
+                        e.fl_code = m.face;
+                        DstvElementType::Numeration(e)
+                    }
+                    DstvElementType::Bend(e) => {
+                        let mut e = e.clone();
+                        let m = mapping(&e.fl_code)?;
+                        let (x, y) = m.point(e.origin_x, e.origin_y);
+                        e.origin_x = x;
+                        e.origin_y = y;
+                        let (x, y) = m.point(e.finish_x, e.finish_y);
+                        e.finish_x = x;
+                        e.finish_y = y;
+                        e.fl_code = m.face;
+                        DstvElementType::Bend(e)
+                    }
+                    DstvElementType::Cut(e) => {
+                        let mut e = e.clone();
+                        match transformation {
+                            Transformation::Mirror(MirrorPlane::EndToEnd) => {
+                                e.sp_point_x = header.length - e.sp_point_x;
+                                e.nor_vec_x = -e.nor_vec_x;
+                            }
+                            Transformation::Mirror(MirrorPlane::Horizontal) => {
+                                e.sp_point_y =
+                                    Millimetres(header.face_width(&PartFace::Front)) - e.sp_point_y;
+                                e.nor_vec_y = -e.nor_vec_y;
+                            }
+                            Transformation::Mirror(MirrorPlane::Vertical) => {
+                                e.sp_point_z = header.flange_width - e.sp_point_z;
+                                e.nor_vec_z = -e.nor_vec_z;
+                            }
+                            Transformation::SwapLegs => {
+                                std::mem::swap(&mut e.sp_point_y, &mut e.sp_point_z);
+                                std::mem::swap(&mut e.nor_vec_y, &mut e.nor_vec_z);
+                            }
+                        }
+                        DstvElementType::Cut(e)
+                    }
+                    DstvElementType::Custom(_) | DstvElementType::Unknown(_) => element.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, ParseDstvError>>()?;
+        let mut header = header.clone();
+        match transformation {
+            Transformation::Mirror(MirrorPlane::EndToEnd) => {
+                std::mem::swap(&mut header.web_start_cut, &mut header.web_end_cut);
+                std::mem::swap(&mut header.flange_start_cut, &mut header.flange_end_cut);
+            }
+            Transformation::Mirror(MirrorPlane::Horizontal) => {
+                header.web_start_cut = -header.web_start_cut;
+                header.web_end_cut = -header.web_end_cut;
+            }
+            Transformation::Mirror(MirrorPlane::Vertical) => {
+                header.flange_start_cut = -header.flange_start_cut;
+                header.flange_end_cut = -header.flange_end_cut;
+            }
+            Transformation::SwapLegs => {
+                std::mem::swap(&mut header.profile_height, &mut header.flange_width);
+                std::mem::swap(&mut header.web_thickness, &mut header.flange_thickness);
+                std::mem::swap(&mut header.web_start_cut, &mut header.flange_start_cut);
+                std::mem::swap(&mut header.web_end_cut, &mut header.flange_end_cut);
+            }
+        }
+        Ok(Self { header, elements })
+    }
+
+    /// Reverses the direction of the part by turning it end to end, e.g. for a machine
+    /// that loads from the other side. Plates and channels are turned about the normal of
+    /// their web, equal angles about the bisector of their legs and other profiles about
+    /// their vertical axis. The start and end cuts in the header are swapped.
+    /// # Returns
+    /// The reversed part
+    /// # Error
+    /// * If the part is an angle with unequal legs, which cannot be turned end to end
+    ///   without its legs changing places
+    pub fn reverse(&self) -> Result<Self, ParseDstvError> {
+        let header = &self.header;
+        let turned = self.mirror(MirrorPlane::EndToEnd)?;
+        match header.code_profile {
+            CodeProfile::B | CodeProfile::U | CodeProfile::C => {
+                turned.mirror(MirrorPlane::Horizontal)
+            }
+            CodeProfile::L
+                if header.profile_height != header.flange_width
+                    || header.web_thickness != header.flange_thickness =>
+            {
+                Err(ParseDstvError::new(format!(
+                    "Cannot reverse {}: its legs are not equal",
+                    header.profile.trim()
+                )))
+            }
+            CodeProfile::L => turned.transform(Transformation::SwapLegs),
+            _ => turned.mirror(MirrorPlane::Vertical),
+        }
+    }
+
+    /// Returns where the end region of the part begins: the x coordinate past which the
+    /// outer contours no longer run along both of their edges, e.g. the start of a mitre
+    /// or of a notch at the end. A square end begins at the length of the part.
+    fn end_region(&self) -> f64 {
+        let edge_end = |contour: &[BorderPoint], y: f64| {
+            contour
+                .iter()
+                .filter(|p| (*p.y_coord - y).abs() < EPSILON)
+                .map(|p| *p.x_coord)
+                .fold(f64::NEG_INFINITY, f64::max)
+        };
+        self.elements
+            .iter()
+            .filter_map(|element| match element {
+                DstvElementType::OuterBorder(e) if !e.contour.is_empty() => Some(&e.contour),
+                _ => None,
+            })
+            .map(|contour| {
+                let ys = contour.iter().map(|p| *p.y_coord);
+                let bottom = ys.clone().fold(f64::INFINITY, f64::min);
+                let top = ys.fold(f64::NEG_INFINITY, f64::max);
+                edge_end(contour, bottom).min(edge_end(contour, top))
+            })
+            .fold(*self.header.length, f64::min)
+    }
+
+    /// Trims or extends the part to a new length. Material is removed or added at one
+    /// end: the end region, everything past the last square section, moves along with
+    /// it, so that mitres, notches and the holes and cuts in them keep their shape.
+    /// Everything else stays in place.
+    /// # Arguments
+    /// * `length` - The new length of the part in mm
+    /// * `end` - The end to trim or extend
+    /// # Returns
+    /// The part with the new length
+    /// # Error
+    /// * If the new length is not positive
+    /// * If trimming would cut through a hole, slot, mark or contour point
+    pub fn adjust_length(&self, length: f64, end: PartEnd) -> Result<Self, ParseDstvError> {
+        if end == PartEnd::Start {
+            return self
+                .mirror(MirrorPlane::EndToEnd)?
+                .adjust_length(length, PartEnd::End)?
+                .mirror(MirrorPlane::EndToEnd);
+        }
+        if length.is_nan() || length <= 0.0 {
+            return Err(ParseDstvError::new("The length of a part must be positive"));
+        }
+        let old_length = self.header.length;
+        let delta = length - *old_length;
+        let region = self.end_region();
+        let moves = |x: Millimetres| *x >= region - EPSILON;
+        let shift = |x: Millimetres| match moves(x) {
+            true => Millimetres(*x + delta),
+            false => x,
+        };
+        let mut elements = self.elements.clone();
+        for (index, element) in elements.iter_mut().enumerate() {
+            let (moved, reach) = match element {
+                DstvElementType::OuterBorder(e) => {
+                    e.contour
+                        .iter_mut()
+                        .for_each(|p| p.x_coord = shift(p.x_coord));
+                    let reach = e.contour.iter().map(|p| *p.x_coord).fold(0.0, f64::max);
+                    (true, reach)
+                }
+                DstvElementType::InnerBorder(e) => {
+                    let moved = e.contour.iter().all(|p| moves(p.x_coord));
+                    if moved {
+                        e.contour
+                            .iter_mut()
+                            .for_each(|p| p.x_coord = shift(p.x_coord));
+                    }
+                    let reach = e.contour.iter().map(|p| *p.x_coord).fold(0.0, f64::max);
+                    (moved, reach)
+                }
+                DstvElementType::Hole(e) => {
+                    let moved = moves(e.x_coord);
+                    e.x_coord = shift(e.x_coord);
+                    (moved, *e.x_coord + *e.diameter / 2.0)
+                }
+                DstvElementType::Slot(e) => {
+                    let moved = moves(e.x_coord);
+                    e.x_coord = shift(e.x_coord);
+                    let (sin, cos) = e.angle.to_radians().sin_cos();
+                    let (along, across) = (*e.slot_length * cos, -*e.slot_width * sin);
+                    let far = along.max(across).max(along + across).max(0.0);
+                    (moved, *e.x_coord + far + *e.diameter / 2.0)
+                }
+                DstvElementType::Numeration(e) => {
+                    let moved = moves(e.x_coord);
+                    e.x_coord = shift(e.x_coord);
+                    (moved, *e.x_coord)
+                }
+                DstvElementType::Bend(e) => {
+                    e.origin_x = shift(e.origin_x);
+                    e.finish_x = shift(e.finish_x);
+                    (true, e.origin_x.max(*e.finish_x))
+                }
+                DstvElementType::Cut(e) => {
+                    let moved = moves(e.sp_point_x);
+                    e.sp_point_x = shift(e.sp_point_x);
+                    (moved, *e.sp_point_x)
+                }
+                DstvElementType::Custom(_) | DstvElementType::Unknown(_) => (true, 0.0),
+            };
+            // when trimming, the end region must not pass over what stays in place
+            let limit = match moved || delta >= 0.0 {
+                true => length,
+                false => region + delta,
+            };
+            if reach > limit + EPSILON {
+                return Err(ParseDstvError::new(format!(
+                    "Cannot trim to {length}: element {index} reaches up to {reach}"
+                )));
+            }
+        }
+        let mut header = self.header.clone();
+        header.saw_length = header
+            .saw_length
+            .map(|saw_length| saw_length + length - old_length);
+        header.length = Millimetres(length);
+        Ok(Self { header, elements })
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, AddAssign, Deref, Div, Mul, Neg, Sub, SubAssign},
+};
+
+/// Millimetres in an inch
+const MM_PER_INCH: f64 = 25.4;
+/// Millimetres in a foot
+const MM_PER_FOOT: f64 = 304.8;
+/// Kilograms in a pound
+const KG_PER_POUND: f64 = 0.453_592_37;
+
+/// Defines a quantity stored as a `f64` in the unit DSTV files use. A quantity
+/// compares with and converts from a bare `f64`, reads as a `f64` through `Deref`
+/// and is written like the `f64` it holds.
+macro_rules! unit {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "serde", serde(transparent))]
+        pub struct $name(pub f64);
+
+        impl $name {
+            /// Returns the value in the unit of the DSTV file
+            pub fn value(self) -> f64 {
+                self.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = f64;
+
+            fn deref(&self) -> &f64 {
+                &self.0
+            }
+        }
+
+        impl From<f64> for $name {
+            fn from(value: f64) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for f64 {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl PartialEq<f64> for $name {
+            fn eq(&self, other: &f64) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialOrd<f64> for $name {
+            fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+                self.0.partial_cmp(other)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                Display::fmt(&self.0, f)
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl Add<f64> for $name {
+            type Output = Self;
+
+            fn add(self, other: f64) -> Self {
+                Self(self.0 + other)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0;
+            }
+        }
+
+        impl AddAssign<f64> for $name {
+            fn add_assign(&mut self, other: f64) {
+                self.0 += other;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+
+        impl Sub<f64> for $name {
+            type Output = Self;
+
+            fn sub(self, other: f64) -> Self {
+                Self(self.0 - other)
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, other: Self) {
+                self.0 -= other.0;
+            }
+        }
+
+        impl SubAssign<f64> for $name {
+            fn sub_assign(&mut self, other: f64) {
+                self.0 -= other;
+            }
+        }
+
+        impl Mul<$name> for f64 {
+            type Output = $name;
+
+            fn mul(self, value: $name) -> $name {
+                $name(self * value.0)
+            }
+        }
+
+        impl Mul for $name {
+            type Output = f64;
+
+            fn mul(self, other: Self) -> f64 {
+                self.0 * other.0
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+
+            fn mul(self, factor: f64) -> Self {
+                Self(self.0 * factor)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+
+            fn div(self, divisor: f64) -> Self {
+                Self(self.0 / divisor)
+            }
+        }
+
+        impl Div for $name {
+            type Output = f64;
+
+            fn div(self, divisor: Self) -> f64 {
+                self.0 / divisor.0
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Sum for $name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|value| value.0).sum())
+            }
+        }
+    };
+}
+
+unit!(
+    /// A length, position or diameter in millimetres
+    Millimetres
+);
+
+unit!(
+    /// An angle in degrees
+    Degrees
+);
+
+unit!(
+    /// A weight per metre of profile in kg/m
+    KilogramsPerMetre
+);
+
+unit!(
+    /// A painting surface per metre of profile in m²/m
+    SquareMetresPerMetre
+);
+
+impl Millimetres {
+    /// Creates a length from inches
+    /// # Arguments
+    /// * `inches` - The length in inches
+    pub fn from_inches(inches: f64) -> Self {
+        Self(inches * MM_PER_INCH)
+    }
+
+    /// Returns the length in inches
+    pub fn inches(self) -> f64 {
+        self.0 / MM_PER_INCH
+    }
+
+    /// Returns the length in feet
+    pub fn feet(self) -> f64 {
+        self.0 / MM_PER_FOOT
+    }
+}
+
+impl Degrees {
+    /// Returns the angle in radians
+    pub fn radians(self) -> f64 {
+        self.0.to_radians()
+    }
+}
+
+impl KilogramsPerMetre {
+    /// Returns the weight per foot of profile in lb/ft
+    pub fn pounds_per_foot(self) -> f64 {
+        self.0 / KG_PER_POUND * MM_PER_FOOT / 1000.0
+    }
+}
+
+impl SquareMetresPerMetre {
+    /// Returns the painting surface per foot of profile in ft²/ft
+    pub fn square_feet_per_foot(self) -> f64 {
+        // m²/m equals m, so the value converts as a length
+        self.0 * 1000.0 / MM_PER_FOOT
+    }
+}
@@ -0,0 +1,728 @@
+use crate::{
+    dstv_element_type::DstvElementType,
+    geometry::{self, Point, EPSILON},
+    prelude::{Border, BorderPoint, CodeProfile, ContourFeature, Dstv, PartEnd, PartFace},
+};
+use std::fmt::Display;
+
+/// Tolerance used by the validation rules when comparing coordinates, in millimetres
+const TOLERANCE: f64 = 0.01;
+
+/// Points closer than this to an edge of a contour lie on that edge, in millimetres
+const FEATURE_TOLERANCE: f64 = 0.1;
+
+/// The severity of a validation finding
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Worth knowing, but the part can be produced as is
+    Info,
+    /// Likely a mistake, the part should be checked before production
+    Warning,
+    /// The part cannot be produced correctly
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single problem found by a validation rule
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    /// The name of the rule that produced the finding
+    pub rule: &'static str,
+    /// The severity of the finding
+    pub severity: Severity,
+    /// The face the finding applies to, if any
+    pub face: Option<PartFace>,
+    /// The index of the element in `Dstv::elements` the finding applies to, if any
+    pub element: Option<usize>,
+    /// A human readable description of the finding
+    pub message: String,
+}
+
+impl Finding {
+    fn new<S: AsRef<str>>(rule: &'static str, severity: Severity, message: S) -> Self {
+        Self {
+            rule,
+            severity,
+            face: None,
+            element: None,
+            message: message.as_ref().to_string(),
+        }
+    }
+
+    fn on(mut self, face: &PartFace, element: usize) -> Self {
+        self.face = Some(face.clone());
+        self.element = Some(element);
+        self
+    }
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[{}] {}: {}", self.severity, self.rule, self.message)
+    }
+}
+
+/// A rule checking a parsed DSTV file for a specific kind of problem
+pub trait ValidationRule {
+    /// Returns the name of the rule, used to identify its findings
+    fn name(&self) -> &'static str;
+
+    /// Checks a DSTV file against the rule
+    /// # Arguments
+    /// * `dstv` - The DSTV file to check
+    /// # Returns
+    /// All findings of the rule, empty if the file satisfies the rule
+    fn check(&self, dstv: &Dstv) -> Vec<Finding>;
+}
+
+/// Runs a set of validation rules over a DSTV file
+pub struct Validator {
+    rules: Vec<Box<dyn ValidationRule>>,
+}
+
+impl Validator {
+    /// Creates a validator with all built-in rules and their default settings
+    pub fn new() -> Self {
+        Self::empty()
+            .with_rule(ContourRule)
+            .with_rule(FaceRule)
+            .with_rule(LengthRule)
+            .with_rule(HolePlacementRule)
+            .with_rule(OverlappingHolesRule)
+            .with_rule(EdgeDistanceRule::default())
+            .with_rule(ProfileRule)
+            .with_rule(EndCutRule::default())
+    }
+
+    /// Creates a validator without any rules, to which rules can be added one by one
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Adds a rule to the validator
+    /// # Arguments
+    /// * `rule` - The rule to add
+    pub fn with_rule<R: ValidationRule + 'static>(mut self, rule: R) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Runs all rules over a DSTV file
+    /// # Arguments
+    /// * `dstv` - The DSTV file to check
+    /// # Returns
+    /// The findings of all rules, ordered by descending severity
+    pub fn validate(&self, dstv: &Dstv) -> Vec<Finding> {
+        let mut findings = self
+            .rules
+            .iter()
+            .flat_map(|rule| rule.check(dstv))
+            .collect::<Vec<_>>();
+        findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+        findings
+    }
+}
+
+impl Default for Validator {
+    /// Creates a validator with all built-in rules and their default settings
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Dstv {
+    /// Validates the DSTV file with all built-in rules
+    /// # Returns
+    /// The findings of all rules, ordered by descending severity
+    pub fn validate(&self) -> Vec<Finding> {
+        Validator::default().validate(self)
+    }
+}
+
+/// A hole or slot of a face, used to check both alike. A slot is the rectangle between
+/// the centres of its roundings widened by the radius, a hole is its centre widened by
+/// the radius.
+struct Opening {
+    index: usize,
+    /// `hole` or `slot`, used in messages
+    kind: &'static str,
+    face: PartFace,
+    center: Point,
+    /// The centre of a hole, or the centres of the roundings of a slot
+    core: Vec<Point>,
+    diameter: f64,
+}
+
+/// Returns the shortest distance between the edges of two shapes, each a point, a line
+/// or a polygon given by its corner points
+fn edge_distance(a: &[Point], b: &[Point]) -> f64 {
+    let edge = |shape: &[Point], i: usize| (shape[i], shape[(i + 1) % shape.len()]);
+    let crossing = (0..a.len()).any(|i| {
+        (0..b.len()).any(|j| {
+            let ((a1, a2), (b1, b2)) = (edge(a, i), edge(b, j));
+            geometry::lines_cross(&a1, &a2, &b1, &b2)
+        })
+    });
+    if crossing {
+        return 0.0;
+    }
+    a.iter()
+        .map(|p| geometry::distance_to_polygon(b, p))
+        .chain(b.iter().map(|p| geometry::distance_to_polygon(a, p)))
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Returns the distance between two shapes, which is zero if one lies within the other
+fn shape_distance(a: &[Point], b: &[Point]) -> f64 {
+    let contains = |polygon: &[Point], points: &[Point]| {
+        polygon.len() > 2
+            && points
+                .iter()
+                .any(|p| geometry::polygon_contains(polygon, p))
+    };
+    match contains(a, b) || contains(b, a) {
+        true => 0.0,
+        false => edge_distance(a, b),
+    }
+}
+
+/// Collects the holes and slots of a DSTV file
+fn openings(dstv: &Dstv) -> Vec<Opening> {
+    dstv.elements
+        .iter()
+        .enumerate()
+        .filter_map(|(index, element)| match element {
+            DstvElementType::Hole(e) => Some(Opening {
+                index,
+                kind: "hole",
+                face: e.fl_code.clone(),
+                center: e.center(),
+                core: vec![e.center()],
+                diameter: *e.diameter,
+            }),
+            DstvElementType::Slot(e) => Some(Opening {
+                index,
+                kind: "slot",
+                face: e.fl_code.clone(),
+                center: e.centroid(),
+                core: e.centres(),
+                diameter: *e.diameter,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the polygons of the outer and the inner borders on a face
+fn border_polygons(dstv: &Dstv, face: &PartFace) -> (Vec<Vec<Point>>, Vec<Vec<Point>>) {
+    let mut outer = Vec::new();
+    let mut inner = Vec::new();
+    for element in dstv.elements.iter() {
+        match element {
+            DstvElementType::OuterBorder(e) if element.get_facing() == face => {
+                outer.push(e.polygon())
+            }
+            DstvElementType::InnerBorder(e) if element.get_facing() == face => {
+                inner.push(e.polygon())
+            }
+            _ => {}
+        }
+    }
+    (outer, inner)
+}
+
+/// Returns the outline of a face without an outer border, with the end cuts applied
+fn face_outline(dstv: &Dstv, face: &PartFace) -> Vec<Point> {
+    let mut outline = dstv
+        .header
+        .default_outline(face)
+        .iter()
+        .map(|point| Point::new(*point.x_coord, *point.y_coord))
+        .collect::<Vec<_>>();
+    outline.pop();
+    outline
+}
+
+/// Checks that contours are closed, have enough points, have no zero-length segments
+/// and do not intersect themselves
+pub struct ContourRule;
+
+impl ContourRule {
+    fn check_contour(&self, index: usize, contour: &[BorderPoint]) -> Vec<Finding> {
+        let name = self.name();
+        let face = contour
+            .first()
+            .map(|p| p.fl_code.clone())
+            .unwrap_or_default();
+        if contour.len() < 3 {
+            return vec![Finding::new(
+                name,
+                Severity::Error,
+                format!(
+                    "contour has {} points, at least 3 are needed",
+                    contour.len()
+                ),
+            )
+            .on(&face, index)];
+        }
+        let mut findings = Vec::new();
+        let (first, last) = (&contour[0], &contour[contour.len() - 1]);
+        if (*first.x_coord - *last.x_coord).hypot(*first.y_coord - *last.y_coord) > TOLERANCE {
+            findings.push(
+                Finding::new(
+                    name,
+                    Severity::Warning,
+                    format!(
+                        "contour is not closed: starts at ({}, {}) but ends at ({}, {})",
+                        first.x_coord, first.y_coord, last.x_coord, last.y_coord
+                    ),
+                )
+                .on(&face, index),
+            );
+        }
+        for (i, pair) in contour.windows(2).enumerate() {
+            if (*pair[0].x_coord - *pair[1].x_coord).hypot(*pair[0].y_coord - *pair[1].y_coord)
+                <= EPSILON
+            {
+                findings.push(
+                    Finding::new(
+                        name,
+                        Severity::Error,
+                        format!(
+                            "zero-length segment between points {} and {} at ({}, {})",
+                            i + 1,
+                            i + 2,
+                            pair[0].x_coord,
+                            pair[0].y_coord
+                        ),
+                    )
+                    .on(&face, index),
+                );
+            }
+        }
+        let polygon = geometry::flatten(
+            &geometry::contour_segments(contour),
+            geometry::FLATTEN_TOLERANCE,
+        );
+        let n = polygon.len();
+        let crosses = (0..n).any(|i| {
+            (i + 2..n).filter(|j| (j + 1) % n != i).any(|j| {
+                geometry::lines_cross(
+                    &polygon[i],
+                    &polygon[(i + 1) % n],
+                    &polygon[j],
+                    &polygon[(j + 1) % n],
+                )
+            })
+        });
+        if crosses {
+            findings.push(
+                Finding::new(name, Severity::Error, "contour intersects itself").on(&face, index),
+            );
+        }
+        findings
+    }
+}
+
+impl ValidationRule for ContourRule {
+    fn name(&self) -> &'static str {
+        "contour"
+    }
+
+    fn check(&self, dstv: &Dstv) -> Vec<Finding> {
+        dstv.elements
+            .iter()
+            .enumerate()
+            .flat_map(|(index, element)| match element {
+                DstvElementType::OuterBorder(e) => self.check_contour(index, &e.contour),
+                DstvElementType::InnerBorder(e) => self.check_contour(index, &e.contour),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// Checks that all elements are placed on faces that exist for the profile of the piece
+pub struct FaceRule;
+
+impl ValidationRule for FaceRule {
+    fn name(&self) -> &'static str {
+        "face"
+    }
+
+    fn check(&self, dstv: &Dstv) -> Vec<Finding> {
+        let faces = dstv.header.code_profile.faces();
+        dstv.elements
+            .iter()
+            .enumerate()
+            .filter(|(_, element)| !faces.contains(element.get_facing()))
+            .map(|(index, element)| {
+                Finding::new(
+                    self.name(),
+                    Severity::Error,
+                    format!(
+                        "element on face {:?}, which does not exist for a {}",
+                        element.get_facing(),
+                        dstv.header.code_profile.to_str()
+                    ),
+                )
+                .on(element.get_facing(), index)
+            })
+            .collect()
+    }
+}
+
+/// Checks that no coordinate lies beyond the length of the piece given in the header
+pub struct LengthRule;
+
+impl LengthRule {
+    /// Returns the x coordinates used by an element
+    fn x_coords(element: &DstvElementType) -> Vec<f64> {
+        match element {
+            DstvElementType::OuterBorder(e) => e.contour.iter().map(|p| *p.x_coord).collect(),
+            DstvElementType::InnerBorder(e) => e.contour.iter().map(|p| *p.x_coord).collect(),
+            DstvElementType::Hole(e) => vec![*e.x_coord],
+            DstvElementType::Slot(e) => vec![*e.x_coord],
+            DstvElementType::Numeration(e) => vec![*e.x_coord],
+            DstvElementType::Bend(e) => vec![*e.origin_x, *e.finish_x],
+            DstvElementType::Cut(_) | DstvElementType::Custom(_) | DstvElementType::Unknown(_) => {
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl ValidationRule for LengthRule {
+    fn name(&self) -> &'static str {
+        "length"
+    }
+
+    fn check(&self, dstv: &Dstv) -> Vec<Finding> {
+        // round plates are described around x = 0, so the piece starts at its leftmost border
+        let start = dstv
+            .elements
+            .iter()
+            .filter_map(|element| match element {
+                DstvElementType::OuterBorder(e) => Some(e.polygon()),
+                _ => None,
+            })
+            .flatten()
+            .map(|point| point.x)
+            .fold(0.0_f64, f64::min);
+        let end = start + *dstv.header.length;
+        dstv.elements
+            .iter()
+            .enumerate()
+            .filter_map(|(index, element)| {
+                Self::x_coords(element)
+                    .into_iter()
+                    .find(|x| *x < start - TOLERANCE || *x > end + TOLERANCE)
+                    .map(|x| {
+                        Finding::new(
+                            self.name(),
+                            Severity::Error,
+                            format!(
+                                "x coordinate {} lies outside the piece length of {}",
+                                x, dstv.header.length
+                            ),
+                        )
+                        .on(element.get_facing(), index)
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Checks that holes and slots lie within the outer border of their face and not
+/// within one of its inner borders. Slots are checked by the centres of all their
+/// roundings.
+pub struct HolePlacementRule;
+
+impl ValidationRule for HolePlacementRule {
+    fn name(&self) -> &'static str {
+        "hole-placement"
+    }
+
+    fn check(&self, dstv: &Dstv) -> Vec<Finding> {
+        openings(dstv)
+            .into_iter()
+            .filter_map(|opening| {
+                let (outer, inner) = border_polygons(dstv, &opening.face);
+                let (kind, x, y) = (opening.kind, opening.center.x, opening.center.y);
+                let within = |polygon: &Vec<Point>, point: &Point| {
+                    geometry::polygon_contains(polygon, point)
+                };
+                let message = if !outer.is_empty()
+                    && !opening
+                        .core
+                        .iter()
+                        .all(|point| outer.iter().any(|polygon| within(polygon, point)))
+                {
+                    format!("{kind} at ({x}, {y}) lies outside the outer border")
+                } else if opening
+                    .core
+                    .iter()
+                    .any(|point| inner.iter().any(|polygon| within(polygon, point)))
+                {
+                    format!("{kind} at ({x}, {y}) lies inside an inner border")
+                } else {
+                    return None;
+                };
+                Some(
+                    Finding::new(self.name(), Severity::Error, message)
+                        .on(&opening.face, opening.index),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Checks that holes and slots on the same face do not overlap, measuring slots by
+/// their true outline
+pub struct OverlappingHolesRule;
+
+impl ValidationRule for OverlappingHolesRule {
+    fn name(&self) -> &'static str {
+        "overlapping-holes"
+    }
+
+    fn check(&self, dstv: &Dstv) -> Vec<Finding> {
+        let openings = openings(dstv);
+        let mut findings = Vec::new();
+        for (i, a) in openings.iter().enumerate() {
+            for b in openings[i + 1..].iter().filter(|b| b.face == a.face) {
+                let distance = shape_distance(&a.core, &b.core);
+                if distance < (a.diameter + b.diameter) / 2.0 - TOLERANCE {
+                    findings.push(
+                        Finding::new(
+                            self.name(),
+                            Severity::Error,
+                            format!(
+                                "{} at ({}, {}) and {} at ({}, {}) overlap",
+                                a.kind, a.center.x, a.center.y, b.kind, b.center.x, b.center.y
+                            ),
+                        )
+                        .on(&a.face, b.index),
+                    );
+                }
+            }
+        }
+        findings
+    }
+}
+
+/// Checks that holes and slots keep a minimum distance from the edges of their face.
+/// The distance is measured from the centre of the hole, or the centres of the roundings
+/// of a slot, and expressed as a multiple of the hole diameter.
+pub struct EdgeDistanceRule {
+    /// The minimum edge distance as a multiple of the hole diameter
+    pub factor: f64,
+}
+
+impl Default for EdgeDistanceRule {
+    /// Creates the rule with a minimum edge distance of 1.5 times the hole diameter
+    fn default() -> Self {
+        Self { factor: 1.5 }
+    }
+}
+
+impl ValidationRule for EdgeDistanceRule {
+    fn name(&self) -> &'static str {
+        "edge-distance"
+    }
+
+    fn check(&self, dstv: &Dstv) -> Vec<Finding> {
+        openings(dstv)
+            .into_iter()
+            .filter_map(|opening| {
+                let (mut outer, inner) = border_polygons(dstv, &opening.face);
+                if outer.is_empty() {
+                    outer.push(face_outline(dstv, &opening.face));
+                }
+                let distance = outer
+                    .iter()
+                    .chain(inner.iter())
+                    .map(|polygon| edge_distance(polygon, &opening.core))
+                    .fold(f64::INFINITY, f64::min);
+                let required = self.factor * opening.diameter;
+                match distance < required - TOLERANCE {
+                    true => {
+                        let (kind, x, y) = (opening.kind, opening.center.x, opening.center.y);
+                        let message = format!(
+                            "{kind} at ({x}, {y}) is {distance:.2} from an edge, at least \
+                             {required:.2} is required"
+                        );
+                        Some(
+                            Finding::new(self.name(), Severity::Warning, message)
+                                .on(&opening.face, opening.index),
+                        )
+                    }
+                    false => None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Checks the profile dimensions and weight in the header against the catalogue of
+/// standard sections. Plates are not checked.
+pub struct ProfileRule;
+
+impl ValidationRule for ProfileRule {
+    fn name(&self) -> &'static str {
+        "profile"
+    }
+
+    fn check(&self, dstv: &Dstv) -> Vec<Finding> {
+        let header = &dstv.header;
+        if header.code_profile == CodeProfile::B {
+            return Vec::new();
+        }
+        let section = match header.catalogue_section() {
+            Ok(section) => section,
+            Err(_) => {
+                return vec![Finding::new(
+                    self.name(),
+                    Severity::Info,
+                    format!("profile `{}` is not in the catalogue", header.profile),
+                )]
+            }
+        };
+        let mut findings = Vec::new();
+        if section.family.code_profile() != header.code_profile {
+            findings.push(Finding::new(
+                self.name(),
+                Severity::Warning,
+                format!(
+                    "profile `{}` is a {}, but the header says {}",
+                    header.profile,
+                    section.family.code_profile().to_str(),
+                    header.code_profile.to_str()
+                ),
+            ));
+        }
+        for mismatch in header.check_profile().unwrap_or_default() {
+            let severity = match mismatch.field {
+                "weight_by_meter" => Severity::Warning,
+                _ => Severity::Error,
+            };
+            findings.push(Finding::new(
+                self.name(),
+                severity,
+                format!(
+                    "`{}` is {} but {} is {}",
+                    mismatch.field, mismatch.actual, section.name, mismatch.expected
+                ),
+            ));
+        }
+        findings
+    }
+}
+
+/// Checks that the end cut angles in the header agree with the ends of the outer
+/// contours. Ends with a square cut in the header and ends with copes, notches or snips
+/// are not checked.
+pub struct EndCutRule {
+    /// The largest allowed difference between the angles, in degrees
+    pub tolerance: f64,
+}
+
+impl Default for EndCutRule {
+    /// Allows a difference of half a degree
+    fn default() -> Self {
+        Self { tolerance: 0.5 }
+    }
+}
+
+impl EndCutRule {
+    /// Measures the angle of the cut at an end of a contour, with the sign convention
+    /// of the header
+    fn measure(polygon: &[Point], end: PartEnd) -> Option<f64> {
+        let min_y = polygon.iter().map(|p| p.y).fold(f64::MAX, f64::min);
+        let max_y = polygon.iter().map(|p| p.y).fold(f64::MIN, f64::max);
+        let width = max_y - min_y;
+        if width <= EPSILON {
+            return None;
+        }
+        let extreme = |y: f64| {
+            let xs = polygon
+                .iter()
+                .filter(|p| (p.y - y).abs() <= FEATURE_TOLERANCE)
+                .map(|p| p.x);
+            match end {
+                PartEnd::Start => xs.fold(f64::MAX, f64::min),
+                PartEnd::End => -xs.fold(f64::MIN, f64::max),
+            }
+        };
+        Some((extreme(max_y) - extreme(min_y)).atan2(width).to_degrees())
+    }
+}
+
+impl ValidationRule for EndCutRule {
+    fn name(&self) -> &'static str {
+        "end-cut"
+    }
+
+    fn check(&self, dstv: &Dstv) -> Vec<Finding> {
+        let header = &dstv.header;
+        let features = dstv.features();
+        let mut findings = Vec::new();
+        for (index, element) in dstv.elements.iter().enumerate() {
+            let border = match element {
+                DstvElementType::OuterBorder(border) => border,
+                _ => continue,
+            };
+            let face = element.get_facing();
+            let polygon = border.polygon();
+            for end in [PartEnd::Start, PartEnd::End] {
+                let expected = header.face_cut_angle(face, end);
+                let shaped = features.iter().any(|feature| match feature {
+                    ContourFeature::Cope(f) => &f.face == face && f.end == end,
+                    ContourFeature::CornerSnip(f) => &f.face == face && f.end == end,
+                    ContourFeature::Other(f) => &f.face == face,
+                    _ => false,
+                });
+                if expected == 0.0 || shaped {
+                    continue;
+                }
+                let actual = match Self::measure(&polygon, end) {
+                    Some(actual) => actual,
+                    None => continue,
+                };
+                if (actual - expected).abs() > self.tolerance {
+                    let (kind, side) = match (face, end) {
+                        (PartFace::Top, PartEnd::Start) | (PartFace::Bottom, PartEnd::Start) => {
+                            ("flange", "start")
+                        }
+                        (PartFace::Top, PartEnd::End) | (PartFace::Bottom, PartEnd::End) => {
+                            ("flange", "end")
+                        }
+                        (_, PartEnd::Start) => ("web", "start"),
+                        (_, PartEnd::End) => ("web", "end"),
+                    };
+                    findings.push(
+                        Finding::new(
+                            self.name(),
+                            Severity::Warning,
+                            format!(
+                                "{kind} {side} cut is {expected}° in the header, but the contour is cut at {:.1}°",
+                                actual
+                            ),
+                        )
+                        .on(face, index),
+                    );
+                }
+            }
+        }
+        findings
+    }
+}
@@ -0,0 +1,114 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{assert_close, part, plate_header};
+    use dstv::prelude::*;
+    use std::f64::consts::PI;
+
+    /// A plate of 200 by 100, 10 thick, bent over the middle
+    fn plate(angle: f64) -> Dstv {
+        part(plate_header("PL1", 200.0, 100.0))
+            .outer_contour(
+                PartFace::Front,
+                vec![(0.0, 0.0), (200.0, 0.0), (200.0, 100.0), (0.0, 100.0)],
+            )
+            .add_hole(PartFace::Front, 50.0, 50.0, 22.0)
+            .add_slot(PartFace::Front, 150.0, 50.0, 20.0, 30.0, 0.0, 0.0)
+            .add_element(DstvElementType::Bend(
+                Bend::from_str(&format!("  v 100.00 0.00 {angle} 5.00 100.00 100.00")).unwrap(),
+            ))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_bend_lines() {
+        let bend = Bend::from_str("  o 100.00 0.00 -90.00 5.00 100.00 100.00").unwrap();
+        assert_eq!(bend.get_facing(), &PartFace::Top);
+        assert_eq!(bend.direction(), BendDirection::Down);
+        assert_eq!(bend.length(), 100.0);
+        assert_eq!(bend.label(), "down 90° R5");
+
+        // bend lines without a flange code are on the front face
+        let bend = Bend::from_str("  100.00 0.00 90.00 5.00 100.00 100.00").unwrap();
+        assert_eq!(bend.get_facing(), &PartFace::Front);
+        assert_eq!(bend.origin(), Point::new(100.0, 0.0));
+        assert_eq!(
+            bend.to_dstv(),
+            "KA\n  v    100.00      0.00     90.00      5.00    100.00    100.00\n"
+        );
+        assert_eq!(Bend::from_str(&bend.to_dstv()[3..]).unwrap(), bend);
+    }
+
+    #[test]
+    fn bend_allowance_and_deduction() {
+        let bend = Bend::from_str("  v 100.00 0.00 90.00 5.00 100.00 100.00").unwrap();
+        // a quarter of the neutral fibre at 5 + 0.5 * 10 from the centre
+        assert_close(bend.allowance(10.0, 0.5), 5.0 * PI);
+        // both legs are measured to the outside corner at 5 + 10 from the centre
+        assert_close(bend.deduction(10.0, 0.5), 30.0 - 5.0 * PI);
+        assert_close(bend.distance(&Point::new(0.0, 50.0)), -100.0);
+        assert_close(bend.distance(&Point::new(150.0, 50.0)), 50.0);
+    }
+
+    #[test]
+    fn bend_lines_are_drawn_with_their_direction() {
+        let svg = plate(90.0).to_svg();
+        assert!(svg.contains("stroke=\"blue\" stroke-dasharray=\"10 5\""));
+        assert!(svg.contains(">up 90° R5</text>"));
+        let svg = plate(-45.0).to_svg();
+        assert!(svg.contains("stroke=\"red\" stroke-dasharray=\"10 5\""));
+        assert!(svg.contains(">down 45° R5</text>"));
+    }
+
+    #[test]
+    fn faces_are_written_as_dxf() {
+        let dxf = plate(90.0).to_dxf(&PartFace::Front);
+        assert!(dxf.starts_with("0\nSECTION\n2\nHEADER\n9\n$ACADVER\n1\nAC1009\n"));
+        assert!(dxf.ends_with("0\nENDSEC\n0\nEOF\n"));
+        assert!(dxf.contains("0\nPOLYLINE\n8\nCONTOUR\n"));
+        assert!(dxf.contains("0\nCIRCLE\n8\nHOLES\n10\n50\n20\n50\n40\n11\n"));
+        assert!(dxf.contains("0\nLINE\n8\nBEND_UP\n10\n100\n20\n0\n11\n100\n21\n100\n"));
+        assert!(dxf.contains("1\nup 90° R5\n"));
+        // the slot is a rectangle with two half circles of two quarter arcs each
+        assert_eq!(dxf.matches("0\nVERTEX\n8\nHOLES\n").count(), 6);
+        assert_eq!(dxf.matches("42\n0.414213562373095").count(), 4);
+        assert_eq!(dxf.matches("0\nVERTEX\n8\nCONTOUR\n").count(), 4);
+
+        // faces without bend lines have no bend layer
+        let dxf = plate(-90.0).to_dxf(&PartFace::Top);
+        assert!(!dxf.contains("BEND_"));
+        assert!(plate(-90.0)
+            .to_dxf(&PartFace::Front)
+            .contains("8\nBEND_DOWN\n"));
+    }
+
+    #[test]
+    fn plates_are_folded_along_their_bend_lines() {
+        let dstv = plate(90.0);
+        assert_eq!(dstv.bends().len(), 1);
+        let folding = dstv.folding(0.5);
+        // the side with the start of the contour stays flat
+        assert_eq!(folding.fold(&Point::new(50.0, 50.0)), [50.0, 50.0, 0.0]);
+        // the far side stands up, moved in by the bend zone
+        let [x, y, z] = folding.fold(&Point::new(200.0, 50.0));
+        let leg = 110.0 - 5.0 * PI / 2.0;
+        assert_close(x, leg);
+        assert_close(y, 50.0);
+        assert_close(z, leg);
+
+        // bending down folds away from the viewer
+        let [x, _, z] = plate(-90.0).folding(0.5).fold(&Point::new(200.0, 50.0));
+        assert_close(x, leg);
+        assert_close(z, -leg);
+
+        // the folded outline follows the bend zone
+        let outlines = dstv.folded_outlines(0.5);
+        assert_eq!(outlines.len(), 1);
+        let outline = &outlines[0];
+        assert!(outline.len() > 4);
+        assert!(outline.iter().all(|p| p[0] <= 102.147 && p[2] >= 0.0));
+        assert!(outline.iter().any(|p| (p[2] - 102.146).abs() < 1e-3));
+    }
+}
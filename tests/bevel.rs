@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+
+    fn bevel(values: &str) -> Option<Bevel> {
+        Bevel::from_values(values.split_whitespace()).unwrap()
+    }
+
+    #[test]
+    fn parse_bevels_from_contours() {
+        let border = OuterBorder::from_block(&Block::new(
+            "AK",
+            &[
+                "  v 0.00 0.00 0.00 30.00",
+                "  v 400.00 0.00 0.00 30.00 8.00",
+                "  v 400.00 100.00 0.00 45.00 10.00 30.00 5.00",
+                "  v 0.00 100.00 0.00 45.00 0.00 45.00",
+                "  v 0.00 0.00 0.00",
+            ],
+        ))
+        .unwrap();
+        let segments = border.segments();
+        assert_eq!(segments.len(), 4);
+        assert_eq!(segments[0].bevel.unwrap().kind(), BevelKind::V);
+        assert_eq!(segments[1].bevel.unwrap().kind(), BevelKind::Y);
+        assert_eq!(segments[2].bevel.unwrap().kind(), BevelKind::K);
+        assert_eq!(segments[3].bevel.unwrap().kind(), BevelKind::X);
+        assert_eq!(
+            segments[2].bevel,
+            Some(Bevel {
+                top_angle: Degrees(45.0),
+                top_depth: Millimetres(10.0),
+                bottom_angle: Degrees(30.0),
+                bottom_depth: Millimetres(5.0),
+            })
+        );
+        assert_eq!(border.contour[4].bevel, None);
+
+        // bevels are written back without trailing zeros
+        let nc = border.to_dstv();
+        assert!(nc.contains("      0.00     30.00\n"));
+        assert!(nc.contains("      0.00     45.00      0.00     45.00\n"));
+        let lines = nc.lines().skip(1).collect::<Vec<_>>();
+        assert_eq!(
+            OuterBorder::from_block(&Block::new("AK", &lines)).unwrap(),
+            border
+        );
+    }
+
+    #[test]
+    fn bevel_kinds_and_root_faces() {
+        assert_eq!(bevel("0 0"), None);
+        let v = bevel("30").unwrap();
+        assert_eq!(v.kind(), BevelKind::V);
+        assert_eq!(v.root_face(20.0), 0.0);
+        assert_eq!(v.to_string(), "V 30°");
+
+        let y = bevel("30 8").unwrap();
+        assert_eq!(y.kind(), BevelKind::Y);
+        assert_eq!(y.root_face(20.0), 12.0);
+        assert_eq!(y.to_string(), "Y 30° 8");
+
+        // a bevel on the bottom side only
+        let y = bevel("0 0 30 8").unwrap();
+        assert_eq!(y.kind(), BevelKind::Y);
+        assert_eq!(y.root_face(20.0), 12.0);
+
+        let x = bevel("30 8 30 8").unwrap();
+        assert_eq!(x.kind(), BevelKind::X);
+        assert_eq!(x.root_face(20.0), 4.0);
+        assert_eq!(x.to_string(), "X 30° 8/30° 8");
+
+        // a negative angle bevels the other side
+        let bottom = bevel("-22.5 11").unwrap();
+        assert!(!bottom.is_top());
+        assert_eq!(bottom.bottom_angle, 22.5);
+        assert_eq!(bottom.bottom_depth, 11.0);
+        assert_eq!(bottom.to_dstv(), bevel("0 0 22.5 11").unwrap().to_dstv());
+        let swapped = bevel("-45 10 -30 5").unwrap();
+        assert_eq!(swapped, bevel("30 5 45 10").unwrap());
+        assert!(Bevel::from_values("30 8 -45 5".split_whitespace()).is_err());
+
+        let k = bevel("45 10 30 5").unwrap();
+        assert_eq!(k.kind(), BevelKind::K);
+        assert_eq!(k.root_face(20.0), 5.0);
+        assert_eq!(k.to_string(), "K 45° 10/30° 5");
+    }
+
+    #[test]
+    fn bevels_are_annotated() {
+        let mut dstv = Dstv::from_file("./tests/data/0008-SE0004.nc1").unwrap();
+        let svg = dstv.to_svg();
+        assert!(svg.contains("stroke=\"red\" stroke-width=\"4\" /><text x=\"243.96\" y=\"40\" fill=\"red\" font-size=\"10\">V 22.5°</text>"));
+        // the negative angle on the top flange bevels its bottom side
+        assert!(svg.contains(">Y 22.5° 11</text>"));
+
+        let dxf = dstv.to_dxf(&PartFace::Bottom);
+        assert!(dxf.contains("0\nTEXT\n8\nBEVEL\n10\n243.96\n20\n40\n"));
+        assert!(dxf.contains("1\nV 22.5°\n"));
+    }
+}
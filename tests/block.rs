@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+
+    #[test]
+    fn every_element_reads_its_block() {
+        let contour = [
+            "  v 0.00 0.00 0.00",
+            "  v 400.00 0.00 0.00",
+            "  v 400.00 100.00 0.00",
+            "  v 0.00 100.00 0.00",
+            "  v 0.00 0.00 0.00",
+        ];
+        let border = OuterBorder::from_block(&Block::new("AK", &contour)).unwrap();
+        assert_eq!(border.contour.len(), 5);
+        assert_eq!(OuterBorder::from_str(&contour.join("\n")).unwrap(), border);
+
+        let hole = Hole::from_block(&Block::new("BO", &["  v 100.00 50.00 22.00"])).unwrap();
+        assert_eq!(hole, Hole::from_str("  v 100.00 50.00 22.00").unwrap());
+        assert!(Hole::from_block(&Block::new("BO", &[])).is_err());
+
+        let unknown = UnknownBlock::from_block(&Block::new("PU", &["  v 1 2", "  v 3 4"])).unwrap();
+        assert_eq!(unknown.code, "PU");
+        assert_eq!(unknown.to_dstv(), "PU\n  v 1 2\n  v 3 4\n");
+    }
+
+    #[test]
+    fn errors_point_at_the_line() {
+        let lines = ["  v 100.00 fifty 22.00"];
+        let block = Block::new("BO", &lines).with_line_numbers(&[27]);
+        assert_eq!(block.line_number(0), Some(27));
+        let error = block.locate(Hole::from_block(&block).unwrap_err());
+        assert!(error.to_string().contains("Invalid `BO` block at line 27"));
+
+        let unnumbered = Block::new("BO", &lines);
+        let error = unnumbered.locate(Hole::from_block(&unnumbered).unwrap_err());
+        assert_eq!(error, Hole::from_block(&unnumbered).unwrap_err());
+    }
+
+    #[test]
+    fn files_with_invalid_blocks_name_the_line() {
+        let source = std::fs::read_to_string("./tests/data/0008-SE0004.nc1")
+            .unwrap()
+            .replace("130.00    22.00", "130.00    twenty");
+        let expected = "Invalid `BO` block at line 56";
+        let error = Dstv::from_str_strict(&source).unwrap_err();
+        assert!(error.to_string().contains(expected), "{error}");
+
+        // by default the invalid block is skipped
+        let dstv = Dstv::from_str(&source).unwrap();
+        let valid = Dstv::from_file("./tests/data/0008-SE0004.nc1").unwrap();
+        assert_eq!(dstv.elements.len(), valid.elements.len() - 1);
+
+        // the syntax tree keeps the block as it is, with its error
+        let tree = SyntaxTree::parse(&source);
+        assert_eq!(tree.to_string(), source);
+        let invalid = tree
+            .nodes()
+            .iter()
+            .filter(|node| node.error.is_some())
+            .collect::<Vec<_>>();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].element, None);
+        assert_eq!(invalid[0].error, Some(error.clone()));
+
+        let reader = DstvReader::new(std::io::Cursor::new(source.clone()));
+        assert_eq!(reader.into_dstv().unwrap(), dstv);
+        let mut reader = DstvReader::new(std::io::Cursor::new(source));
+        reader.set_strict(true);
+        let errors = reader.filter_map(Result::err).collect::<Vec<_>>();
+        assert_eq!(errors, vec![error]);
+    }
+
+    #[test]
+    fn write_matches_to_dstv() {
+        let dstv = Dstv::from_file("./tests/data/P1565.nc").unwrap();
+        for element in &dstv.elements {
+            let mut block = String::new();
+            element.write(&mut block).unwrap();
+            assert_eq!(block, element.to_dstv());
+        }
+    }
+
+    #[test]
+    fn positions_and_bounding_boxes() {
+        let hole = DstvElementType::Hole(Hole::from_str("v 100 50 20").unwrap());
+        assert_eq!(hole.position(), Some(Point::new(100.0, 50.0)));
+        assert_eq!(
+            hole.bounding_box(),
+            Some((Point::new(90.0, 40.0), Point::new(110.0, 60.0)))
+        );
+
+        let slot = Slot::from_str("v 100 50 20 0 40 0 0").unwrap();
+        assert_eq!(
+            slot.bounding_box(),
+            Some((Point::new(90.0, 40.0), Point::new(150.0, 60.0)))
+        );
+
+        let border = OuterBorder::from_str("v 0 0 0\nv 400 0 0\nv 400 100 0\nv 0 100 0").unwrap();
+        assert_eq!(border.position(), Some(Point::new(200.0, 50.0)));
+        assert_eq!(
+            border.bounding_box(),
+            Some((Point::new(0.0, 0.0), Point::new(400.0, 100.0)))
+        );
+
+        let cut = Cut::from_str("o 500 0 0 1 0 0").unwrap();
+        assert_eq!(cut.position(), Some(Point::new(500.0, 0.0)));
+        assert_eq!(cut.bounding_box(), None);
+        assert_eq!(UnknownBlock::new("PU", &["v 1 2"]).position(), None);
+    }
+}
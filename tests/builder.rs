@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+
+    fn base_plate() -> Header {
+        HeaderBuilder::new()
+            .order_identification("ORDER-1")
+            .piece_identification("BP1")
+            .steel_quality("S355J2+N")
+            .quantity(4)
+            .length(300.0)
+            .plate(200.0, 15.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn header_defaults() {
+        let header = base_plate();
+        assert_eq!(header.order_identification, "ORDER-1");
+        assert_eq!(header.drawing_identification, "NA");
+        assert_eq!(header.profile, "PL15*200");
+        assert_eq!(header.code_profile, CodeProfile::B);
+        assert_eq!(header.face_width(&PartFace::Front), 200.0);
+        assert_eq!(header.face_thickness(&PartFace::Front), 15.0);
+        assert!((header.weight_by_meter - 23.55).abs() < 1e-9);
+        assert_eq!(header.saw_length, None);
+    }
+
+    #[test]
+    fn plate_matches_exported_plates() {
+        let exported = Dstv::from_file("./tests/data/P2663.nc").unwrap().header;
+        let header = HeaderBuilder::new()
+            .piece_identification("P2663")
+            .length(550.0)
+            .plate(550.0, 20.0)
+            .build()
+            .unwrap();
+        assert_eq!(header.profile, exported.profile);
+        assert_eq!(header.code_profile, exported.code_profile);
+        assert_eq!(header.profile_height, exported.profile_height);
+        assert_eq!(header.flange_width, exported.flange_width);
+        assert_eq!(header.flange_thickness, exported.flange_thickness);
+        assert_eq!(header.web_thickness, exported.web_thickness);
+        assert_eq!(header.radius, exported.radius);
+    }
+
+    #[test]
+    fn header_validation() {
+        assert!(HeaderBuilder::new()
+            .length(300.0)
+            .plate(200.0, 15.0)
+            .build()
+            .is_err());
+        assert_eq!(
+            HeaderBuilder::new()
+                .piece_identification("BP1")
+                .plate(200.0, 15.0)
+                .build(),
+            Err(BuildDstvError::Header {
+                field: "length",
+                requirement: "positive"
+            })
+        );
+        assert!(HeaderBuilder::new()
+            .piece_identification("BP1")
+            .length(300.0)
+            .plate(200.0, 15.0)
+            .quantity(0)
+            .build()
+            .is_err());
+        assert_eq!(
+            HeaderBuilder::new()
+                .piece_identification("B1")
+                .length(1000.0)
+                .profile("HEA200", CodeProfile::I)
+                .dimensions(190.0, 200.0, 10.0, 0.0, 18.0)
+                .build()
+                .err()
+                .unwrap()
+                .to_string(),
+            "header: `web_thickness` must be positive"
+        );
+    }
+
+    #[test]
+    fn rectangular_plate() {
+        let dstv = DstvBuilder::new(base_plate())
+            .add_hole(PartFace::Front, 50.0, 50.0, 22.0)
+            .add_hole(PartFace::Front, 250.0, 50.0, 22.0)
+            .add_slot(PartFace::Front, 140.0, 150.0, 18.0, 20.0, 0.0, 0.0)
+            .add_mark(PartFace::Front, 100.0, 100.0, "BP1")
+            .build()
+            .unwrap();
+        assert_eq!(dstv.elements.len(), 5);
+        match &dstv.elements[0] {
+            DstvElementType::OuterBorder(border) => {
+                assert_eq!(border.contour.len(), 5);
+                assert_eq!(border.area(), 300.0 * 200.0);
+            }
+            element => panic!("expected an outer border, got {element:?}"),
+        }
+        assert!(dstv.validate().is_empty());
+        // the generated file can be read back
+        assert_eq!(Dstv::from_str(dstv.to_dstv()).unwrap(), dstv);
+    }
+
+    #[test]
+    fn contours() {
+        let dstv = DstvBuilder::new(base_plate())
+            .outer_contour(
+                PartFace::Front,
+                [
+                    (0.0, 0.0),
+                    (300.0, 0.0),
+                    (300.0, 150.0),
+                    (250.0, 200.0),
+                    (0.0, 200.0),
+                ],
+            )
+            .add_inner_contour(
+                PartFace::Front,
+                vec![(100.0, 80.0, 0.0), (200.0, 80.0, 20.0), (200.0, 120.0, 0.0)],
+            )
+            .build()
+            .unwrap();
+        assert_eq!(dstv.elements.len(), 2);
+        match &dstv.elements[1] {
+            DstvElementType::InnerBorder(border) => {
+                assert_eq!(border.contour.len(), 4);
+                assert_eq!(border.contour[1].radius, 20.0);
+                assert_eq!(border.contour[3].x_coord, 100.0);
+            }
+            element => panic!("expected an inner border, got {element:?}"),
+        }
+        let cut_out = match &dstv.elements[1] {
+            DstvElementType::InnerBorder(border) => border.area(),
+            _ => 0.0,
+        };
+        assert_eq!(
+            dstv.net_area(&PartFace::Front),
+            300.0 * 200.0 - 50.0 * 50.0 / 2.0 - cut_out
+        );
+    }
+
+    #[test]
+    fn invalid_parts() {
+        // a hole outside of the plate
+        let result = DstvBuilder::new(base_plate())
+            .add_hole(PartFace::Front, 350.0, 50.0, 22.0)
+            .build();
+        assert!(result.is_err());
+        // overlapping holes
+        let result = DstvBuilder::new(base_plate())
+            .add_hole(PartFace::Front, 50.0, 50.0, 22.0)
+            .add_hole(PartFace::Front, 60.0, 50.0, 22.0)
+            .build();
+        match result {
+            Err(BuildDstvError::Validation(findings)) => {
+                assert_eq!(findings[0].rule, "overlapping-holes")
+            }
+            _ => panic!("overlapping holes must fail validation"),
+        }
+        // a plate has no top face
+        let result = DstvBuilder::new(base_plate())
+            .add_hole(PartFace::Top, 50.0, 50.0, 22.0)
+            .build();
+        assert!(result.is_err());
+        // marks are written as a single word
+        let result = DstvBuilder::new(base_plate())
+            .add_mark(PartFace::Front, 50.0, 50.0, "BP 1")
+            .build();
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "mark: invalid text `BP 1`"
+        );
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use dstv::prelude::*;
+
+    #[test]
+    fn recognize_profile_names() {
+        let hea = SectionDimensions::from_str("HEA200").unwrap();
+        assert_eq!(hea.family, SectionFamily::Hea);
+        assert_eq!(hea.name, "HEA 200");
+        assert_eq!(hea.height, 190.0);
+        assert_eq!(hea.weight_by_meter, 42.3);
+        assert_eq!(SectionDimensions::from_str("HE 200 A").unwrap(), hea);
+        assert_eq!(SectionDimensions::from_str("hea 200").unwrap(), hea);
+
+        let ipe = SectionDimensions::from_str("IPE  300").unwrap();
+        assert_eq!(ipe.family, SectionFamily::Ipe);
+        assert_eq!(ipe.radius, Some(15.0));
+
+        let angle = SectionDimensions::from_str("L150x100x10").unwrap();
+        assert_eq!(angle.family, SectionFamily::Angle);
+        assert_eq!(
+            (angle.height, angle.width, angle.web_thickness),
+            (150.0, 100.0, 10.0)
+        );
+        assert_eq!(
+            SectionDimensions::from_str("L 100*10").unwrap().name,
+            "L 100x100x10"
+        );
+
+        let shs = SectionDimensions::from_str("100x100x6 SHS").unwrap();
+        assert_eq!(shs.family, SectionFamily::Shs);
+        assert_eq!(shs.name, "SHS 100x100x6");
+        assert!((shs.weight_by_meter - 17.5).abs() < 0.2);
+
+        let chs = SectionDimensions::from_str("CHS168.3x6.3").unwrap();
+        assert_eq!(chs.family, SectionFamily::Chs);
+        assert!((chs.weight_by_meter - 25.2).abs() < 0.1);
+        assert_eq!(chs.family.code_profile(), CodeProfile::RO);
+    }
+
+    #[test]
+    fn unknown_profile_names() {
+        assert!(SectionDimensions::from_str("W21X44").is_err());
+        assert!(SectionDimensions::from_str("IPE 310").is_err());
+        assert!(SectionDimensions::from_str("196.451x10 PL").is_err());
+        assert_eq!(
+            SectionDimensions::from_str("ZS175*1.5")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Could not parse DSTV element: \"Unknown profile: ZS175*1.5\""
+        );
+    }
+
+    #[test]
+    fn matching_headers() {
+        for file in ["0008-SE0004.nc1", "0008-SE0008.nc1", "0008-SE0009.nc1"] {
+            let dstv = Dstv::from_file(format!("./tests/data/{file}")).unwrap();
+            assert_eq!(dstv.header.check_profile(), Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn mistyped_profile_height() {
+        let mut dstv = Dstv::from_file("./tests/data/0008-SE0008.nc1").unwrap();
+        dstv.header.profile_height = Millimetres(330.0);
+        dstv.header.weight_by_meter = KilogramsPerMetre(49.1);
+        assert_eq!(
+            dstv.header.check_profile().unwrap(),
+            vec![
+                ProfileMismatch {
+                    field: "profile_height",
+                    expected: 300.0,
+                    actual: 330.0
+                },
+                ProfileMismatch {
+                    field: "weight_by_meter",
+                    expected: 42.2,
+                    actual: 49.1
+                },
+            ]
+        );
+        let findings = Validator::empty().with_rule(ProfileRule).validate(&dstv);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(
+            findings[0].message,
+            "`profile_height` is 330 but IPE 300 is 300"
+        );
+        assert_eq!(findings[1].severity, Severity::Warning);
+    }
+}
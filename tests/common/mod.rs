@@ -0,0 +1,43 @@
+//! Helpers shared by the integration tests. Not every test uses every helper.
+#![allow(dead_code)]
+
+use dstv::prelude::*;
+
+/// Asserts that two values are equal within the rounding of floating point arithmetic
+pub fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-6,
+        "{actual} is not close to {expected}"
+    );
+}
+
+/// Starts the header of a plate, 10 mm thick
+/// # Arguments
+/// * `piece` - The piece identification
+/// * `length` - The length of the plate in mm
+/// * `width` - The width of the plate in mm
+pub fn plate_header(piece: &str, length: f64, width: f64) -> HeaderBuilder {
+    HeaderBuilder::new()
+        .piece_identification(piece)
+        .length(length)
+        .plate(width, 10.0)
+}
+
+/// Starts the header of an IPE 300 beam
+/// # Arguments
+/// * `piece` - The piece identification
+/// * `length` - The length of the beam in mm
+pub fn beam_header(piece: &str, length: f64) -> HeaderBuilder {
+    HeaderBuilder::new()
+        .piece_identification(piece)
+        .profile("IPE300", CodeProfile::I)
+        .dimensions(300.0, 150.0, 10.7, 7.1, 15.0)
+        .length(length)
+}
+
+/// Builds a header and starts a part on it
+/// # Arguments
+/// * `header` - The header, e.g. from `plate_header` or `beam_header`
+pub fn part(header: HeaderBuilder) -> DstvBuilder {
+    DstvBuilder::new(header.build().unwrap())
+}
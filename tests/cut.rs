@@ -0,0 +1,124 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{part, plate_header};
+    use dstv::prelude::*;
+
+    fn points(polygon: &[Point]) -> Vec<(f64, f64)> {
+        polygon.iter().map(|p| (p.x, p.y)).collect()
+    }
+
+    #[test]
+    fn parse_cut_planes() {
+        let dstv = Dstv::from_file("./tests/data/SC-beam.nc").unwrap();
+        let cuts = dstv.cuts();
+        assert_eq!(cuts.len(), 2);
+        assert_eq!(cuts[0].get_facing(), &PartFace::Front);
+        assert_eq!(cuts[1].get_facing(), &PartFace::Top);
+        assert_eq!(cuts[0].point(), [900.0, 200.0, 50.0]);
+        let normal = cuts[0].normal();
+        assert!((normal[0] - 0.5f64.sqrt()).abs() < 1e-9);
+        assert!((normal[1] - 0.5f64.sqrt()).abs() < 1e-9);
+
+        // cuts without a flange code are still read
+        let cut = Cut::from_str("  10.00 0.00 0.00 1.00 0.00 0.00").unwrap();
+        assert_eq!(cut.fl_code, PartFace::Front);
+        assert_eq!(cut.sp_point_x, 10.0);
+    }
+
+    #[test]
+    fn cuts_trim_the_part_in_3d() {
+        let dstv = Dstv::from_file("./tests/data/SC-beam.nc").unwrap();
+        // the web end is cut at 45° through the top of the web
+        assert!(dstv.is_cut_away([990.0, 190.0, 50.0]));
+        assert!(!dstv.is_cut_away([990.0, 10.0, 50.0]));
+        // the front corners of the flanges at the start are snipped
+        assert!(dstv.is_cut_away([10.0, 200.0, 10.0]));
+        assert!(dstv.is_cut_away([10.0, 0.0, 10.0]));
+        assert!(!dstv.is_cut_away([10.0, 0.0, 90.0]));
+        // beyond the length of the part
+        assert!(dstv.is_cut_away([1001.0, 0.0, 50.0]));
+    }
+
+    #[test]
+    fn cuts_are_projected_on_the_faces() {
+        let dstv = Dstv::from_file("./tests/data/SC-beam.nc").unwrap();
+        assert_eq!(
+            points(&dstv.face_outlines(&PartFace::Front)[0]),
+            vec![
+                (0.0, 0.0),
+                (1000.0, 0.0),
+                (1000.0, 100.0),
+                (900.0, 200.0),
+                (0.0, 200.0)
+            ]
+        );
+        assert_eq!(
+            points(&dstv.face_outlines(&PartFace::Top)[0]),
+            vec![
+                (50.0, 0.0),
+                (900.0, 0.0),
+                (900.0, 100.0),
+                (0.0, 100.0),
+                (0.0, 50.0)
+            ]
+        );
+        assert_eq!(
+            points(&dstv.face_outlines(&PartFace::Bottom)[0]),
+            vec![
+                (50.0, 0.0),
+                (1000.0, 0.0),
+                (1000.0, 100.0),
+                (0.0, 100.0),
+                (0.0, 50.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn cut_parts_are_drawn_cut() {
+        let mut dstv = Dstv::from_file("./tests/data/SC-beam.nc").unwrap();
+        let svg = dstv.to_svg();
+        assert!(svg.contains("id=\"bottom\""));
+        assert!(svg.contains("id=\"top\""));
+        assert!(svg.contains("L 1000 100 L 900 200"));
+        // the old marker of the cut point is no longer drawn
+        assert!(!svg.contains("<line"));
+        assert!(dstv.cuts().iter().all(|cut| cut.to_svg().is_empty()));
+    }
+
+    #[test]
+    fn cuts_on_contours_are_overlaid() {
+        let mut dstv = part(plate_header("FP1", 300.0, 200.0))
+            .add_element(DstvElementType::Cut(
+                Cut::from_str("v 300.00 100.00 0.00 1.00 1.00 0.00").unwrap(),
+            ))
+            .build()
+            .unwrap();
+        assert_eq!(
+            points(&dstv.face_outlines(&PartFace::Front)[0]),
+            vec![
+                (0.0, 0.0),
+                (300.0, 0.0),
+                (300.0, 100.0),
+                (200.0, 200.0),
+                (0.0, 200.0)
+            ]
+        );
+        assert!(dstv.to_svg().contains(
+            "<polygon points=\"300,100 300,200 200,200\" fill=\"white\" stroke=\"red\" stroke-dasharray=\"4\" />"
+        ));
+    }
+
+    #[test]
+    fn mirror_cuts() {
+        let dstv = Dstv::from_file("./tests/data/SC-beam.nc").unwrap();
+        let mirrored = dstv.mirror(MirrorPlane::Vertical).unwrap();
+        let cut = mirrored.cuts()[1].clone();
+        assert_eq!(cut.point(), [50.0, 0.0, 100.0]);
+        assert_eq!(cut.nor_vec_z, 1.0);
+        assert!(mirrored.is_cut_away([10.0, 0.0, 90.0]));
+        assert!(!mirrored.is_cut_away([10.0, 0.0, 10.0]));
+    }
+}
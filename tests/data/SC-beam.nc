@@ -0,0 +1,33 @@
+ST
+** SC-beam.nc
+  ORDER-1
+  D-1
+  1
+  B-SC1
+  S355J2
+  2
+  IPE200
+  I
+     1000.00
+      200.00
+      100.00
+        8.50
+        5.60
+       12.00
+       22.40
+        0.77
+        0.00
+        0.00
+        0.00
+        0.00
+
+
+
+
+BO
+  v     500.00    100.00     22.00       0.00
+SC
+  v     900.00    200.00     50.00      1.00      1.00      0.00
+SC
+  o      50.00      0.00      0.00     -1.00      0.00     -1.00
+EN
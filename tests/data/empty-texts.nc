@@ -0,0 +1,35 @@
+ST
+** plate exported without drawing and phase
+  ORDER-7
+  
+
+  P7
+  S355J2
+  1
+  PL10*200
+  B
+    400.00
+    200.00
+     10.00
+     10.00
+     10.00
+      0.00
+     15.70
+      0.42
+      0.00
+      0.00
+      0.00
+      0.00
+
+  Schwelle
+
+
+AK
+  v      0.00u      0.00       0.00
+       400.00       0.00       0.00
+       400.00     200.00       0.00
+         0.00     200.00       0.00
+         0.00       0.00       0.00
+BO
+  v    100.00u     50.00      22.00
+EN
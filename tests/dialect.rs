@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+    use std::io::Cursor;
+
+    /// The 20 values of the header of a plate
+    const VALUES: &str = "  O1\n  D1\n  1\n  P1\n  S235JR\n  2\n  PL10*100\n  B\n   1000.00\n     10.00\n    100.00\n     10.00\n     10.00\n      0.00\n      7.85\n      0.00\n      0.00\n      0.00\n      0.00\n      0.00\n";
+    /// The blocks after the header
+    const BLOCKS: &str = "AK\n  v 0.00 0.00 0.00\n  v 1000.00 0.00 0.00\n  v 1000.00 100.00 0.00\n  v 0.00 100.00 0.00\n  v 0.00 0.00 0.00\nBO\n  v 50.00 50.00 22.00\nEN\n";
+
+    /// Reads a file as a string and as a stream, which must give the same part
+    fn read(source: &str) -> (Dstv, Dialect) {
+        let tree = SyntaxTree::parse(source);
+        assert_eq!(tree.to_string(), source);
+        let dstv = Dstv::from_str(source).unwrap();
+        let mut reader = DstvReader::new(Cursor::new(source));
+        assert_eq!(reader.dialect().unwrap(), tree.dialect());
+        assert_eq!(reader.into_dstv().unwrap(), dstv);
+        (dstv, tree.dialect())
+    }
+
+    #[test]
+    fn dialects_of_the_test_files() {
+        for (file, dialect) in [
+            ("0008-SE0004.nc1", Dialect::Standard),
+            ("P1.nc", Dialect::Standard),
+            ("P1565.nc", Dialect::Tekla),
+            ("RST37-2.nc", Dialect::Dstv1998),
+        ] {
+            let source = std::fs::read_to_string(format!("./tests/data/{file}")).unwrap();
+            assert_eq!(SyntaxTree::parse(&source).dialect(), dialect, "{file}");
+        }
+        assert_eq!(Dialect::Sds2.to_string(), "SDS/2");
+    }
+
+    #[test]
+    fn text_lines_may_be_left_out() {
+        let (dstv, dialect) = read(&format!("ST\n{VALUES}{BLOCKS}"));
+        assert_eq!(dialect, Dialect::Dstv1998);
+        assert_eq!(dstv.header.piece_identification, "P1");
+        assert_eq!(dstv.header.text1_info_on_piece, "");
+        assert_eq!(dstv.elements.len(), 2);
+        assert!(matches!(dstv.elements[0], DstvElementType::OuterBorder(_)));
+
+        // some of the text lines only
+        let (dstv, _) = read(&format!("ST\n{VALUES}  Pfette\n{BLOCKS}"));
+        assert_eq!(dstv.header.text1_info_on_piece, "Pfette");
+        assert_eq!(dstv.header.text2_info_on_piece, "");
+        assert_eq!(dstv.elements.len(), 2);
+    }
+
+    #[test]
+    fn comments_in_the_header() {
+        let values = VALUES.replace("  S235JR\n", "  S235JR\n** a note\n");
+        let source = format!(
+            "ST\n** exported by Advance Steel\n** second comment\n{values}\n\n\n\n{BLOCKS}"
+        );
+        let (dstv, dialect) = read(&source);
+        assert_eq!(dialect, Dialect::AdvanceSteel);
+        assert_eq!(dstv.header.steel_quality, "S235JR");
+        assert_eq!(dstv.header.length, 1000.0);
+        assert_eq!(dstv.elements.len(), 2);
+
+        let source = format!("ST\n** SDS/2 NC export\n{VALUES}\n\n\n\n{BLOCKS}");
+        assert_eq!(read(&source).1, Dialect::Sds2);
+        let source = format!("ST\n** Tekla Structures\n{VALUES}\n\n\n\n{BLOCKS}");
+        assert_eq!(read(&source).1, Dialect::Tekla);
+        let source = format!("ST\n{VALUES}\n\n\n\n{BLOCKS}");
+        assert_eq!(read(&source).1, Dialect::Standard);
+    }
+
+    #[test]
+    fn text_values_may_be_blank() {
+        let source = std::fs::read_to_string("./tests/data/empty-texts.nc").unwrap();
+        let (dstv, dialect) = read(&source);
+        assert_eq!(dialect, Dialect::Standard);
+        assert_eq!(dstv.header.order_identification, "ORDER-7");
+        assert_eq!(dstv.header.drawing_identification, "");
+        assert_eq!(dstv.header.phase_identification, "");
+        assert_eq!(dstv.header.piece_identification, "P7");
+        assert_eq!(dstv.header.quantity_of_pieces, 1);
+        assert_eq!(dstv.header.length, 400.0);
+        assert_eq!(dstv.header.text1_info_on_piece, "");
+        assert_eq!(dstv.header.text2_info_on_piece, "Schwelle");
+        assert_eq!(dstv.elements.len(), 2);
+
+        // the values are read by their position, so a blank line is a blank value
+        let values = VALUES.replace("  S235JR\n", "\n  S235JR\n");
+        assert!(Dstv::from_str(format!("ST\n{values}{BLOCKS}")).is_err());
+    }
+
+    #[test]
+    fn extra_header_lines_are_skipped() {
+        let source = format!("ST\n{VALUES}  T1\n  T2\n  T3\n  T4\n  T5\n{BLOCKS}");
+        let (dstv, dialect) = read(&source);
+        assert_eq!(dialect, Dialect::Standard);
+        assert_eq!(dstv.header.text4_info_on_piece, "T4");
+        assert_eq!(dstv.elements.len(), 2);
+    }
+
+    #[test]
+    fn headers_need_all_values() {
+        let values = VALUES
+            .lines()
+            .take(15)
+            .map(|line| format!("{line}\n"))
+            .collect::<String>();
+        assert!(Dstv::from_str(format!("ST\n{values}{BLOCKS}")).is_err());
+        assert!(Header::from_reader(Cursor::new(format!("ST\n{values}{BLOCKS}"))).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{part, plate_header};
+    use dstv::prelude::*;
+
+    fn plate(quantity: i32, holes: &[(f64, f64, f64)]) -> Dstv {
+        let header = plate_header("FP1", 300.0, 200.0).quantity(quantity);
+        holes
+            .iter()
+            .fold(part(header), |builder, (x, y, d)| {
+                builder.add_hole(PartFace::Front, *x, *y, *d)
+            })
+            .add_mark(PartFace::Front, 150.0, 100.0, "FP1")
+            .build()
+            .unwrap()
+    }
+
+    fn kinds(diff: &DstvDiff) -> Vec<ChangeKind> {
+        diff.elements.iter().map(|change| change.kind).collect()
+    }
+
+    #[test]
+    fn same_file_has_no_changes() {
+        let dstv = Dstv::from_file("./tests/data/0008-SE0004.nc1").unwrap();
+        let diff = dstv.diff(&dstv.clone(), &DiffOptions::default());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "");
+    }
+
+    #[test]
+    fn element_order_is_ignored() {
+        let dstv = Dstv::from_file("./tests/data/P1565.nc").unwrap();
+        let mut reordered = dstv.clone();
+        reordered.elements.reverse();
+        assert!(dstv.diff(&reordered, &DiffOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn rewritten_contour_is_the_same() {
+        let dstv = plate(1, &[]);
+        let mut rewritten = dstv.clone();
+        for element in rewritten.elements.iter_mut() {
+            if let DstvElementType::OuterBorder(border) = element {
+                // start at another point and walk the other way around
+                border.contour.pop();
+                border.contour.rotate_left(2);
+                border.contour.reverse();
+                let first = border.contour[0].clone();
+                border.contour.push(first);
+            }
+        }
+        assert!(dstv.diff(&rewritten, &DiffOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn header_changes() {
+        let old = plate(1, &[]);
+        let new = plate(4, &[]);
+        let diff = old.diff(&new, &DiffOptions::default());
+        assert_eq!(
+            diff.header,
+            vec![HeaderChange {
+                field: "quantity_of_pieces",
+                old: "1".to_string(),
+                new: "4".to_string(),
+            }]
+        );
+        assert!(diff.elements.is_empty());
+    }
+
+    #[test]
+    fn header_dimensions_are_compared_within_the_tolerance() {
+        let old = plate(1, &[]);
+        let mut new = old.clone();
+        new.header.length = Millimetres(300.04);
+        new.header.web_start_cut = Degrees(0.05);
+        assert!(old.diff(&new, &DiffOptions::default()).is_empty());
+        new.header.length = Millimetres(300.5);
+        let diff = old.diff(&new, &DiffOptions::default());
+        assert_eq!(
+            diff.header,
+            vec![HeaderChange {
+                field: "length",
+                old: "300.00".to_string(),
+                new: "300.50".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn saw_length_changes() {
+        let old = plate(1, &[]);
+        let mut new = old.clone();
+        new.header.saw_length = Some(Millimetres(298.0));
+        let diff = old.diff(&new, &DiffOptions::default());
+        assert_eq!(
+            diff.header,
+            vec![HeaderChange {
+                field: "saw_length",
+                old: "".to_string(),
+                new: "298.00".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn added_removed_moved_and_modified_holes() {
+        let old = plate(
+            1,
+            &[(50.0, 50.0, 22.0), (100.0, 50.0, 22.0), (150.0, 50.0, 22.0)],
+        );
+        let new = plate(
+            1,
+            &[
+                (50.0, 50.0, 22.0),
+                (100.05, 50.0, 22.0),
+                (150.0, 80.0, 22.0),
+                (250.0, 150.0, 18.0),
+            ],
+        );
+        let diff = old.diff(&new, &DiffOptions::default());
+        assert_eq!(kinds(&diff), vec![ChangeKind::Moved, ChangeKind::Added]);
+        assert_eq!(
+            diff.to_string(),
+            "v: moved hole Ø22 at (150, 50) -> hole Ø22 at (150, 80)\nv: added hole Ø18 at (250, 150)\n"
+        );
+
+        let new = plate(1, &[(50.0, 50.0, 22.0), (100.0, 50.0, 26.0)]);
+        let diff = old.diff(&new, &DiffOptions::default());
+        assert_eq!(
+            kinds(&diff),
+            vec![ChangeKind::Modified, ChangeKind::Removed]
+        );
+    }
+
+    #[test]
+    fn far_moves_are_removed_and_added() {
+        let old = plate(1, &[(50.0, 50.0, 22.0)]);
+        let new = plate(1, &[(250.0, 150.0, 22.0)]);
+        let options = DiffOptions {
+            max_move: 50.0,
+            ..DiffOptions::default()
+        };
+        let diff = old.diff(&new, &options);
+        assert_eq!(kinds(&diff), vec![ChangeKind::Removed, ChangeKind::Added]);
+        assert_eq!(diff.elements[0].face(), &PartFace::Front);
+    }
+
+    #[test]
+    fn changed_contour_and_mark() {
+        let old = plate(1, &[]);
+        let mut new = old.mirror(MirrorPlane::EndToEnd).unwrap();
+        assert!(old.diff(&new, &DiffOptions::default()).is_empty());
+
+        for element in new.elements.iter_mut() {
+            match element {
+                DstvElementType::OuterBorder(border) => {
+                    border.contour[1].x_coord = Millimetres(250.0)
+                }
+                DstvElementType::Numeration(mark) => mark.text = "FP2".to_string(),
+                _ => {}
+            }
+        }
+        let diff = old.diff(&new, &DiffOptions::default());
+        assert_eq!(
+            kinds(&diff),
+            vec![ChangeKind::Modified, ChangeKind::Modified]
+        );
+        assert!(matches!(
+            diff.elements[0].new,
+            Some(DstvElementType::OuterBorder(_))
+        ));
+        assert_eq!(
+            diff.elements[1].to_string(),
+            "modified mark `FP1` at (150, 100) -> mark `FP2` at (150, 100)"
+        );
+    }
+
+    #[test]
+    fn svg_overlay() {
+        let old = plate(1, &[(50.0, 50.0, 22.0)]);
+        let new = plate(1, &[(50.0, 80.0, 22.0), (250.0, 150.0, 18.0)]);
+        let svg = old.diff(&new, &DiffOptions::default()).to_svg(&new);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("id=\"front\""));
+        assert!(svg.contains("stroke=\"red\""));
+        assert_eq!(svg.matches("stroke=\"green\"").count(), 2);
+        assert!(!svg.contains("stroke=\"orange\""));
+    }
+}
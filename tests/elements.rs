@@ -56,6 +56,8 @@ mod tests {
                     assert_eq!(e.get_index(), 2);
                     numeration += 1;
                 },
+                DstvElementType::Custom(_) => panic!("no custom blocks are registered"),
+                DstvElementType::Unknown(_) => {},
             }
         }
         assert_eq!(outer_border, 1);
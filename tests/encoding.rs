@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+    use std::fs;
+    use std::io::Cursor;
+
+    /// A plate whose header holds text in Windows-1252, as written by German and
+    /// Scandinavian detailers
+    fn windows_1252_file() -> Vec<u8> {
+        let source = fs::read_to_string("./tests/data/P1.nc").unwrap();
+        let source =
+            source
+                .replacen("PROJECT-1", "Müller Straße", 1)
+                .replacen("A992", "S355J2 Bøjle", 1);
+        let mut bytes = Encoding::Latin1.encode(&source).unwrap();
+        // the euro sign only exists in Windows-1252
+        let end = bytes.windows(3).rposition(|w| w == b"EN\n").unwrap();
+        bytes.splice(end..end, b"** 5 \x80\n".iter().copied());
+        bytes
+    }
+
+    #[test]
+    fn encodings_are_detected() {
+        let bytes = windows_1252_file();
+        assert!(String::from_utf8(bytes.clone()).is_err());
+        assert_eq!(Encoding::detect(&bytes), Encoding::Windows1252);
+        assert_eq!(Encoding::detect("Müller".as_bytes()), Encoding::Utf8);
+        assert_eq!(Encoding::detect(b"PROJECT-1"), Encoding::Utf8);
+
+        let path = std::env::temp_dir().join("dstv-encoding-windows-1252.nc");
+        fs::write(&path, &bytes).unwrap();
+        let dstv = Dstv::from_file(&path).unwrap();
+        let (detected, encoding) = Dstv::from_file_detect(&path).unwrap();
+        let tree = SyntaxTree::from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(detected, dstv);
+        assert_eq!(encoding, Encoding::Windows1252);
+        assert_eq!(tree.encoding(), Encoding::Windows1252);
+        assert_eq!(tree.to_bytes().unwrap(), bytes);
+        assert_eq!(dstv.header.order_identification, "Müller Straße");
+        assert_eq!(dstv.header.steel_quality, "S355J2 Bøjle");
+        assert_eq!(Dstv::from_bytes(&bytes, Encoding::Latin1).unwrap(), dstv);
+        assert!(Dstv::from_bytes(&bytes, Encoding::Utf8).is_err());
+    }
+
+    #[test]
+    fn text_is_written_in_the_same_encoding() {
+        let bytes = windows_1252_file();
+        let source = Encoding::Windows1252.decode(&bytes).unwrap();
+        assert!(source.contains("** 5 €\n"));
+        let tree = SyntaxTree::from_bytes(&bytes, Encoding::Windows1252).unwrap();
+        assert_eq!(tree.to_string(), source);
+        assert_eq!(tree.to_bytes().unwrap(), bytes);
+        assert_eq!(SyntaxTree::parse(&source).encoding(), Encoding::Utf8);
+
+        let dstv = Dstv::from_bytes(&bytes, Encoding::Windows1252).unwrap();
+        let written = dstv.to_bytes(Encoding::Windows1252).unwrap();
+        assert!(written.windows(15).any(|w| w == b"  M\xfcller Stra\xdfe"));
+        assert_eq!(
+            Dstv::from_bytes(&written, Encoding::Windows1252).unwrap(),
+            dstv
+        );
+        let utf8 = dstv.to_bytes(Encoding::Utf8).unwrap();
+        assert!(String::from_utf8(utf8).unwrap().contains("Müller Straße"));
+
+        // the euro sign cannot be written in Latin-1
+        let error = tree.to_bytes_with_encoding(Encoding::Latin1).unwrap_err();
+        assert!(error.to_string().contains("€"));
+        assert_eq!(Encoding::Windows1252.encode("€ ä").unwrap(), b"\x80 \xe4");
+    }
+
+    #[test]
+    fn applied_changes_keep_the_encoding() {
+        let bytes = windows_1252_file();
+        let mut tree = SyntaxTree::from_bytes(&bytes, Encoding::Windows1252).unwrap();
+        let mut dstv = Dstv::from_syntax(&tree).unwrap();
+        dstv.header.quantity_of_pieces = 3;
+        tree.apply(&dstv);
+        assert_eq!(tree.encoding(), Encoding::Windows1252);
+        let written = tree.to_bytes().unwrap();
+        assert!(written.windows(15).any(|w| w == b"  M\xfcller Stra\xdfe"));
+        assert!(written.windows(6).any(|w| w == b"** 5 \x80"));
+        let reread = Dstv::from_bytes(&written, Encoding::Windows1252).unwrap();
+        assert_eq!(reread.header.quantity_of_pieces, 3);
+        assert_eq!(reread.header.steel_quality, "S355J2 Bøjle");
+    }
+
+    #[test]
+    fn streams_are_decoded_per_line() {
+        let bytes = windows_1252_file();
+        let header = Header::from_reader(Cursor::new(&bytes)).unwrap();
+        assert_eq!(header.order_identification, "Müller Straße");
+
+        let mut reader = DstvReader::with_encoding(Cursor::new(&bytes), Encoding::Latin1);
+        assert_eq!(reader.header().unwrap().steel_quality, "S355J2 Bøjle");
+        let mut reader = DstvReader::with_encoding(Cursor::new(&bytes), Encoding::Utf8);
+        assert!(reader.header().is_err());
+
+        let dstv = Dstv::from_reader(Cursor::new(&bytes)).unwrap();
+        assert_eq!(
+            dstv,
+            Dstv::from_bytes(&bytes, Encoding::Windows1252).unwrap()
+        );
+    }
+}
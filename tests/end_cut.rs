@@ -0,0 +1,121 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{assert_close, beam_header, part, plate_header};
+    use dstv::prelude::*;
+
+    fn beam(web_end_cut: f64, flange_start_cut: f64) -> Dstv {
+        part(beam_header("B1", 1000.0).end_cuts(0.0, web_end_cut, flange_start_cut, 0.0))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn default_outline_follows_the_end_cuts() {
+        let dstv = beam(45.0, -45.0);
+        let front = dstv
+            .header
+            .default_outline(&PartFace::Front)
+            .iter()
+            .map(|p| (*p.x_coord, *p.y_coord))
+            .collect::<Vec<_>>();
+        assert_eq!(front.len(), 5);
+        assert_eq!(front[0], (0.0, 0.0));
+        assert_close(front[1].0, 1000.0);
+        assert_close(front[2].0, 700.0);
+        assert_eq!(front[3], (0.0, 300.0));
+
+        // a negative angle shortens the part at the lower edge
+        let top = dstv.header.default_outline(&PartFace::Top);
+        assert_close(*top[0].x_coord, 150.0);
+        assert_close(*top[3].x_coord, 0.0);
+        assert_close(*top[1].x_coord, 1000.0);
+    }
+
+    #[test]
+    fn end_planes() {
+        let dstv = beam(45.0, -45.0);
+        let header = &dstv.header;
+        assert_close(header.end_plane_x(PartEnd::End, 300.0, 0.0), 700.0);
+        assert_close(header.end_plane_x(PartEnd::Start, 0.0, 0.0), 150.0);
+        assert_close(header.end_plane_x(PartEnd::Start, 300.0, 150.0), 0.0);
+        let normal = header.end_plane_normal(PartEnd::End);
+        assert_close(normal[0], 1.0 / 2f64.sqrt());
+        assert_close(normal[1], 1.0 / 2f64.sqrt());
+        assert_close(normal[2], 0.0);
+        let normal = header.end_plane_normal(PartEnd::Start);
+        assert_close(normal[0], -1.0 / 2f64.sqrt());
+        assert_close(normal[1], 0.0);
+        assert_close(normal[2], -1.0 / 2f64.sqrt());
+    }
+
+    #[test]
+    fn saw_cuts() {
+        let dstv = beam(15.0, 0.0);
+        let [start, end] = dstv.saw_cuts();
+        assert!(start.is_square());
+        assert_eq!(start.to_string(), "start: square");
+        assert_eq!(end.to_string(), "end: web 15°");
+        let compound = beam(15.0, 0.0).header.end_cut(PartEnd::End);
+        assert!(!compound.is_compound());
+        let mut header = dstv.header.clone();
+        header.flange_end_cut = Degrees(30.0);
+        assert_eq!(
+            header.end_cut(PartEnd::End).to_string(),
+            "end: web 15°, flange 30° (compound)"
+        );
+    }
+
+    #[test]
+    fn saw_length() {
+        let mut dstv = Dstv::from_file("./tests/data/P1.nc").unwrap();
+        assert_close(dstv.saw_length(), 6236.88);
+        dstv.header.saw_length = Some(Millimetres(6240.0));
+        assert_close(dstv.saw_length(), 6240.0);
+    }
+
+    #[test]
+    fn svg_shows_end_cuts() {
+        let mut dstv = Dstv::from_file("./tests/data/P1.nc").unwrap();
+        assert_eq!(dstv.header.web_end_cut, 15.0);
+        let outlines = dstv.default_outlines();
+        assert_eq!(outlines.len(), 1);
+        assert_eq!(outlines[0].get_facing(), &PartFace::Front);
+        assert!(dstv.to_svg().contains(&outlines[0].to_svg()));
+
+        let mut square = beam(0.0, 0.0);
+        assert!(square.default_outlines().is_empty());
+        assert!(!square.to_svg().contains("<path"));
+    }
+
+    #[test]
+    fn end_cuts_must_match_the_contour() {
+        let header = plate_header("FP1", 400.0, 100.0).end_cuts(0.0, 45.0, 0.0, 0.0);
+        let skewed = part(header.clone())
+            .outer_contour(
+                PartFace::Front,
+                vec![(0.0, 0.0), (400.0, 0.0), (300.0, 100.0), (0.0, 100.0)],
+            )
+            .build()
+            .unwrap();
+        let rule = EndCutRule::default();
+        assert!(rule.check(&skewed).is_empty());
+
+        let square = part(header)
+            .outer_contour(
+                PartFace::Front,
+                vec![(0.0, 0.0), (400.0, 0.0), (400.0, 100.0), (0.0, 100.0)],
+            )
+            .build()
+            .unwrap();
+        let findings = rule.check(&square);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "end-cut");
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert_eq!(
+            findings[0].message,
+            "web end cut is 45° in the header, but the contour is cut at 0.0°"
+        );
+    }
+}
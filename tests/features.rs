@@ -0,0 +1,175 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{assert_close, beam_header, part, plate_header};
+    use dstv::prelude::*;
+
+    fn beam() -> Dstv {
+        part(beam_header("B1", 1000.0))
+            .outer_contour(
+                PartFace::Front,
+                vec![
+                    (10.0, 0.0, 0.0),
+                    (400.0, 0.0, 0.0),
+                    (400.0, 20.0, -10.0),
+                    (410.0, 30.0, 0.0),
+                    (490.0, 30.0, -10.0),
+                    (500.0, 20.0, 0.0),
+                    (500.0, 0.0, 0.0),
+                    (1000.0, 0.0, 0.0),
+                    (900.0, 300.0, 0.0),
+                    (80.0, 300.0, 0.0),
+                    (80.0, 250.0, 0.0),
+                    (0.0, 250.0, 0.0),
+                    (0.0, 10.0, 0.0),
+                ],
+            )
+            .add_inner_contour(
+                PartFace::Front,
+                vec![
+                    (600.0, 100.0, 0.0),
+                    (750.0, 100.0, 0.0),
+                    (750.0, 200.0, 0.0),
+                    (600.0, 200.0, 0.0),
+                ],
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn beam_features() {
+        let features = beam().features();
+        assert_eq!(features.len(), 5);
+        match &features[0] {
+            ContourFeature::Notch(notch) => {
+                assert_eq!(notch.edge, FaceEdge::Lower);
+                assert_close(notch.position, 400.0);
+                assert_close(notch.length, 100.0);
+                assert_close(notch.depth, 30.0);
+                assert_close(notch.radius, 10.0);
+            }
+            feature => panic!("Expected a notch, got {feature:?}"),
+        }
+        match &features[1] {
+            ContourFeature::SkewedEndCut(cut) => {
+                assert_eq!(cut.end, PartEnd::End);
+                assert_close(cut.angle, (100.0f64 / 300.0).atan().to_degrees());
+                assert_close(cut.offset, 100.0);
+            }
+            feature => panic!("Expected a skewed end cut, got {feature:?}"),
+        }
+        assert_eq!(
+            features[2],
+            ContourFeature::Cope(Cope {
+                face: PartFace::Front,
+                end: PartEnd::Start,
+                edge: FaceEdge::Upper,
+                length: 80.0,
+                depth: 50.0,
+                radius: 0.0,
+            })
+        );
+        assert_eq!(
+            features[3],
+            ContourFeature::CornerSnip(CornerSnip {
+                face: PartFace::Front,
+                end: PartEnd::Start,
+                edge: FaceEdge::Lower,
+                length: 10.0,
+                depth: 10.0,
+            })
+        );
+        assert_eq!(
+            features[4],
+            ContourFeature::WebPenetration(Opening {
+                face: PartFace::Front,
+                x: 600.0,
+                y: 100.0,
+                length: 150.0,
+                height: 100.0,
+                radius: 0.0,
+            })
+        );
+    }
+
+    #[test]
+    fn rectangular_plate_has_no_features() {
+        let dstv = part(plate_header("B1", 300.0, 200.0)).build().unwrap();
+        assert!(dstv.features().is_empty());
+    }
+
+    #[test]
+    fn openings_outside_the_web() {
+        let dstv = part(plate_header("B1", 300.0, 200.0))
+            .add_inner_contour(
+                PartFace::Front,
+                vec![
+                    (100.0, 50.0, 0.0),
+                    (200.0, 50.0, 0.0),
+                    (200.0, 150.0, 0.0),
+                    (100.0, 150.0, 0.0),
+                ],
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            dstv.features(),
+            vec![ContourFeature::Opening(Opening {
+                face: PartFace::Front,
+                x: 100.0,
+                y: 50.0,
+                length: 100.0,
+                height: 100.0,
+                radius: 0.0,
+            })]
+        );
+    }
+
+    #[test]
+    fn copes_of_a_beam_file() {
+        let dstv = Dstv::from_file("./tests/data/0008-SE0008.nc1").unwrap();
+        let features = dstv.features();
+        let copes = features
+            .iter()
+            .filter_map(|feature| match feature {
+                ContourFeature::Cope(cope) => Some(cope),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(copes.len(), 4);
+        assert!(copes
+            .iter()
+            .all(|cope| cope.face == PartFace::Front && cope.radius == 10.0));
+        let penetrations = features
+            .iter()
+            .filter(|feature| matches!(feature, ContourFeature::WebPenetration(_)))
+            .count();
+        assert_eq!(penetrations, 2);
+        // the slots in the flanges are openings, not web penetrations
+        let openings = features
+            .iter()
+            .filter_map(|feature| match feature {
+                ContourFeature::Opening(opening) => Some(opening.face.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(openings, vec![PartFace::Top, PartFace::Bottom]);
+    }
+
+    #[test]
+    fn miter_cuts_on_flanges() {
+        let dstv = Dstv::from_file("./tests/data/0008-SE0009.nc1").unwrap();
+        let angles = dstv
+            .features()
+            .iter()
+            .filter(|feature| feature.face() == &PartFace::Top)
+            .map(|feature| match feature {
+                ContourFeature::SkewedEndCut(cut) => (cut.end, cut.angle.round()),
+                feature => panic!("Expected a skewed end cut, got {feature:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(angles, vec![(PartEnd::End, 30.0), (PartEnd::Start, 45.0)]);
+    }
+}
@@ -0,0 +1,147 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{beam_header, part, plate_header};
+    use dstv::prelude::*;
+
+    fn plate(piece: &str, hole_x: f64) -> Dstv {
+        part(plate_header(piece, 300.0, 200.0))
+            .add_hole(PartFace::Front, hole_x, 50.0, 22.0)
+            .add_hole(PartFace::Front, 250.0, 150.0, 22.0)
+            .add_mark(PartFace::Front, 150.0, 100.0, piece)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn identifications_and_marks_are_ignored() {
+        let a = plate("FP1", 50.0);
+        let mut b = plate("FP2", 50.0);
+        b.header.order_identification = "OTHER".to_string();
+        b.header.quantity_of_pieces = 12;
+        b.header.profile = "BL10X200".to_string();
+        assert_eq!(
+            a.fingerprint(FINGERPRINT_TOLERANCE).unwrap(),
+            b.fingerprint(FINGERPRINT_TOLERANCE).unwrap()
+        );
+    }
+
+    #[test]
+    fn profile_spelling_is_ignored() {
+        let beam = |profile: &str| {
+            let header = beam_header("B1", 1000.0)
+                .profile(profile, CodeProfile::I)
+                .dimensions(190.0, 200.0, 10.0, 6.5, 18.0);
+            part(header).build().unwrap()
+        };
+        assert_eq!(
+            beam("HEA200").fingerprint(FINGERPRINT_TOLERANCE).unwrap(),
+            beam("HE 200 A").fingerprint(FINGERPRINT_TOLERANCE).unwrap()
+        );
+    }
+
+    #[test]
+    fn element_order_and_contour_start_are_ignored() {
+        let a = Dstv::from_file("./tests/data/P1565.nc").unwrap();
+        let mut b = a.clone();
+        b.elements.reverse();
+        for element in b.elements.iter_mut() {
+            if let DstvElementType::OuterBorder(border) = element {
+                border.contour.pop();
+                border.contour.rotate_left(1);
+                border.contour.reverse();
+                let first = border.contour[0].clone();
+                border.contour.push(first);
+            }
+        }
+        assert_eq!(
+            a.fingerprint(FINGERPRINT_TOLERANCE).unwrap(),
+            b.fingerprint(FINGERPRINT_TOLERANCE).unwrap()
+        );
+    }
+
+    #[test]
+    fn geometry_changes_the_fingerprint() {
+        let a = plate("FP1", 50.0);
+        assert_eq!(
+            a.fingerprint(FINGERPRINT_TOLERANCE).unwrap(),
+            plate("FP1", 50.02)
+                .fingerprint(FINGERPRINT_TOLERANCE)
+                .unwrap()
+        );
+        assert_ne!(
+            a.fingerprint(FINGERPRINT_TOLERANCE).unwrap(),
+            plate("FP1", 60.0)
+                .fingerprint(FINGERPRINT_TOLERANCE)
+                .unwrap()
+        );
+        let mut grade = a.clone();
+        grade.header.steel_quality = "S355J2".to_string();
+        assert_ne!(
+            a.fingerprint(FINGERPRINT_TOLERANCE).unwrap(),
+            grade.fingerprint(FINGERPRINT_TOLERANCE).unwrap()
+        );
+        let mut length = a.clone();
+        length.header.length = Millimetres(310.0);
+        assert_ne!(
+            a.fingerprint(FINGERPRINT_TOLERANCE).unwrap(),
+            length.fingerprint(FINGERPRINT_TOLERANCE).unwrap()
+        );
+    }
+
+    #[test]
+    fn fingerprint_is_stable() {
+        let dstv = Dstv::from_file("./tests/data/0008-SE0004.nc1").unwrap();
+        let fingerprint = dstv.fingerprint(FINGERPRINT_TOLERANCE).unwrap();
+        assert_eq!(fingerprint.to_string().len(), 16);
+        let reparsed = Dstv::from_str(dstv.to_dstv()).unwrap();
+        assert_eq!(
+            reparsed.fingerprint(FINGERPRINT_TOLERANCE).unwrap(),
+            fingerprint
+        );
+    }
+
+    #[test]
+    fn group_parts() {
+        let parts = vec![
+            plate("FP1", 50.0),
+            plate("FP2", 60.0),
+            plate("FP3", 50.0),
+            Dstv::from_file("./tests/data/P1565.nc").unwrap(),
+            plate("FP4", 60.0),
+        ];
+        let groups = group_by_fingerprint(&parts, FINGERPRINT_TOLERANCE).unwrap();
+        let pieces = groups
+            .iter()
+            .map(|(_, group)| {
+                group
+                    .iter()
+                    .map(|part| part.header.piece_identification.as_str())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pieces,
+            vec![vec!["FP1", "FP3"], vec!["FP2", "FP4"], vec!["P1565"]]
+        );
+        assert_eq!(
+            groups[0].0,
+            parts[0].fingerprint(FINGERPRINT_TOLERANCE).unwrap()
+        );
+    }
+
+    #[test]
+    fn tolerance_must_be_positive() {
+        let dstv = plate("FP1", 50.0);
+        for tolerance in [0.0, -0.1, f64::NAN, f64::INFINITY] {
+            assert!(dstv.fingerprint(tolerance).is_err());
+            assert!(dstv.canonical_geometry(tolerance).is_err());
+            assert!(group_by_fingerprint(&[], tolerance).is_err());
+        }
+        assert_eq!(
+            dstv.fingerprint(0.0).unwrap_err().to_string(),
+            "Could not parse DSTV element: \"The fingerprint tolerance must be positive, got 0\""
+        );
+    }
+}
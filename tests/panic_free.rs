@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+    use dstv::{get_f64_from_str, get_str_from_f64};
+    use std::fs;
+    use std::io::Cursor;
+
+    /// The number of generated inputs per property
+    const CASES: usize = 2000;
+
+    /// Words that make generated input look like a DSTV file
+    const TOKENS: &[&str] = &[
+        "ST", "EN", "AK", "IK", "BO", "SI", "SC", "KA", "XX", "**", "v", "o", "u", "h", "s", "0",
+        "0.00", "-1", "12.5o", "22.00", "1e308", "-1e308", "NaN", "inf", "-0", "1.0s", "t", "w",
+        "l", "ü", "€", "\t", "\r", "",
+    ];
+
+    /// A small deterministic xorshift generator, so failures can be reproduced
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n.max(1) as u64) as usize
+        }
+
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            items[self.below(items.len())]
+        }
+    }
+
+    /// Returns the files in the test data, read lossily as they may not be UTF-8
+    fn fixtures() -> Vec<String> {
+        fs::read_dir("./tests/data")
+            .unwrap()
+            .map(|entry| fs::read(entry.unwrap().path()).unwrap())
+            .map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+            .collect()
+    }
+
+    /// Returns a line of random DSTV-like words
+    fn token_line(rng: &mut Rng) -> String {
+        let indent = ["", "  ", " "][rng.below(3)];
+        let words = (0..rng.below(10))
+            .map(|_| rng.pick(TOKENS))
+            .collect::<Vec<_>>();
+        format!("{indent}{}", words.join(" "))
+    }
+
+    /// Mutates a file by truncating, deleting, duplicating or inserting lines and characters
+    fn mutate(rng: &mut Rng, source: &str) -> String {
+        let mut lines = source.split('\n').map(str::to_string).collect::<Vec<_>>();
+        for _ in 0..=rng.below(4) {
+            let i = rng.below(lines.len());
+            match rng.below(6) {
+                0 => lines.truncate(i),
+                1 if i < lines.len() => {
+                    lines.remove(i);
+                }
+                2 if i < lines.len() => {
+                    let line = lines[i].clone();
+                    lines.insert(i, line);
+                }
+                3 => lines.insert(i, token_line(rng)),
+                4 => lines.insert(i, rng.pick(TOKENS).to_string()),
+                _ if i < lines.len() => {
+                    let mut chars = lines[i].chars().collect::<Vec<_>>();
+                    let at = rng.below(chars.len() + 1);
+                    chars.insert(
+                        at,
+                        rng.pick(&["ü", "€", "v", ".", "-", " ", "\r"])
+                            .chars()
+                            .next()
+                            .unwrap_or(' '),
+                    );
+                    lines[i] = chars.into_iter().collect();
+                }
+                _ => {}
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Runs every entry point of the parser on an input, which must never panic
+    fn parse_all(input: &str) {
+        let tree = SyntaxTree::parse(input);
+        assert_eq!(tree.to_string(), input);
+        if let Ok(dstv) = Dstv::from_str(input) {
+            assert_eq!(Dstv::from_syntax(&tree).ok(), Some(dstv.clone()));
+            dstv.to_dstv();
+            dstv.clone().to_svg();
+        }
+        for element in DstvReader::new(Cursor::new(input)) {
+            let _ = element;
+        }
+        let _ = Header::from_reader(Cursor::new(input));
+    }
+
+    #[test]
+    fn random_bytes_do_not_panic() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..CASES {
+            let bytes = (0..rng.below(400))
+                .map(|_| rng.next() as u8)
+                .collect::<Vec<_>>();
+            parse_all(&String::from_utf8_lossy(&bytes));
+        }
+    }
+
+    #[test]
+    fn random_tokens_do_not_panic() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        let header = fixtures()
+            .into_iter()
+            .find(|source| source.contains("0008-SE0004"))
+            .unwrap()
+            .split('\n')
+            .take(26)
+            .collect::<Vec<_>>()
+            .join("\n");
+        for _ in 0..CASES {
+            let body = (0..rng.below(30))
+                .map(|_| token_line(&mut rng))
+                .collect::<Vec<_>>()
+                .join("\n");
+            parse_all(&body);
+            parse_all(&format!("{header}\n{body}"));
+        }
+    }
+
+    #[test]
+    fn mutated_files_do_not_panic() {
+        let mut rng = Rng(0xd1b5_4a32_d192_ed03);
+        let fixtures = fixtures();
+        for _ in 0..CASES {
+            let source = &fixtures[rng.below(fixtures.len())];
+            parse_all(&mutate(&mut rng, source));
+        }
+    }
+
+    #[test]
+    fn malformed_values_are_errors() {
+        let source = fs::read_to_string("./tests/data/0008-SE0004.nc1").unwrap();
+        let header = source.split('\n').take(26).collect::<Vec<_>>().join("\n");
+        for block in ["BO", "SI", "SC", "KA"] {
+            let source = format!("{header}\n{block}\n{block}\n  v 1e999 0 0\nEN\n");
+            let dstv = Dstv::from_str(&source).unwrap();
+            assert!(dstv.elements.is_empty(), "{block}");
+            assert_eq!(SyntaxTree::parse(&source).to_string(), source);
+        }
+        let border = OuterBorder::from_str("").unwrap();
+        assert!(border.contour.is_empty());
+        assert_eq!(border.get_facing(), &PartFace::Front);
+        let border = InnerBorder::from_str("  v 0 0 0\n  v 10 0 0").unwrap();
+        assert_eq!(border.contour.len(), 2);
+        assert!(get_f64_from_str(Some("inf"), "x").is_err());
+        assert_eq!(get_str_from_f64(f64::NAN), "       NaN");
+    }
+}
@@ -0,0 +1,94 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::assert_close;
+    use std::f64::consts::PI;
+
+    use dstv::prelude::*;
+
+    fn outer_borders(dstv: &Dstv) -> Vec<&OuterBorder> {
+        dstv.elements
+            .iter()
+            .filter_map(|element| match element {
+                DstvElementType::OuterBorder(e) => Some(e),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn polygon_area_and_perimeter() {
+        let dstv = Dstv::from_file("./tests/data/product2.NC1").unwrap();
+        let border = outer_borders(&dstv)[0];
+        assert_close(border.area(), 320.0 * 300.0 - 50.0 * 50.0);
+        assert_close(
+            border.perimeter(),
+            270.0 + 300.0 + 270.0 + 200.0 + 2.0 * 50.0 * 2f64.sqrt(),
+        );
+    }
+
+    #[test]
+    fn circular_area_with_exact_arcs() {
+        let dstv = Dstv::from_file("./tests/data/P2663.nc").unwrap();
+        let border = outer_borders(&dstv)[0];
+        assert_close(border.area(), PI * 275.0 * 275.0);
+        assert_close(border.perimeter(), 2.0 * PI * 275.0);
+        let centroid = border.centroid().unwrap();
+        assert_close(centroid.x, 275.0);
+        assert_close(centroid.y, 275.0);
+    }
+
+    #[test]
+    fn rounded_cope_segments() {
+        let dstv = Dstv::from_file("./tests/data/0008-SE0008.nc1").unwrap();
+        let border = outer_borders(&dstv)[0];
+        let arcs = border
+            .segments()
+            .into_iter()
+            .filter(|segment| segment.is_arc())
+            .collect::<Vec<_>>();
+        assert_eq!(arcs.len(), 4);
+        for arc in arcs {
+            assert_close(arc.length(), 5.0 * PI);
+        }
+        let cope = 2.0 * 71.75 * 37.0 + 2.0 * 71.75 * 35.0 - 4.0 * (100.0 - 25.0 * PI);
+        assert_close(border.area(), 954.5 * 300.0 - cope);
+    }
+
+    #[test]
+    fn net_area_and_weight_of_plate() {
+        let dstv = Dstv::from_file("./tests/data/P2663.nc").unwrap();
+        let holes = 6.0 * PI * 33.0 * 33.0 / 4.0 + PI * 18.0 * 18.0 / 4.0;
+        let net_area = PI * 275.0 * 275.0 - PI * 150.0 * 150.0 - holes;
+        assert_close(dstv.net_area(&PartFace::Front), net_area);
+        assert_close(
+            dstv.net_weight(STEEL_DENSITY),
+            net_area * 20.0 * STEEL_DENSITY * 1e-9,
+        );
+        // all holes are placed symmetrically, except for the small one
+        let small_hole = PI * 18.0 * 18.0 / 4.0;
+        let cog = dstv.centre_of_gravity().unwrap();
+        assert_close(cog.x, 275.0 + small_hole * (275.0 - 135.90) / net_area);
+        assert_close(cog.y, 275.0 + small_hole * (275.0 - 135.91) / net_area);
+    }
+
+    #[test]
+    fn net_weight_of_profile() {
+        let dstv = Dstv::from_file("./tests/data/P1.nc").unwrap();
+        let gross = 65.479 * 6236.88 / 1000.0;
+        assert_close(dstv.gross_weight(STEEL_DENSITY), gross);
+        let holes = 3.0 * PI * 20.64 * 20.64 / 4.0 * 11.43 * STEEL_DENSITY * 1e-9;
+        assert_close(dstv.net_weight(STEEL_DENSITY), gross - holes);
+    }
+
+    #[test]
+    fn cut_length() {
+        let dstv = Dstv::from_file("./tests/data/P2663.nc").unwrap();
+        let holes = 6.0 * PI * 33.0 + PI * 18.0;
+        assert_close(
+            dstv.cut_length(),
+            2.0 * PI * 275.0 + 2.0 * PI * 150.0 + holes,
+        );
+    }
+}
@@ -0,0 +1,117 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{part, plate_header};
+    use dstv::prelude::*;
+
+    fn plate() -> Dstv {
+        part(plate_header("P1", 1000.0, 200.0))
+            .add_hole(PartFace::Front, 50.0, 50.0, 22.0)
+            .add_hole(PartFace::Front, 950.0, 50.0, 22.0)
+            .add_hole(PartFace::Front, 500.0, 150.0, 18.0)
+            .add_slot(PartFace::Front, 300.0, 100.0, 18.0, 40.0, 0.0, 0.0)
+            .add_inner_contour(
+                PartFace::Front,
+                vec![(600.0, 50.0), (700.0, 50.0), (700.0, 150.0), (600.0, 150.0)],
+            )
+            .add_mark(PartFace::Front, 100.0, 150.0, "P1")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn typed_queries() {
+        let dstv = plate();
+        assert_eq!(dstv.holes().count(), 3);
+        assert_eq!(dstv.holes_on(&PartFace::Front).count(), 3);
+        assert_eq!(dstv.holes_on(&PartFace::Behind).count(), 0);
+        assert_eq!(dstv.slots().count(), 1);
+        assert_eq!(dstv.marks().next().unwrap().text, "P1");
+        assert_eq!(dstv.elements_of::<Slot>().count(), 1);
+        assert_eq!(dstv.elements_on::<Numeration>(&PartFace::Top).count(), 0);
+        assert!(dstv.outer_border(&PartFace::Front).is_some());
+        assert!(dstv.outer_border(&PartFace::Top).is_none());
+        assert_eq!(dstv.inner_borders(&PartFace::Front).count(), 1);
+    }
+
+    #[test]
+    fn filters() {
+        let dstv = plate();
+        let large = dstv
+            .holes_with_diameter(20.0..=24.0)
+            .map(|hole| *hole.x_coord)
+            .collect::<Vec<_>>();
+        assert_eq!(large, vec![50.0, 950.0]);
+        assert_eq!(dstv.holes_with_diameter(..20.0).count(), 1);
+
+        let start = [
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 0.0),
+            Point::new(100.0, 200.0),
+            Point::new(0.0, 200.0),
+        ];
+        let holes = dstv.holes_in(&PartFace::Front, &start).collect::<Vec<_>>();
+        assert_eq!(holes.len(), 1);
+        assert_eq!(holes[0].x_coord, 50.0);
+    }
+
+    #[test]
+    fn nearest_hole() {
+        let dstv = plate();
+        let index = dstv.spatial_index(&PartFace::Front);
+        let nearest = |x, y| {
+            index
+                .nearest_hole(&Point::new(x, y))
+                .map(|hole| (*hole.x_coord, *hole.y_coord))
+        };
+        assert_eq!(nearest(0.0, 0.0), Some((50.0, 50.0)));
+        assert_eq!(nearest(480.0, 10.0), Some((500.0, 150.0)));
+        assert_eq!(nearest(2000.0, 50.0), Some((950.0, 50.0)));
+        assert!(dstv
+            .spatial_index(&PartFace::Top)
+            .nearest_hole(&Point::new(0.0, 0.0))
+            .is_none());
+
+        let around = index.holes_within(&Point::new(520.0, 100.0), 500.0);
+        assert_eq!(
+            around.iter().map(|hole| *hole.x_coord).collect::<Vec<_>>(),
+            vec![500.0, 950.0, 50.0]
+        );
+    }
+
+    #[test]
+    fn nearest_hole_matches_brute_force() {
+        let dstv = plate();
+        let index = dstv.spatial_index(&PartFace::Front);
+        for i in 0..50 {
+            let point = Point::new(i as f64 * 23.0 - 50.0, (i * 37 % 250) as f64 - 25.0);
+            let expected = dstv
+                .holes()
+                .map(|hole| hole.center().distance(&point))
+                .fold(f64::INFINITY, f64::min);
+            let found = index
+                .nearest_hole(&point)
+                .unwrap()
+                .center()
+                .distance(&point);
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn point_in_contour() {
+        let dstv = plate();
+        let index = dstv.spatial_index(&PartFace::Front);
+        assert!(index.is_material(&Point::new(200.0, 100.0)));
+        assert!(!index.in_outline(&Point::new(1100.0, 100.0)));
+        assert!(index.in_cut_out(&Point::new(650.0, 100.0)));
+        assert!(!index.is_material(&Point::new(650.0, 100.0)));
+        assert!(index.hole_at(&Point::new(55.0, 55.0)).is_some());
+        assert!(!index.is_material(&Point::new(55.0, 55.0)));
+        // the slot runs from x = 300 to x = 340 with rounded ends
+        assert!(index.slot_at(&Point::new(345.0, 100.0)).is_some());
+        assert!(index.slot_at(&Point::new(345.0, 108.0)).is_none());
+        assert!(index.is_material(&Point::new(350.0, 100.0)));
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+    use std::fs;
+    use std::io::Cursor;
+
+    #[test]
+    fn headers_are_read_without_the_elements() {
+        for entry in fs::read_dir("./tests/data").unwrap() {
+            let path = entry.unwrap().path();
+            if let Ok(dstv) = Dstv::from_file(&path) {
+                assert_eq!(Dstv::read_header(&path).unwrap(), dstv.header, "{path:?}");
+            }
+        }
+        let header = Dstv::read_header("./tests/data/0008-SE0004.nc1").unwrap();
+        assert_eq!(header.order_identification, "0008");
+        assert_eq!(header.profile, "UPE 200");
+        assert_eq!(header.length, 322.25);
+        assert_eq!(header.quantity_of_pieces, 1);
+    }
+
+    #[test]
+    fn headers_are_read_from_streams() {
+        let source = fs::read_to_string("./tests/data/0008-SE0004.nc1").unwrap();
+        // everything after the header may be missing or broken
+        let header = source.lines().take(27).collect::<Vec<_>>().join("\n") + "\n  v\u{0}\u{0}";
+        let header = Header::from_reader(Cursor::new(header)).unwrap();
+        assert_eq!(header.piece_identification, "0008-SE0004");
+
+        assert!(Header::from_reader(Cursor::new("ST\n  O1\n")).is_err());
+        assert!(Dstv::read_header("./tests/data/missing.nc").is_err());
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+    use std::fs;
+    use std::io::{BufReader, Cursor, Read};
+
+    /// A stream that fails on every read, standing in for the rest of a large file
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "connection reset",
+            ))
+        }
+    }
+
+    #[test]
+    fn streams_read_like_strings() {
+        for entry in fs::read_dir("./tests/data").unwrap() {
+            let path = entry.unwrap().path();
+            if let Ok(dstv) = Dstv::from_file(&path) {
+                let file = BufReader::new(fs::File::open(&path).unwrap());
+                assert_eq!(Dstv::from_reader(file).unwrap(), dstv, "{path:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn elements_are_read_one_at_a_time() {
+        let file = fs::File::open("./tests/data/0008-SE0004.nc1").unwrap();
+        let mut reader = DstvReader::new(BufReader::new(file));
+        assert_eq!(reader.header().unwrap().piece_identification, "0008-SE0004");
+        let first = reader.next().unwrap().unwrap();
+        assert!(matches!(first, DstvElementType::OuterBorder(_)));
+        let holes = reader
+            .filter_map(Result::ok)
+            .filter(|element| matches!(element, DstvElementType::Hole(_)))
+            .count();
+        assert_eq!(holes, 2);
+    }
+
+    #[test]
+    fn reading_stops_after_the_header() {
+        let source = fs::read_to_string("./tests/data/0008-SE0004.nc1").unwrap();
+        let header = source.lines().take(26).collect::<Vec<_>>().join("\n") + "\n";
+        let stream = BufReader::new(Cursor::new(header).chain(Failing));
+        let mut reader = DstvReader::new(stream);
+        assert_eq!(reader.header().unwrap().profile, "UPE 200");
+
+        // reading on reaches the failing part of the stream
+        let error = reader.next().unwrap().unwrap_err();
+        assert!(error.to_string().contains("connection reset"));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn invalid_headers_end_the_stream() {
+        let mut reader = DstvReader::new(Cursor::new("ST\n  O1\nEN\n"));
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+        assert!(Dstv::from_reader(Cursor::new("ST\n  O1\nEN\n")).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+    use std::io::Cursor;
+
+    /// A weld seam, written by some vendors in an `XW` block
+    #[derive(Clone, Debug, PartialEq)]
+    struct Weld {
+        fl_code: PartFace,
+        x_coord: f64,
+        length: f64,
+    }
+
+    impl DstvElement for Weld {
+        fn from_block(block: &Block) -> Result<Self, ParseDstvError> {
+            let line = block.first_line();
+            let values = line.split_whitespace().collect::<Vec<_>>();
+            if values.len() != 3 {
+                return Err(ParseDstvError::new(format!("Invalid weld: `{line}`")));
+            }
+            let number = |value: &str| {
+                value
+                    .parse::<f64>()
+                    .map_err(|e| ParseDstvError::from_err("Invalid weld", e))
+            };
+            Ok(Self {
+                fl_code: values[0].parse()?,
+                x_coord: number(values[1])?,
+                length: number(values[2])?,
+            })
+        }
+
+        fn to_svg(&self) -> String {
+            String::new()
+        }
+
+        fn write(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+            write!(
+                out,
+                "XW\n  {} {:>10.2} {:>10.2}\n",
+                self.fl_code.code(),
+                self.x_coord,
+                self.length
+            )
+        }
+
+        fn get_facing(&self) -> &PartFace {
+            &self.fl_code
+        }
+    }
+
+    fn registry() -> ElementRegistry {
+        ElementRegistry::new().register_element::<Weld>("XW")
+    }
+
+    const SOURCE: &str = "ST\n  O1\n  D1\n  1\n  P1\n  S235JR\n  2\n  PL10*100\n  B\n   1000.00\n     10.00\n    100.00\n     10.00\n     10.00\n      0.00\n      7.85\n      0.00\n      0.00\n      0.00\n      0.00\n      0.00\n\n\n\n\nBO\n  v     50.00     50.00     22.00\nXW\n  v     100.00     250.00\nZZ\n  v vendor data\n  more data\nEN\n";
+
+    #[test]
+    fn registered_blocks_are_custom_elements() {
+        let dstv = Dstv::from_str_with_registry(SOURCE, &registry()).unwrap();
+        assert_eq!(dstv.elements.len(), 3);
+        let weld = match &dstv.elements[1] {
+            DstvElementType::Custom(element) => element.as_any().downcast_ref::<Weld>(),
+            _ => None,
+        }
+        .unwrap();
+        assert_eq!(weld.x_coord, 100.0);
+        assert_eq!(weld.length, 250.0);
+        assert_eq!(dstv.elements[1].get_facing(), &PartFace::Front);
+        assert_eq!(dstv.elements[1].clone(), dstv.elements[1]);
+        assert!(registry().is_registered("XW"));
+        assert!(!registry().is_registered("ZZ"));
+
+        let written = Dstv::from_str_with_registry(dstv.to_dstv(), &registry()).unwrap();
+        assert_eq!(written, dstv);
+    }
+
+    #[test]
+    fn unknown_blocks_are_kept() {
+        let dstv = Dstv::from_str(SOURCE).unwrap();
+        let unknown = dstv
+            .elements
+            .iter()
+            .filter_map(|element| match element {
+                DstvElementType::Unknown(block) => Some(block),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(unknown.len(), 2);
+        assert_eq!(unknown[0].code, "XW");
+        assert_eq!(unknown[1].code, "ZZ");
+        assert_eq!(unknown[1].lines, vec!["  v vendor data", "  more data"]);
+        assert_eq!(unknown[1].fl_code, PartFace::Front);
+        assert_eq!(unknown[1].to_dstv(), "ZZ\n  v vendor data\n  more data\n");
+        assert_eq!(Dstv::from_str(dstv.to_dstv()).unwrap(), dstv);
+
+        let contours = Dstv::from_file("./tests/data/P1.nc").unwrap();
+        assert!(contours.elements.iter().any(
+            |element| matches!(element, DstvElementType::Unknown(block) if block.code == "KO")
+        ));
+    }
+
+    #[test]
+    fn unknown_blocks_can_be_skipped() {
+        let registry = registry().skip_unknown();
+        let dstv = Dstv::from_str_with_registry(SOURCE, &registry).unwrap();
+        assert_eq!(dstv.elements.len(), 2);
+        assert!(!dstv
+            .elements
+            .iter()
+            .any(|element| matches!(element, DstvElementType::Unknown(_))));
+    }
+
+    #[test]
+    fn registry_reads_encoded_files() {
+        // the order identification `Ö1` in Windows-1252
+        let bytes = SOURCE.replacen("O1", "\u{d6}1", 1);
+        let bytes = Encoding::Windows1252.encode(&bytes).unwrap();
+        let dstv =
+            Dstv::from_bytes_with_registry(&bytes, Encoding::Windows1252, &registry()).unwrap();
+        assert_eq!(dstv.header.order_identification, "\u{d6}1");
+        assert!(matches!(dstv.elements[1], DstvElementType::Custom(_)));
+
+        let path = std::env::temp_dir().join("dstv-registry-windows-1252.nc");
+        std::fs::write(&path, &bytes).unwrap();
+        let read = Dstv::from_file_with_registry(&path, &registry());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.unwrap(), dstv);
+    }
+
+    #[test]
+    fn reader_uses_the_registry() {
+        let mut reader = DstvReader::new(Cursor::new(SOURCE));
+        reader.set_registry(registry());
+        let elements = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(elements.len(), 3);
+        assert!(matches!(elements[1], DstvElementType::Custom(_)));
+        assert!(matches!(elements[2], DstvElementType::Unknown(_)));
+    }
+
+    #[test]
+    fn syntax_tree_keeps_custom_elements() {
+        let mut tree = SyntaxTree::parse_with_registry(SOURCE, &registry());
+        let mut dstv = Dstv::from_syntax(&tree).unwrap();
+        if let DstvElementType::Hole(hole) = &mut dstv.elements[0] {
+            hole.x_coord = Millimetres(75.0);
+        }
+        tree.apply(&dstv);
+        let written = tree.to_string();
+        assert!(written.contains("XW\n  v     100.00     250.00\n"));
+        assert!(written.contains("ZZ\n  v vendor data\n  more data\n"));
+        assert_eq!(
+            Dstv::from_str_with_registry(&written, &registry()).unwrap(),
+            dstv
+        );
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+
+    /// Asserts that a value is within a relative tolerance of its tabulated value
+    fn assert_table(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance * expected,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn rolled_i_section() {
+        // IPE 300
+        let dstv = Dstv::from_file("./tests/data/0008-SE0008.nc1").unwrap();
+        let section = dstv.header.section_properties().unwrap();
+        assert_table(section.area, 5381.0, 0.005);
+        assert_table(section.second_moment_y, 8356e4, 0.005);
+        assert_table(section.second_moment_z, 603.8e4, 0.005);
+        assert_table(section.elastic_modulus_y, 557.1e3, 0.005);
+        assert_table(section.elastic_modulus_z, 80.5e3, 0.005);
+        assert_table(section.plastic_modulus_y, 628.4e3, 0.005);
+        assert_table(section.plastic_modulus_z, 125.2e3, 0.005);
+        assert_table(section.radius_of_gyration_y, 124.6, 0.005);
+        assert_table(section.radius_of_gyration_z, 33.5, 0.005);
+        assert_table(section.torsion_constant, 20.12e4, 0.02);
+        assert_table(section.centroid_z, 150.0, 1e-9);
+        assert_table(section.weight_by_meter(STEEL_DENSITY), 42.2, 0.005);
+    }
+
+    #[test]
+    fn channel() {
+        // UPE 200
+        let dstv = Dstv::from_file("./tests/data/0008-SE0004.nc1").unwrap();
+        let section = dstv.header.section_properties().unwrap();
+        assert_table(section.area, 2900.0, 0.005);
+        assert_table(section.second_moment_y, 1909e4, 0.005);
+        assert_table(section.second_moment_z, 187.3e4, 0.005);
+        assert_table(section.plastic_modulus_y, 220.1e3, 0.005);
+        assert_table(section.centroid_y, 25.6, 0.005);
+    }
+
+    #[test]
+    fn hollow_sections() {
+        // SHS 100x100x6
+        let dstv = Dstv::from_file("./tests/data/0008-SE0009.nc1").unwrap();
+        let section = dstv.header.section_properties().unwrap();
+        assert_table(section.second_moment_y, section.second_moment_z, 1e-9);
+        assert_table(section.area, 2210.0, 0.01);
+        assert_table(section.torsion_constant, 498e4, 0.01);
+        // the gross weight follows from the section as the header has no weight by meter
+        assert_table(
+            dstv.gross_weight(STEEL_DENSITY),
+            section.weight_by_meter(STEEL_DENSITY),
+            1e-9,
+        );
+
+        let section =
+            SectionProperties::from_dimensions(&CodeProfile::RO, 168.3, 168.3, 6.3, 6.3, 0.0)
+                .unwrap();
+        assert_table(section.area, 3206.0, 0.005);
+        assert_table(section.second_moment_y, 1053e4, 0.005);
+        assert_table(section.plastic_modulus_y, 165.4e3, 0.005);
+        assert_table(section.torsion_constant, 2107e4, 0.005);
+    }
+
+    #[test]
+    fn catalogue_hollow_sections() {
+        let mut dstv = Dstv::from_file("./tests/data/0008-SE0009.nc1").unwrap();
+        dstv.header.code_profile = CodeProfile::SO;
+        dstv.header.profile = "RHS 200x100x8".to_string();
+        let catalogue = dstv.header.catalogue_section().unwrap();
+        assert_eq!(catalogue.radius, Some(12.0));
+        // the corner radius is kept, so the section weighs what the catalogue says
+        let section = dstv.header.section_properties().unwrap();
+        assert_table(
+            section.weight_by_meter(STEEL_DENSITY),
+            catalogue.weight_by_meter,
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn angle() {
+        let section =
+            SectionProperties::from_dimensions(&CodeProfile::L, 100.0, 100.0, 10.0, 10.0, 12.0)
+                .unwrap();
+        assert_table(section.area, 1920.0, 0.01);
+        assert_table(section.second_moment_y, 177e4, 0.025);
+        assert_table(section.centroid_z, 28.2, 0.01);
+        assert_table(section.centroid_y, section.centroid_z, 1e-9);
+    }
+
+    #[test]
+    fn plate() {
+        let dstv = Dstv::from_file("./tests/data/P2663.nc").unwrap();
+        let section = dstv.header.section_properties().unwrap();
+        assert_eq!(section.area, 550.0 * 20.0);
+        assert_table(section.second_moment_y, 20.0 * 550f64.powi(3) / 12.0, 1e-9);
+        assert_table(section.plastic_modulus_y, 20.0 * 550f64.powi(2) / 4.0, 1e-9);
+        assert_table(section.second_moment_z, 550.0 * 20f64.powi(3) / 12.0, 1e-9);
+    }
+
+    #[test]
+    fn special_profiles() {
+        // a cold formed Z-section, described by its header dimensions
+        let dstv = Dstv::from_file("./tests/data/RST37-2.nc").unwrap();
+        let section = dstv.header.section_properties().unwrap();
+        assert_table(section.area, 2.0 * 81.0 * 1.5 + 172.0 * 1.5, 1e-9);
+        assert_table(section.centroid_y, 81.0 - 0.75, 1e-9);
+        assert_table(section.centroid_z, 87.5, 1e-9);
+    }
+
+    #[test]
+    fn missing_dimensions() {
+        assert_eq!(
+            SectionProperties::from_dimensions(&CodeProfile::I, 300.0, 150.0, 7.1, 0.0, 15.0),
+            None
+        );
+        assert_eq!(
+            SectionProperties::from_dimensions(&CodeProfile::M, 0.0, 100.0, 6.0, 6.0, 0.0),
+            None
+        );
+    }
+}
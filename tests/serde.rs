@@ -0,0 +1,129 @@
+#[cfg(feature = "serde")]
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+    use serde_json::json;
+
+    #[test]
+    fn json_shape() {
+        let hole = DstvElementType::Hole(Hole {
+            diameter: Millimetres(22.0),
+            depth: Some(Millimetres(0.0)),
+            x_coord: Millimetres(100.0),
+            y_coord: Millimetres(50.0),
+            fl_code: PartFace::Top,
+        });
+        assert_eq!(
+            serde_json::to_value(&hole).unwrap(),
+            json!({
+                "type": "hole",
+                "diameter": 22.0,
+                "depth": 0.0,
+                "x_coord": 100.0,
+                "y_coord": 50.0,
+                "fl_code": "o"
+            })
+        );
+        let dstv = Dstv::from_file("./tests/data/P2663.nc").unwrap();
+        let value = serde_json::to_value(&dstv).unwrap();
+        assert_eq!(value["header"]["code_profile"], "B");
+        assert_eq!(value["header"]["saw_length"], 550.0);
+        assert_eq!(value["elements"][0]["type"], "outer_border");
+        assert_eq!(value["elements"][0]["contour"][0]["fl_code"], "v");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        for file in ["P2663.nc", "0008-SE0009.nc1", "RST37-2.nc"] {
+            let dstv = Dstv::from_file(format!("./tests/data/{file}")).unwrap();
+            let json = serde_json::to_string(&dstv).unwrap();
+            let parsed: Dstv = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, dstv);
+            // a part read back from JSON can be written to a new NC file
+            assert_eq!(Dstv::from_str(parsed.to_dstv()).unwrap(), dstv);
+        }
+    }
+
+    /// A weld seam, written by some vendors in an `XW` block
+    #[derive(Clone, Debug, PartialEq)]
+    struct Weld {
+        x_coord: f64,
+        length: f64,
+    }
+
+    impl DstvElement for Weld {
+        fn from_block(block: &Block) -> Result<Self, ParseDstvError> {
+            let values = block
+                .first_line()
+                .split_whitespace()
+                .skip(1)
+                .map(|value| value.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| ParseDstvError::from_err("Invalid weld", e))?;
+            match values.as_slice() {
+                [x_coord, length] => Ok(Self {
+                    x_coord: *x_coord,
+                    length: *length,
+                }),
+                _ => Err(ParseDstvError::new("Invalid weld")),
+            }
+        }
+
+        fn to_svg(&self) -> String {
+            String::new()
+        }
+
+        fn write(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+            write!(
+                out,
+                "XW\n  v {:>10.2} {:>10.2}\n",
+                self.x_coord, self.length
+            )
+        }
+
+        fn get_facing(&self) -> &PartFace {
+            &PartFace::Front
+        }
+    }
+
+    #[test]
+    fn custom_elements_round_trip() {
+        let registry = ElementRegistry::new().register_element::<Weld>("XW");
+        let source = std::fs::read_to_string("./tests/data/P2663.nc")
+            .unwrap()
+            .replace(
+                "\nEN",
+                "\nXW\n  v     100.00     250.00\nZZ\n  v vendor data\nEN",
+            );
+        let dstv = Dstv::from_str_with_registry(&source, &registry).unwrap();
+        assert!(matches!(
+            dstv.elements.last(),
+            Some(DstvElementType::Unknown(_))
+        ));
+
+        let json = serde_json::to_string(&dstv).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let elements = value["elements"].as_array().unwrap();
+        let custom = &elements[elements.len() - 2];
+        assert_eq!(custom["type"], "custom");
+        assert_eq!(custom["code"], "XW");
+        assert_eq!(custom["lines"], json!(["  v     100.00     250.00"]));
+        assert_eq!(elements[elements.len() - 1]["type"], "unknown");
+
+        // the custom element comes back as the block it writes, which the registry
+        // reads into the registered type again
+        let parsed: Dstv = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.to_dstv(), dstv.to_dstv());
+        assert_eq!(
+            Dstv::from_str_with_registry(parsed.to_dstv(), &registry).unwrap(),
+            dstv
+        );
+    }
+
+    #[test]
+    fn invalid_face() {
+        let json = r#"{"type": "hole", "diameter": 22.0, "depth": 0.0,
+            "x_coord": 100.0, "y_coord": 50.0, "fl_code": "x"}"#;
+        assert!(serde_json::from_str::<DstvElementType>(json).is_err());
+    }
+}
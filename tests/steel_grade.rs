@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use dstv::prelude::*;
+
+    #[test]
+    fn en_10025_grades() {
+        let grade = SteelGrade::from_str("S235JR").unwrap();
+        assert_eq!(grade.name(), "S235JR");
+        assert_eq!(grade.standard, SteelStandard::En10025);
+        assert_eq!(grade.yield_strength, 235.0);
+        assert_eq!(grade.quality, "JR");
+        assert_eq!(grade.delivery_condition, None);
+        assert!(!grade.legacy);
+
+        let grade = SteelGrade::from_str(" s355j2+n ").unwrap();
+        assert_eq!(grade.name(), "S355J2+N");
+        assert_eq!(
+            grade.delivery_condition,
+            Some(DeliveryCondition::Normalized)
+        );
+        assert_eq!(grade.yield_strength_at(10.0), 355.0);
+        assert_eq!(grade.yield_strength_at(20.0), 345.0);
+        assert_eq!(grade.density(), STEEL_DENSITY);
+
+        assert_eq!(SteelGrade::from_str("S460NL").unwrap().quality, "NL");
+        assert_eq!(
+            SteelGrade::from_str("S355K2+AR")
+                .unwrap()
+                .delivery_condition,
+            Some(DeliveryCondition::AsRolled)
+        );
+    }
+
+    #[test]
+    fn yield_strength_by_thickness() {
+        let strengths = |name: &str, thicknesses: &[f64]| {
+            let grade = SteelGrade::from_str(name).unwrap();
+            thicknesses
+                .iter()
+                .map(|t| grade.yield_strength_at(*t))
+                .collect::<Vec<_>>()
+        };
+        let thicknesses = [10.0, 30.0, 50.0, 70.0, 90.0, 120.0];
+        assert_eq!(
+            strengths("S235JR", &thicknesses),
+            vec![235.0, 225.0, 215.0, 215.0, 215.0, 195.0]
+        );
+        assert_eq!(
+            strengths("S275J0", &thicknesses),
+            vec![275.0, 265.0, 255.0, 245.0, 235.0, 225.0]
+        );
+        assert_eq!(
+            strengths("S355J2+N", &thicknesses),
+            vec![355.0, 345.0, 335.0, 325.0, 315.0, 295.0]
+        );
+        assert_eq!(
+            strengths("S460NL", &thicknesses),
+            vec![460.0, 440.0, 430.0, 410.0, 400.0, 380.0]
+        );
+        assert_eq!(
+            strengths("S355M", &thicknesses),
+            vec![355.0, 345.0, 335.0, 325.0, 325.0, 320.0]
+        );
+        assert_eq!(
+            strengths("S690QL", &thicknesses),
+            vec![690.0, 690.0, 690.0, 650.0, 650.0, 630.0]
+        );
+        assert_eq!(strengths("S355J2H", &[10.0, 30.0]), vec![355.0, 345.0]);
+        assert_eq!(
+            strengths("S235JR", &[16.0, 40.0, 300.0]),
+            vec![235.0, 225.0, 175.0]
+        );
+    }
+
+    #[test]
+    fn en_10219_grades() {
+        let grade = SteelGrade::from_str("S355J2H").unwrap();
+        assert_eq!(grade.standard, SteelStandard::En10219);
+        assert_eq!(grade.quality, "J2");
+        assert_eq!(grade.name(), "S355J2H");
+    }
+
+    #[test]
+    fn legacy_grades() {
+        let grade = SteelGrade::from_str("RST37-2").unwrap();
+        assert_eq!(grade.name(), "S235JR");
+        assert!(grade.legacy);
+        assert_eq!(SteelGrade::from_str("St 52-3").unwrap().name(), "S355J2+N");
+        assert_eq!(SteelGrade::from_str("Fe510D").unwrap().name(), "S355J2");
+        let grade = SteelGrade::from_str("S355J2G3").unwrap();
+        assert_eq!(grade.name(), "S355J2+N");
+        assert!(grade.legacy);
+    }
+
+    #[test]
+    fn astm_grades() {
+        let dstv = Dstv::from_file("./tests/data/P1.nc").unwrap();
+        let grade = dstv.header.steel_grade().unwrap();
+        assert_eq!(grade.name(), "A992");
+        assert_eq!(grade.standard, SteelStandard::Astm);
+        assert_eq!(grade.yield_strength_at(50.0), 345.0);
+    }
+
+    #[test]
+    fn grades_from_headers() {
+        let dstv = Dstv::from_file("./tests/data/P2663.nc").unwrap();
+        assert_eq!(dstv.header.steel_grade().unwrap().name(), "S355J2+N");
+        let dstv = Dstv::from_file("./tests/data/RST37-2.nc").unwrap();
+        assert_eq!(dstv.header.steel_grade().unwrap().name(), "S235JR");
+    }
+
+    #[test]
+    fn unknown_grades() {
+        assert_eq!(
+            SteelGrade::from_str("MS").err().unwrap().to_string(),
+            "Could not parse DSTV element: \"Unknown steel grade: MS\""
+        );
+        assert!(SteelGrade::from_str("S356JR").is_err());
+        assert!(SteelGrade::from_str("S355XY").is_err());
+        assert!(SteelGrade::from_str("S355J2+X").is_err());
+        let dstv = Dstv::from_file("./tests/data/product2.NC1").unwrap();
+        assert!(dstv.header.steel_grade().is_err());
+        assert_eq!(dstv.header.density(), STEEL_DENSITY);
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+    use std::fs;
+
+    const FILE: &str = "./tests/data/0008-SE0004.nc1";
+
+    /// Returns the lines that differ between two texts of the same length in lines
+    fn changed_lines<'a>(a: &'a str, b: &'a str) -> Vec<(&'a str, &'a str)> {
+        a.split('\n')
+            .zip(b.split('\n'))
+            .filter(|(a, b)| a != b)
+            .collect()
+    }
+
+    #[test]
+    fn files_are_written_back_unchanged() {
+        for entry in fs::read_dir("./tests/data").unwrap() {
+            let path = entry.unwrap().path();
+            let source = String::from_utf8_lossy(&fs::read(&path).unwrap()).to_string();
+            let tree = SyntaxTree::parse(&source);
+            assert_eq!(tree.to_string(), source, "{path:?}");
+            if let Ok(dstv) = Dstv::from_str(&source) {
+                assert_eq!(Dstv::from_syntax(&tree).unwrap(), dstv);
+            }
+        }
+    }
+
+    #[test]
+    fn lines_keep_comments_and_tokens() {
+        let source = fs::read_to_string(FILE).unwrap();
+        let tree = SyntaxTree::parse(&source);
+        assert_eq!(tree.comments().len(), 1);
+        assert!(tree.comments()[0].starts_with("**NC-DSTV"));
+        assert_eq!(tree.header_lines().len(), 24);
+        assert!(tree.lines().iter().all(|line| line.ending == "\r\n"));
+
+        let node = &tree.nodes()[0];
+        assert_eq!(node.code, "AK");
+        assert_eq!(node.lines.len(), 8);
+        assert!(matches!(
+            node.element,
+            Some(DstvElementType::OuterBorder(_))
+        ));
+        let line = &tree.lines()[node.lines[0]];
+        assert_eq!(line.kind, LineKind::Data);
+        assert_eq!(line.words(), vec!["v", "239.41o", "0.00", "0.00"]);
+        assert_eq!(line.tokens[1].kind, TokenKind::Number);
+        assert_eq!(line.tokens[1].span, Span { start: 5, end: 12 });
+    }
+
+    #[test]
+    fn moving_a_hole_changes_one_value() {
+        let source = fs::read_to_string(FILE).unwrap();
+        let mut tree = SyntaxTree::parse(&source);
+        let mut dstv = Dstv::from_syntax(&tree).unwrap();
+        let hole = dstv
+            .elements
+            .iter_mut()
+            .find_map(|element| match element {
+                DstvElementType::Hole(hole) if hole.x_coord == 35.0 && hole.y_coord == 130.0 => {
+                    Some(hole)
+                }
+                _ => None,
+            })
+            .unwrap();
+        hole.x_coord = Millimetres(40.5);
+        tree.apply(&dstv);
+
+        let written = tree.to_string();
+        assert_eq!(
+            changed_lines(&source, &written),
+            vec![(
+                "  v 35.00o    130.00    22.00    0.0    0.0    0.0\r",
+                "  v 40.50o    130.00    22.00    0.0    0.0    0.0\r"
+            )]
+        );
+        assert_eq!(Dstv::from_str(&written).unwrap(), dstv);
+    }
+
+    #[test]
+    fn values_keep_their_columns() {
+        let source = "ST\n  O1\n  D1\n  1\n  P1\n  S235JR\n  2\n  PL10*100\n  B\n   1000.00\n     10.00\n    100.00\n     10.00\n     10.00\n      0.00\n      7.85\n      0.00\n      0.00\n      0.00\n      0.00\n      0.00\n\n\n\n\n** holes for the cleat\nBO\n  v     50.00     50.00     22.00\n  v    950.00     50.00     22.00\nXX\n  kept as it is\nEN\n";
+        let mut tree = SyntaxTree::parse(source);
+        let mut dstv = Dstv::from_syntax(&tree).unwrap();
+        assert!(tree
+            .nodes()
+            .iter()
+            .any(|node| node.code == "XX"
+                && matches!(node.element, Some(DstvElementType::Unknown(_)))));
+
+        dstv.header.length = Millimetres(12500.0);
+        if let DstvElementType::Hole(hole) = &mut dstv.elements[1] {
+            hole.x_coord = Millimetres(1950.25);
+        }
+        tree.apply(&dstv);
+        let written = tree.to_string();
+        assert_eq!(
+            changed_lines(source, &written),
+            vec![
+                ("   1000.00", "  12500.00"),
+                (
+                    "  v    950.00     50.00     22.00",
+                    "  v   1950.25     50.00     22.00"
+                ),
+            ]
+        );
+        assert!(written.contains("** holes for the cleat\n"));
+        assert!(written.contains("XX\n  kept as it is\n"));
+    }
+
+    #[test]
+    fn elements_are_added_and_removed() {
+        let source = "ST\n  O1\n  D1\n  1\n  P1\n  S235JR\n  2\n  PL10*100\n  B\n   1000.00\n     10.00\n    100.00\n     10.00\n     10.00\n      0.00\n      7.85\n      0.00\n      0.00\n      0.00\n      0.00\n      0.00\n\n\n\n\nBO\n  v     50.00     50.00     22.00\n** the last hole\nBO\n  v    950.00     50.00     22.00\nEN";
+        let mut tree = SyntaxTree::parse(source);
+        let mut dstv = Dstv::from_syntax(&tree).unwrap();
+        dstv.elements.remove(1);
+        tree.apply(&dstv);
+        assert_eq!(
+            tree.to_string(),
+            source.replace("BO\n  v    950.00     50.00     22.00\n", "")
+        );
+
+        let hole = Hole::from_str("  v 500.00 50.00 18.00 0.00").unwrap();
+        dstv.elements.push(DstvElementType::Hole(hole));
+        tree.apply(&dstv);
+        let written = tree.to_string();
+        assert!(written.ends_with(
+            "  v     50.00     50.00     22.00\nBO\n  v    500.00     50.00     18.00      0.00\n** the last hole\nEN"
+        ));
+        assert_eq!(Dstv::from_str(&written).unwrap(), dstv);
+    }
+}
@@ -0,0 +1,263 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{assert_close, part, plate_header};
+    use dstv::prelude::*;
+
+    fn plate() -> Dstv {
+        part(plate_header("FP1", 300.0, 200.0).end_cuts(0.0, 0.0, 10.0, 20.0))
+            .add_hole(PartFace::Front, 100.0, 50.0, 22.0)
+            .add_slot(PartFace::Front, 200.0, 120.0, 18.0, 40.0, 10.0, 30.0)
+            .add_mark(PartFace::Front, 150.0, 100.0, "FP1")
+            .build()
+            .unwrap()
+    }
+
+    fn holes(dstv: &Dstv) -> Vec<(PartFace, f64, f64)> {
+        dstv.elements
+            .iter()
+            .filter_map(|element| match element {
+                DstvElementType::Hole(e) => Some((e.fl_code.clone(), *e.x_coord, *e.y_coord)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn slot(dstv: &Dstv) -> Slot {
+        dstv.elements
+            .iter()
+            .find_map(|element| match element {
+                DstvElementType::Slot(e) => Some(e.clone()),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn mirror_end_to_end() {
+        let dstv = plate();
+        let mirrored = dstv.mirror(MirrorPlane::EndToEnd).unwrap();
+        assert_eq!(holes(&mirrored), vec![(PartFace::Front, 200.0, 50.0)]);
+        let (before, after) = (slot(&dstv).centroid(), slot(&mirrored).centroid());
+        assert_close(after.x, 300.0 - before.x);
+        assert_close(after.y, before.y);
+        assert_close(slot(&mirrored).area(), slot(&dstv).area());
+        assert_eq!(mirrored.header.flange_start_cut, 20.0);
+        assert_eq!(mirrored.header.flange_end_cut, 10.0);
+        assert_close(
+            mirrored.net_area(&PartFace::Front),
+            dstv.net_area(&PartFace::Front),
+        );
+        assert!(mirrored.validate().is_empty());
+        assert_eq!(
+            mirrored.mirror(MirrorPlane::EndToEnd).unwrap().to_dstv(),
+            dstv.to_dstv()
+        );
+    }
+
+    #[test]
+    fn mirror_plate_about_its_axis() {
+        let dstv = plate();
+        let mirrored = dstv.mirror(MirrorPlane::Horizontal).unwrap();
+        assert_eq!(holes(&mirrored), vec![(PartFace::Front, 100.0, 150.0)]);
+        let (before, after) = (slot(&dstv).centroid(), slot(&mirrored).centroid());
+        assert_close(after.x, before.x);
+        assert_close(after.y, 200.0 - before.y);
+        assert_eq!(mirrored.mirror(MirrorPlane::Vertical).unwrap(), mirrored);
+    }
+
+    #[test]
+    fn reverse_plate() {
+        let dstv = plate();
+        let reversed = dstv.reverse().unwrap();
+        assert_eq!(holes(&reversed), vec![(PartFace::Front, 200.0, 150.0)]);
+        let (before, after) = (slot(&dstv).centroid(), slot(&reversed).centroid());
+        assert_close(after.x, 300.0 - before.x);
+        assert_close(after.y, 200.0 - before.y);
+        assert_close(*slot(&reversed).angle, 210.0);
+        assert_eq!(reversed.reverse().unwrap().to_dstv(), dstv.to_dstv());
+    }
+
+    #[test]
+    fn mirror_profile() {
+        let dstv = Dstv::from_file("./tests/data/0008-SE0008.nc1").unwrap();
+        let mirrored = dstv.mirror(MirrorPlane::Horizontal).unwrap();
+        assert_eq!(mirrored.elements.len(), dstv.elements.len());
+        for (before, after) in dstv.elements.iter().zip(&mirrored.elements) {
+            let expected = match before.get_facing() {
+                PartFace::Top => PartFace::Bottom,
+                PartFace::Bottom => PartFace::Top,
+                face => face.clone(),
+            };
+            assert_eq!(after.get_facing(), &expected);
+        }
+        for face in [PartFace::Front, PartFace::Top, PartFace::Bottom] {
+            assert_close(mirrored.net_area(&face), dstv.net_area(&face));
+        }
+        let centre = dstv.centre_of_gravity().unwrap();
+        let mirrored_centre = mirrored.centre_of_gravity().unwrap();
+        assert_close(mirrored_centre.x, centre.x);
+        assert_close(mirrored_centre.y, 300.0 - centre.y);
+
+        let reversed = dstv.reverse().unwrap();
+        let reversed_centre = reversed.centre_of_gravity().unwrap();
+        assert_close(reversed_centre.x, 954.5 - centre.x);
+        assert_close(reversed_centre.y, centre.y);
+        assert_eq!(
+            reversed.validate().len(),
+            dstv.validate().len(),
+            "reversing must not introduce findings"
+        );
+    }
+
+    #[test]
+    fn mirror_across_missing_face() {
+        let header = HeaderBuilder::new()
+            .piece_identification("A1")
+            .length(500.0)
+            .profile("L100x100x10", CodeProfile::L)
+            .dimensions(100.0, 100.0, 10.0, 10.0, 12.0)
+            .build()
+            .unwrap();
+        let dstv = DstvBuilder::new(header)
+            .add_hole(PartFace::Bottom, 50.0, 50.0, 18.0)
+            .build()
+            .unwrap();
+        assert!(dstv.mirror(MirrorPlane::Horizontal).is_err());
+        // the leg would point the other way when mirrored through the web
+        assert!(dstv.mirror(MirrorPlane::Vertical).is_err());
+    }
+
+    #[test]
+    fn reverse_channel() {
+        let header = HeaderBuilder::new()
+            .piece_identification("C1")
+            .length(1000.0)
+            .profile("UPN200", CodeProfile::U)
+            .dimensions(200.0, 75.0, 11.5, 8.5, 11.5)
+            .end_cuts(10.0, 0.0, 0.0, 0.0)
+            .build()
+            .unwrap();
+        let dstv = DstvBuilder::new(header)
+            .add_hole(PartFace::Front, 100.0, 50.0, 22.0)
+            .add_hole(PartFace::Top, 100.0, 40.0, 18.0)
+            .build()
+            .unwrap();
+        assert!(dstv.mirror(MirrorPlane::Vertical).is_err());
+        let reversed = dstv.reverse().unwrap();
+        assert_eq!(
+            holes(&reversed),
+            vec![
+                (PartFace::Front, 900.0, 150.0),
+                (PartFace::Bottom, 900.0, 40.0)
+            ]
+        );
+        assert_eq!(reversed.header.web_end_cut, -10.0);
+        assert_eq!(reversed.reverse().unwrap().to_dstv(), dstv.to_dstv());
+    }
+
+    #[test]
+    fn reverse_angle() {
+        let angle = |profile: &str, width: f64| {
+            let header = HeaderBuilder::new()
+                .piece_identification("A1")
+                .length(500.0)
+                .profile(profile, CodeProfile::L)
+                .dimensions(100.0, width, 10.0, 10.0, 12.0)
+                .build()
+                .unwrap();
+            DstvBuilder::new(header)
+                .add_hole(PartFace::Front, 50.0, 60.0, 18.0)
+                .add_hole(PartFace::Bottom, 150.0, 40.0, 18.0)
+                .build()
+                .unwrap()
+        };
+        let dstv = angle("L100x100x10", 100.0);
+        let reversed = dstv.reverse().unwrap();
+        // the legs change places when an equal angle is turned end to end
+        assert_eq!(
+            holes(&reversed),
+            vec![
+                (PartFace::Bottom, 450.0, 60.0),
+                (PartFace::Front, 350.0, 40.0)
+            ]
+        );
+        assert_eq!(reversed.reverse().unwrap().to_dstv(), dstv.to_dstv());
+        assert!(angle("L100x75x10", 75.0).reverse().is_err());
+        // the length of an unequal angle can still be adjusted at its start
+        let trimmed = angle("L100x75x10", 75.0)
+            .adjust_length(480.0, PartEnd::Start)
+            .unwrap();
+        assert_eq!(
+            holes(&trimmed),
+            vec![
+                (PartFace::Front, 30.0, 60.0),
+                (PartFace::Bottom, 130.0, 40.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn adjust_length() {
+        let dstv = plate();
+        let extended = dstv.adjust_length(400.0, PartEnd::End).unwrap();
+        assert_eq!(extended.header.length, 400.0);
+        assert_eq!(holes(&extended), holes(&dstv));
+        assert_close(
+            extended.net_area(&PartFace::Front),
+            dstv.net_area(&PartFace::Front) + 100.0 * 200.0,
+        );
+        assert!(extended.validate().is_empty());
+
+        let trimmed = dstv.adjust_length(250.0, PartEnd::Start).unwrap();
+        assert_eq!(holes(&trimmed), vec![(PartFace::Front, 50.0, 50.0)]);
+        assert_close(*slot(&trimmed).x_coord, 150.0);
+        assert_close(
+            trimmed.net_area(&PartFace::Front),
+            dstv.net_area(&PartFace::Front) - 50.0 * 200.0,
+        );
+        assert!(trimmed.validate().is_empty());
+
+        // trimming through the hole or the slot is not possible
+        assert!(dstv.adjust_length(200.0, PartEnd::Start).is_err());
+        assert!(dstv.adjust_length(240.0, PartEnd::End).is_err());
+        assert!(dstv.adjust_length(0.0, PartEnd::End).is_err());
+    }
+
+    #[test]
+    fn adjust_length_keeps_the_end_cut() {
+        // the angle of the mitre at the end of the front face
+        let mitre = |dstv: &Dstv| {
+            let contour = dstv
+                .elements
+                .iter()
+                .find_map(|element| match element {
+                    DstvElementType::OuterBorder(e) if e.get_facing() == &PartFace::Front => {
+                        Some(e.contour.clone())
+                    }
+                    _ => None,
+                })
+                .unwrap();
+            let end = |y: f64| {
+                contour
+                    .iter()
+                    .filter(|p| *p.y_coord == y)
+                    .map(|p| *p.x_coord)
+                    .fold(0.0, f64::max)
+            };
+            ((end(200.0) - end(0.0)) / 200.0).atan().to_degrees()
+        };
+        let dstv = Dstv::from_file("./tests/data/0008-SE0004.nc1").unwrap();
+        assert!((mitre(&dstv) - 22.5).abs() < 0.01);
+        for length in [422.25, 300.0] {
+            let adjusted = dstv.adjust_length(length, PartEnd::End).unwrap();
+            assert_close(mitre(&adjusted), mitre(&dstv));
+            assert_eq!(holes(&adjusted), holes(&dstv));
+        }
+        let extended = dstv.adjust_length(422.25, PartEnd::Start).unwrap();
+        assert_close(mitre(&extended), mitre(&dstv));
+        // the mitre cannot move over the holes at the start
+        assert!(dstv.adjust_length(100.0, PartEnd::End).is_err());
+    }
+}
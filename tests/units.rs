@@ -0,0 +1,68 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::assert_close;
+    use dstv::prelude::*;
+
+    #[test]
+    fn conversions() {
+        assert_close(Millimetres(254.0).inches(), 10.0);
+        assert_close(Millimetres(3048.0).feet(), 10.0);
+        assert_close(*Millimetres::from_inches(2.0), 50.8);
+        assert_close(Degrees(180.0).radians(), std::f64::consts::PI);
+        assert_close(KilogramsPerMetre(1.488_163_94).pounds_per_foot(), 1.0);
+        assert_close(SquareMetresPerMetre(0.3048).square_feet_per_foot(), 1.0);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let length = Millimetres(1000.0);
+        assert_eq!(length - Millimetres(250.0), Millimetres(750.0));
+        assert_eq!(length / 4.0, 250.0);
+        assert_eq!(2.0 * length, Millimetres(2000.0));
+        assert_eq!(length / Millimetres(500.0), 2.0);
+        assert!(length > 999.0);
+        assert_eq!(length.to_string(), "1000");
+        assert_eq!(
+            [Millimetres(1.5), Millimetres(2.5)]
+                .iter()
+                .copied()
+                .sum::<Millimetres>(),
+            Millimetres(4.0)
+        );
+    }
+
+    #[test]
+    fn header_in_us_units() {
+        let dstv = Dstv::from_file("./tests/data/0008-SE0008.nc1").unwrap();
+        let header = &dstv.header;
+        assert_close(header.length.inches(), *header.length / 25.4);
+        assert_close(
+            header.weight_by_meter.pounds_per_foot(),
+            *header.weight_by_meter * 0.671_968_975,
+        );
+        assert!(header.saw_length.is_none());
+    }
+
+    #[test]
+    fn missing_depth_is_not_zero() {
+        let through = Hole::from_str("v 100 50 22").unwrap();
+        assert_eq!(through.depth, None);
+        assert_eq!(through.to_dstv(), "BO\n  v    100.00     50.00     22.00\n");
+
+        let zero = Hole::from_str("v 100 50 22 0").unwrap();
+        assert_eq!(zero.depth, Some(Millimetres(0.0)));
+        assert_eq!(
+            zero.to_dstv(),
+            "BO\n  v    100.00     50.00     22.00      0.00\n"
+        );
+
+        let blind = Hole::from_str("v 100 50 22 8").unwrap();
+        assert_eq!(blind.depth, Some(Millimetres(8.0)));
+        assert_eq!(blind.depth_or_zero(), blind.depth.unwrap());
+        assert_eq!(through.depth_or_zero(), zero.depth_or_zero());
+        // an unreadable depth keeps the hole, without a depth
+        assert_eq!(Hole::from_str("v 100 50 22 deep").unwrap().depth, None);
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+
+    /// Builds a 200x100x10 plate with the given blocks after the header
+    fn plate(blocks: &str) -> Dstv {
+        let header = "ST\n  ORDER\n  DRAWING\n  PHASE\n  PIECE\n  S235JR\n  1\n  PL10*100\n  B\n  200.00\n  100.00\n  10.00\n  10.00\n  10.00\n  0.00\n  7.85\n  0.20\n  0.00\n  0.00\n  0.00\n  0.00\n  \n  \n  \n  \n";
+        Dstv::from_str(format!("{header}{blocks}EN\n")).unwrap()
+    }
+
+    const RECTANGLE: &str = "AK\n  v 0.00 0.00 0.00\n    200.00 0.00 0.00\n    200.00 100.00 0.00\n    0.00 100.00 0.00\n    0.00 0.00 0.00\n";
+
+    fn rules(findings: &[Finding]) -> Vec<&'static str> {
+        findings.iter().map(|finding| finding.rule).collect()
+    }
+
+    #[test]
+    fn valid_plate_has_no_findings() {
+        let dstv = plate(&format!(
+            "{RECTANGLE}BO\n  v 50.00 50.00 18.00\n  v 150.00 50.00 18.00\n"
+        ));
+        assert_eq!(dstv.validate(), vec![]);
+    }
+
+    #[test]
+    fn zero_length_segment() {
+        let dstv = Dstv::from_file("./tests/data/P465.nc").unwrap();
+        let findings = dstv.validate();
+        // the end of the first slot lies 24 mm from the start of the plate
+        assert_eq!(rules(&findings), vec!["contour", "edge-distance"]);
+        assert_eq!(findings[0].rule, "contour");
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].face, Some(PartFace::Front));
+        assert_eq!(findings[0].element, Some(0));
+        // a new validator holds all built-in rules, an empty one none
+        assert_eq!(Validator::new().validate(&dstv), findings);
+        assert_eq!(Validator::empty().validate(&dstv), vec![]);
+    }
+
+    #[test]
+    fn open_and_self_intersecting_contours() {
+        let dstv = plate("AK\n  v 0.00 0.00 0.00\n    200.00 100.00 0.00\n    200.00 0.00 0.00\n    0.00 100.00 0.00\n");
+        let findings = Validator::empty().with_rule(ContourRule).validate(&dstv);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].message, "contour intersects itself");
+        assert_eq!(findings[1].severity, Severity::Warning);
+        assert!(findings[1].message.starts_with("contour is not closed"));
+    }
+
+    #[test]
+    fn holes_outside_border_and_inside_inner_border() {
+        let dstv = plate(&format!(
+            "{RECTANGLE}IK\n  v 80.00 30.00 0.00\n    120.00 30.00 0.00\n    120.00 70.00 0.00\n    80.00 70.00 0.00\n    80.00 30.00 0.00\nBO\n  v 100.00 50.00 10.00\n  v 250.00 50.00 10.00\n"
+        ));
+        let findings = Validator::empty()
+            .with_rule(HolePlacementRule)
+            .validate(&dstv);
+        assert_eq!(rules(&findings), vec!["hole-placement", "hole-placement"]);
+        assert!(findings[0].message.ends_with("lies inside an inner border"));
+        assert!(findings[1]
+            .message
+            .ends_with("lies outside the outer border"));
+        assert_eq!(
+            rules(&Validator::empty().with_rule(LengthRule).validate(&dstv)),
+            vec!["length"]
+        );
+    }
+
+    #[test]
+    fn overlapping_holes() {
+        let dstv = plate(&format!(
+            "{RECTANGLE}BO\n  v 50.00 50.00 18.00\n  v 60.00 50.00 18.00\n"
+        ));
+        let findings = Validator::empty()
+            .with_rule(OverlappingHolesRule)
+            .validate(&dstv);
+        assert_eq!(rules(&findings), vec!["overlapping-holes"]);
+        assert_eq!(findings[0].element, Some(2));
+    }
+
+    #[test]
+    fn slots_are_checked_by_their_outline() {
+        // the slots overlap along their length, although their centres are 50 mm apart
+        let dstv = plate(&format!(
+            "{RECTANGLE}BO\n  v 30.00 30.00 10.00 0.00 60.00 0.00 0.00\n  v 80.00 38.00 10.00 0.00 60.00 0.00 0.00\n"
+        ));
+        let findings = Validator::empty()
+            .with_rule(OverlappingHolesRule)
+            .validate(&dstv);
+        assert_eq!(rules(&findings), vec!["overlapping-holes"]);
+        assert!(findings[0].message.starts_with("slot at"));
+
+        // the centre of the slot lies on the plate, its end does not
+        let dstv = plate(&format!(
+            "{RECTANGLE}BO\n  v 150.00 50.00 10.00 0.00 60.00 0.00 0.00\n"
+        ));
+        let findings = Validator::empty()
+            .with_rule(HolePlacementRule)
+            .with_rule(EdgeDistanceRule::default())
+            .validate(&dstv);
+        assert_eq!(rules(&findings), vec!["hole-placement", "edge-distance"]);
+        assert!(findings[1].message.contains("is 0.00 from an edge"));
+    }
+
+    #[test]
+    fn configurable_edge_distance() {
+        let dstv = Dstv::from_file("./tests/data/P2663.nc").unwrap();
+        let findings = Validator::empty()
+            .with_rule(EdgeDistanceRule::default())
+            .validate(&dstv);
+        assert_eq!(findings.len(), 6);
+        assert!(findings.iter().all(|f| f.severity == Severity::Warning));
+        let findings = Validator::empty()
+            .with_rule(EdgeDistanceRule { factor: 1.2 })
+            .validate(&dstv);
+        assert_eq!(findings, vec![]);
+    }
+
+    #[test]
+    fn elements_on_missing_faces() {
+        let dstv = plate(&format!("{RECTANGLE}BO\n  o 50.00 5.00 6.00\n"));
+        let findings = Validator::empty().with_rule(FaceRule).validate(&dstv);
+        assert_eq!(rules(&findings), vec!["face"]);
+        assert_eq!(findings[0].face, Some(PartFace::Top));
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use dstv::prelude::*;
+
+    const FILES: &[&str] = &[
+        "0008-PL0001.NC1",
+        "0008-SE0004.nc1",
+        "0008-SE0008.nc1",
+        "0008-SE0009.nc1",
+        "P1.nc",
+        "P1565.nc",
+        "P1719.nc",
+        "P1728.nc",
+        "P1730.nc",
+        "P2663.nc",
+        "P2683.nc",
+        "P2684.nc",
+        "P465.nc",
+        "RST37-2.nc",
+        "product2.NC1",
+        "product3.NC1",
+    ];
+
+    #[test]
+    fn round_trip() {
+        for file in FILES {
+            let dstv = Dstv::from_file(format!("./tests/data/{file}")).unwrap();
+            let written = dstv.to_dstv();
+            let parsed = Dstv::from_str(&written).unwrap();
+            assert_eq!(parsed, dstv, "{file} changed when written:\n{written}");
+            assert_eq!(parsed.to_dstv(), written);
+        }
+    }
+
+    #[test]
+    fn writes_blocks() {
+        let dstv = Dstv::from_file("./tests/data/P2663.nc").unwrap();
+        let written = dstv.to_dstv();
+        let lines = written.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "ST");
+        assert_eq!(lines[1], "  TEST_0001");
+        assert_eq!(lines[5], "  S355J2+N");
+        assert_eq!(lines[8], "  B");
+        assert_eq!(lines[9], "     550.00,550.00");
+        assert_eq!(lines[25], "AK");
+        assert_eq!(lines[26], "  v      0.00    275.00    275.00");
+        assert!(written.contains("BO\n  v     45.00    275.00     33.00\n"));
+        assert_eq!(lines.last(), Some(&"EN"));
+    }
+
+    #[test]
+    fn writes_elements() {
+        let hole = Hole {
+            diameter: Millimetres(22.0),
+            depth: None,
+            x_coord: Millimetres(100.0),
+            y_coord: Millimetres(-0.0),
+            fl_code: PartFace::Top,
+        };
+        assert_eq!(hole.to_dstv(), "BO\n  o    100.00      0.00     22.00\n");
+        let slot = Slot::from_str("v 50 60 18 0 30 0 0").unwrap();
+        assert_eq!(
+            slot.to_dstv(),
+            "BO\n  v     50.00     60.00     18.00      0.00     30.00      0.00      0.00\n"
+        );
+        let numeration = Numeration::from_str("v 10 20 0 10 P1").unwrap();
+        assert_eq!(
+            numeration.to_dstv(),
+            "SI\n  v     10.00     20.00      0.00     10.00 P1\n"
+        );
+    }
+}